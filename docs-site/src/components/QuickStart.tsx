@@ -15,7 +15,7 @@ let resolved = adapter
 let (event_type, _correlation, payload) = resolved?;
 
 let closed_status = match &payload {
-    EventPayload::DcaClosed { status } => Some(*status),
+    EventPayload::DcaClosed { status, .. } | EventPayload::Closed { status } => Some(*status),
     EventPayload::KaminoDisplay { terminal_status, .. } => *terminal_status,
     _ => None,
 };
@@ -9,6 +9,8 @@ const EXAMPLE_JSONS: Record<ProtocolId, string> = {
   limitV2: '{"CreateOrderEvent": {"order_key": "ABC123"}}',
   kamino:
     '{"OrderDisplayEvent": {"remaining_input_amount": 0, "filled_output_amount": 11744711, "status": 1}}',
+  valueAveraging:
+    '{"FilledEvent": {"value_average": "7xVa…", "in_amount": 250000, "out_amount": 1234}}',
 };
 
 export default function VariantLookup() {
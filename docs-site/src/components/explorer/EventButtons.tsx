@@ -21,9 +21,15 @@ const TRANSITION_LABELS: Record<EventType, string> = {
   FeeCollected: 'MetadataOnly',
   Withdrawn: 'MetadataOnly',
   Deposited: 'MetadataOnly',
+  Diagnostic: 'MetadataOnly',
 };
 
-const METADATA_TYPES = new Set<EventType>(['FeeCollected', 'Withdrawn', 'Deposited']);
+const METADATA_TYPES = new Set<EventType>([
+  'FeeCollected',
+  'Withdrawn',
+  'Deposited',
+  'Diagnostic',
+]);
 
 function buildEventGroups(protocol: ProtocolId) {
   const config = PROTOCOLS[protocol];
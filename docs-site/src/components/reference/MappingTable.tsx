@@ -15,6 +15,7 @@ const TYPE_COLORS: Record<EventType, string> = {
   FeeCollected: 'text-dim',
   Withdrawn: 'text-dim',
   Deposited: 'text-dim',
+  Diagnostic: 'text-dim',
 };
 
 export default function MappingTable({ title, entries }: Props) {
@@ -11,9 +11,10 @@ export type EventType =
   | 'Closed'
   | 'FeeCollected'
   | 'Withdrawn'
-  | 'Deposited';
+  | 'Deposited'
+  | 'Diagnostic';
 
-export type ProtocolId = 'dca' | 'limitV1' | 'limitV2' | 'kamino';
+export type ProtocolId = 'dca' | 'limitV1' | 'limitV2' | 'kamino' | 'valueAveraging';
 
 export const EVENT_TYPE_ORDER: EventType[] = [
   'Created',
@@ -25,6 +26,7 @@ export const EVENT_TYPE_ORDER: EventType[] = [
   'FeeCollected',
   'Withdrawn',
   'Deposited',
+  'Diagnostic',
 ];
 
 export interface ProtocolConfig {
@@ -57,7 +59,10 @@ const UI_METADATA: Record<
     notes: [
       'ClosedEvent terminal status derived from user_closed + unfilled_amount fields',
       'Priority: user_closed → Cancelled, unfilled_amount == 0 → Completed, else → Expired',
-      'Transfer, Deposit, Withdraw, and WithdrawFees instructions are ignored by classification',
+      'Transfer is a keeper payout of output tokens (Balance category), not an ownership change',
+      'OpenedEvent carries the schedule (DcaCreated payload) when cycle_frequency, in_amount_per_cycle, and in_deposited are present',
+      'DcaCreated.dca_version is 2 when the OpenedEvent carries the OpenDcaV2-only payer field, otherwise 1',
+      'Deposit, Withdraw, and WithdrawFees instructions are ignored by classification',
     ],
   },
   limit_v1: {
@@ -88,11 +93,23 @@ const UI_METADATA: Record<
     notes: [
       'OrderDisplayEvent has no order PDA — requires pre_fetched_order_pdas from instruction accounts',
       'Returns Uncorrelated if PDAs missing',
-      'UserSwapBalancesEvent is diagnostic-only (NotRequired correlation → MetadataOnly transition)',
+      'UserSwapBalancesEvent maps to Diagnostic (NotRequired correlation → MetadataOnly transition)',
       'Status codes: 0=Open, 1=Filled(Completed), 2=Cancelled, 3=Expired',
+      'WithdrawHostTip maps to FeeCollected (HostTip payload; amount supplied via decoded args)',
+      'LogUserSwapBalances maps to Diagnostic (SwapBalances payload; pre/post supplied via decoded args)',
       'Admin instructions (InitializeGlobalConfig, etc.) are ignored by classification',
     ],
   },
+  value_averaging: {
+    id: 'valueAveraging',
+    label: 'Jupiter VA',
+    sub: 'Value Averaging',
+    notes: [
+      'Same event vocabulary as DCA, keyed by value_average instead of dca_key',
+      'ClosedEvent terminal status derived from user_closed + unfilled_amount, as for DCA',
+      'Deposit, Withdraw, and WithdrawFees instructions are ignored by classification',
+    ],
+  },
 };
 
 function buildProtocols(): Record<ProtocolId, ProtocolConfig> {
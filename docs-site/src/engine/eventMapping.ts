@@ -18,11 +18,15 @@ export function eventTypeToTransition(
 export function transitionToString(transition: LifecycleTransition): string {
   const closeStatus =
     transition.type === 'Close' ? transition.status : undefined;
-  return wasmTransitionToString(transition.type, closeStatus);
+  const reason =
+    transition.type === 'MetadataOnly' ? transition.reason : undefined;
+  return wasmTransitionToString(transition.type, closeStatus, reason);
 }
 
 export function transitionTarget(transition: LifecycleTransition): string | null {
   const closeStatus =
     transition.type === 'Close' ? transition.status : undefined;
-  return wasmTransitionTarget(transition.type, closeStatus) ?? null;
+  const reason =
+    transition.type === 'MetadataOnly' ? transition.reason : undefined;
+  return wasmTransitionTarget(transition.type, closeStatus, reason) ?? null;
 }
@@ -8,13 +8,20 @@ import {
 
 export type TerminalStatus = 'completed' | 'cancelled' | 'expired';
 
+export type MetadataReason =
+  | 'fee'
+  | 'deposit'
+  | 'withdraw'
+  | 'diagnostic'
+  | 'close_without_status';
+
 export type LifecycleTransition =
   | { type: 'Create' }
   | { type: 'FillDelta' }
   | { type: 'Close'; status: TerminalStatus }
-  | { type: 'MetadataOnly' };
+  | { type: 'MetadataOnly'; reason?: MetadataReason };
 
-export type TransitionDecision = 'Apply' | 'IgnoreTerminalViolation';
+export type TransitionDecision = 'Apply' | 'IgnoreTerminalViolation' | 'IgnoreNoChange';
 
 export interface SnapshotDelta {
   delta: number;
@@ -31,10 +38,13 @@ export function decideTransition(
 ): TransitionDecision {
   const closeStatus =
     transition.type === 'Close' ? transition.status : undefined;
+  const reason =
+    transition.type === 'MetadataOnly' ? transition.reason : undefined;
   return wasmDecideTransition(
     currentStatus,
     transition.type,
     closeStatus,
+    reason,
   ) as TransitionDecision;
 }
 
@@ -7,6 +7,7 @@ export interface VariantLookupResult {
   eventType: EventType;
   transition: string;
   decisionFromNone: string;
+  target: string | null;
   notes: string[];
 }
 
@@ -34,5 +35,7 @@ function protocolIdToWasm(id: ProtocolId): string {
       return 'limitV2';
     case 'kamino':
       return 'kamino';
+    case 'valueAveraging':
+      return 'valueAveraging';
   }
 }
@@ -2,20 +2,26 @@ import {
   decide_transition as wasmDecideTransition,
   get_all_protocols as wasmGetAllProtocols,
   is_terminal as wasmIsTerminal,
+  is_supported_program_id as wasmIsSupportedProgramId,
   lookup_variant as wasmLookupVariant,
   normalize_snapshot as wasmNormalizeSnapshot,
+  replay as wasmReplay,
   wasm_event_type_to_transition as wasmEventTypeToTransition,
   wasm_transition_to_string as wasmTransitionToString,
   wasm_transition_target as wasmTransitionTarget,
+  wasm_terminal_display_class as wasmTerminalDisplayClass,
 } from '../wasm-pkg/defi_tracker_lifecycle';
 
 export {
   wasmDecideTransition,
   wasmGetAllProtocols,
   wasmIsTerminal,
+  wasmIsSupportedProgramId,
   wasmLookupVariant,
   wasmNormalizeSnapshot,
+  wasmReplay,
   wasmEventTypeToTransition,
   wasmTransitionToString,
   wasmTransitionTarget,
+  wasmTerminalDisplayClass,
 };
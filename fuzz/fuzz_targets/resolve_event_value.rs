@@ -0,0 +1,26 @@
+#![no_main]
+
+use defi_tracker_lifecycle::{Protocol, ResolveContext, resolve_event_value};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(fields) = serde_json::from_slice::<serde_json::Value>(data) else {
+        return;
+    };
+    let contexts = [
+        ResolveContext::default(),
+        ResolveContext {
+            pre_fetched_order_pdas: Some(vec![]),
+            ..ResolveContext::default()
+        },
+        ResolveContext {
+            pre_fetched_order_pdas: Some(vec!["order".to_string()]),
+            ..ResolveContext::default()
+        },
+    ];
+    for &protocol in Protocol::ALL {
+        for ctx in &contexts {
+            let _ = resolve_event_value(protocol, &fields, ctx);
+        }
+    }
+});
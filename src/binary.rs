@@ -0,0 +1,120 @@
+use bincode::Options;
+
+use crate::error::Error;
+use crate::lifecycle::adapters::ResolvedEvent;
+
+/// Fixed-width little-endian integers; decoding rejects trailing bytes.
+fn options() -> impl Options {
+    bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .reject_trailing_bytes()
+}
+
+/// Encodes a [`ResolvedEvent`] with `bincode` for compact cross-service transport.
+///
+/// Enums are written by variant index, so the layout is stable as long as variants are only
+/// ever appended. Both ends must run a compatible crate version.
+pub fn to_bytes(event: &ResolvedEvent) -> Result<Vec<u8>, Error> {
+    options().serialize(event).map_err(|e| Error::Parse {
+        reason: format!("binary encode failed: {e}"),
+    })
+}
+
+/// Decodes bytes produced by [`to_bytes`] back into a [`ResolvedEvent`].
+///
+/// Truncated input, trailing garbage, or unknown variant indices yield [`Error::Parse`].
+pub fn from_bytes(bytes: &[u8]) -> Result<ResolvedEvent, Error> {
+    options().deserialize(bytes).map_err(|e| Error::Parse {
+        reason: format!("binary decode failed: {e}"),
+    })
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used, reason = "test assertions")]
+mod tests {
+    use super::*;
+    use crate::lifecycle::TerminalStatus;
+    use crate::lifecycle::adapters::{CorrelationOutcome, EventPayload};
+    use crate::protocols::EventType;
+
+    fn round_trip(event: &ResolvedEvent) {
+        let bytes = to_bytes(event).unwrap();
+        assert_eq!(&from_bytes(&bytes).unwrap(), event);
+    }
+
+    #[test]
+    fn every_payload_variant_round_trips() {
+        let payloads = [
+            EventPayload::None,
+            EventPayload::DcaFill {
+                in_amount: i64::MAX,
+                out_amount: 7,
+                saturated: true,
+                cycle: Some(12),
+            },
+            EventPayload::DcaClosed {
+                status: TerminalStatus::Expired,
+                total_in_withdrawn: 400,
+                total_out_withdrawn: 9_737_035_287_242_015,
+            },
+            EventPayload::LimitFill {
+                in_amount: 100,
+                out_amount: 50,
+                remaining_in_amount: 0,
+                counterparty: "taker".to_string(),
+                maker: Some("maker".to_string()),
+                saturated: false,
+                remaining_out_amount: 25,
+                remaining_anomaly: true,
+            },
+            EventPayload::KaminoDisplay {
+                remaining_input_amount: 0,
+                filled_output_amount: 42,
+                terminal_status: Some(TerminalStatus::Completed),
+                saturated: false,
+            },
+            EventPayload::HostTip { amount: 9 },
+            EventPayload::DcaCreated {
+                cycle_frequency: 60,
+                in_amount_per_cycle: 100,
+                in_deposited: 500,
+                saturated: false,
+                dca_version: 2,
+            },
+            EventPayload::SwapBalances { pre: 10, post: 12 },
+            EventPayload::Closed {
+                status: TerminalStatus::Cancelled,
+            },
+        ];
+        let correlations = [
+            CorrelationOutcome::NotRequired,
+            CorrelationOutcome::Correlated(vec!["pda1".to_string(), "pda2".to_string()]),
+            CorrelationOutcome::Uncorrelated {
+                reason: "pdas not looked up".to_string(),
+            },
+        ];
+
+        for (idx, payload) in payloads.into_iter().enumerate() {
+            let event_type = EventType::ALL[idx % EventType::ALL.len()];
+            let correlation = correlations[idx % correlations.len()].clone();
+            round_trip(&(event_type, correlation, payload));
+        }
+    }
+
+    #[test]
+    fn from_bytes_rejects_truncated_and_trailing_input() {
+        let event = (
+            EventType::FillCompleted,
+            CorrelationOutcome::Correlated(vec!["pda".to_string()]),
+            EventPayload::HostTip { amount: 1 },
+        );
+        let bytes = to_bytes(&event).unwrap();
+
+        let truncated = from_bytes(&bytes[..bytes.len() - 1]).unwrap_err();
+        assert!(truncated.to_string().contains("binary decode failed"));
+
+        let mut trailing = bytes;
+        trailing.push(0);
+        assert!(from_bytes(&trailing).is_err());
+    }
+}
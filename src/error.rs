@@ -1,3 +1,28 @@
+/// Stable machine-readable error category, independent of the human message.
+///
+/// Suitable for grouping/alerting: messages may be reworded, codes do not change.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, strum_macros::Display, strum_macros::AsRefStr,
+)]
+#[strum(serialize_all = "snake_case")]
+#[non_exhaustive]
+pub enum ErrorCode {
+    /// A `u64`/`u16` amount does not fit the signed target type, or an accumulator overflowed.
+    AmountOverflow,
+    /// Instruction name is not part of the protocol's instruction set.
+    UnknownInstruction,
+    /// Instruction is known but not valid for the requested operation (e.g. has no order PDA).
+    UnsupportedInstruction,
+    /// A required account index is missing from the instruction's accounts.
+    OutOfBoundsAccount,
+    /// Payload, args, or accounts JSON could not be deserialized or is inconsistent.
+    MalformedPayload,
+    /// A numeric status code has no known mapping.
+    UnknownStatusCode,
+    /// Event was not recognised by the adapter.
+    UnknownEvent,
+}
+
 /// Errors produced during instruction/event classification and resolution.
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
@@ -7,9 +32,29 @@ pub enum Error {
 
     /// Protocol-level invariant violation (e.g. overflow, unknown status code).
     #[error("protocol error: {reason}")]
-    Protocol { reason: String },
+    Protocol { code: ErrorCode, reason: String },
+
+    /// A `u64` amount does not fit in `i64`. Carries the raw value so callers may clamp instead.
+    #[error("protocol error: {field} exceeds i64::MAX: {value}")]
+    AmountOverflow { field: String, value: u64 },
+
+    /// Event was not recognised by the adapter (unknown variant or missing `fields`).
+    #[error("unknown event: {event_name}")]
+    UnknownEvent { event_name: String },
 
     /// Transparent wrapper around [`serde_json::Error`].
     #[error("json error: {0}")]
     Json(#[from] serde_json::Error),
 }
+
+impl Error {
+    /// Stable [`ErrorCode`] for this error.
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            Self::Protocol { code, .. } => *code,
+            Self::AmountOverflow { .. } => ErrorCode::AmountOverflow,
+            Self::Parse { .. } | Self::Json(_) => ErrorCode::MalformedPayload,
+            Self::UnknownEvent { .. } => ErrorCode::UnknownEvent,
+        }
+    }
+}
@@ -0,0 +1,117 @@
+//! Real-data JSON fixtures bundled into the crate, for downstream integration tests.
+//!
+//! The files under `tests/fixtures/` are embedded with `include_str!`, so consumers load them by
+//! name without locating this crate's source directory. Available via the `test-util` feature.
+
+use crate::types::{RawEvent, RawInstruction};
+
+/// Bundled fixtures as `(file name, JSON contents)`.
+pub const FIXTURES: &[(&str, &str)] = &[
+    (
+        "dca_events.json",
+        include_str!("../tests/fixtures/dca_events.json"),
+    ),
+    (
+        "dca_instructions.json",
+        include_str!("../tests/fixtures/dca_instructions.json"),
+    ),
+    (
+        "dca_opened_events.json",
+        include_str!("../tests/fixtures/dca_opened_events.json"),
+    ),
+    (
+        "kamino_events.json",
+        include_str!("../tests/fixtures/kamino_events.json"),
+    ),
+    (
+        "kamino_instructions.json",
+        include_str!("../tests/fixtures/kamino_instructions.json"),
+    ),
+    (
+        "kamino_multi_order_events.json",
+        include_str!("../tests/fixtures/kamino_multi_order_events.json"),
+    ),
+    (
+        "limit_v1_events.json",
+        include_str!("../tests/fixtures/limit_v1_events.json"),
+    ),
+    (
+        "limit_v1_instructions.json",
+        include_str!("../tests/fixtures/limit_v1_instructions.json"),
+    ),
+    (
+        "limit_v2_events.json",
+        include_str!("../tests/fixtures/limit_v2_events.json"),
+    ),
+    (
+        "limit_v2_instructions.json",
+        include_str!("../tests/fixtures/limit_v2_instructions.json"),
+    ),
+];
+
+/// Raw JSON of the bundled fixture `name` (e.g. `"dca_events.json"`), if it exists.
+pub fn raw(name: &str) -> Option<&'static str> {
+    FIXTURES
+        .iter()
+        .find_map(|(candidate, json)| (*candidate == name).then_some(*json))
+}
+
+/// Parses the bundled instruction fixture `name`.
+///
+/// Panics if the fixture is unknown or is not a `RawInstruction` array.
+pub fn load_instructions(name: &str) -> Vec<RawInstruction> {
+    parse(name)
+}
+
+/// Parses the bundled event fixture `name`.
+///
+/// Panics if the fixture is unknown or is not a `RawEvent` array.
+pub fn load_events(name: &str) -> Vec<RawEvent> {
+    parse(name)
+}
+
+#[cfg_attr(
+    not(test),
+    expect(
+        clippy::panic,
+        reason = "test utility: a missing or malformed fixture is a test setup bug"
+    )
+)]
+fn parse<T: serde::de::DeserializeOwned>(name: &str) -> Vec<T> {
+    let json = raw(name).unwrap_or_else(|| panic!("unknown fixture: {name}"));
+    serde_json::from_str(json).unwrap_or_else(|e| panic!("failed to parse fixture {name}: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocols::{EventType, Protocol};
+    use crate::{ResolveContext, adapter_for};
+
+    #[test]
+    fn dca_events_fixture_loads_and_resolves() {
+        let events = load_events("dca_events.json");
+        assert!(!events.is_empty());
+        let fill = events
+            .iter()
+            .find(|ev| ev.event_name == "FilledEvent")
+            .expect("fixture has a fill");
+        let resolved = adapter_for(Protocol::Dca)
+            .classify_and_resolve_event(fill, &ResolveContext::default())
+            .expect("known event")
+            .expect("resolves");
+        assert_eq!(resolved.0, EventType::FillCompleted);
+    }
+
+    #[test]
+    fn every_bundled_fixture_parses() {
+        for (name, _) in FIXTURES {
+            if name.contains("instructions") {
+                assert!(!load_instructions(name).is_empty(), "{name}");
+            } else {
+                assert!(!load_events(name).is_empty(), "{name}");
+            }
+        }
+        assert_eq!(raw("missing.json"), None);
+    }
+}
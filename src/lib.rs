@@ -13,22 +13,55 @@
     )
 )]
 
+#[cfg(feature = "binary")]
+pub mod binary;
 pub mod error;
+#[cfg(any(test, feature = "test-util"))]
+pub mod fixtures;
 pub mod lifecycle;
 pub mod protocols;
+#[cfg(feature = "native")]
+pub mod stream;
+#[cfg(any(test, feature = "test-util"))]
+pub mod test_util;
 pub mod types;
 
 #[cfg(feature = "wasm")]
 pub mod wasm;
 
-pub use error::Error;
+pub use error::{Error, ErrorCode};
 pub use lifecycle::adapters::{
-    CorrelationOutcome, EventPayload, ProtocolAdapter, adapter_for, dca_closed_terminal_status,
-    kamino_display_terminal_status,
+    ClassifyResult, ContextRequirements, CorrelationOutcome, CrossCheck, EventPayload,
+    InstructionArgs, InstructionOutcome, ProtocolAdapter, ResolveCache, ResolvedEvent, adapter_for,
+    adapter_for_id, adapter_for_program_id, classify_dca_close, classify_instruction_histogram,
+    cross_check, dca_closed_terminal_status, fill_ratio, kamino_display_terminal_status,
+    kamino_display_terminal_status_with, resolve_event_value, resolve_transaction,
 };
-pub use lifecycle::mapping::{event_type_to_transition, transition_target, transition_to_display};
+pub use lifecycle::mapping::{
+    DEFAULT_PUBKEY_PREFIX, DEFAULT_PUBKEY_SUFFIX, MappingOptions, abbreviate_pubkey,
+    event_type_to_transition, event_type_to_transition_with, transition_target,
+    transition_to_display,
+};
+pub use lifecycle::order::{
+    FillAggregate, LifecyclePreview, OrderLifecycle, ReconcileReport, RejectRecord,
+    TransitionOutcome,
+};
+pub use lifecycle::registry::AdapterRegistry;
+pub use lifecycle::replay::{
+    LifecycleStream, ReplaySummary, Row, RowKind, RowMeta, coalesce_fills, replay_events,
+    replay_events_with,
+};
+pub use lifecycle::typed::{KaminoDisplaySnapshot, LimitFillEvent, OrderClosedEvent, TypedEvent};
 pub use lifecycle::{
-    LifecycleEngine, LifecycleTransition, SnapshotDelta, TerminalStatus, TransitionDecision,
+    EngineConfig, LifecycleEngine, LifecycleTransition, MetadataReason, SimulationResult,
+    SnapshotDelta, TerminalStatus, TransitionDecision,
+};
+pub use protocols::{
+    DataSource, EventType, InstructionCategory, ProgramId, ProgramIdFilter, Protocol,
+    expected_payload_kind, instruction_mappings, instruction_names_for, is_known_variant,
 };
-pub use protocols::{EventType, Protocol};
-pub use types::{RawEvent, RawInstruction, ResolveContext};
+#[cfg(feature = "native")]
+pub use stream::{UnknownEventPolicy, resolve_events_jsonl, resolve_events_jsonl_with};
+#[cfg(any(test, feature = "test-util"))]
+pub use test_util::{RawEventBuilder, RawInstructionBuilder};
+pub use types::{DEFAULT_MAX_CORRELATED_PDAS, RawEvent, RawInstruction, ResolveContext};
@@ -1,14 +1,22 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{DefaultHasher, Hash, Hasher};
+
 use crate::error::Error;
-use crate::lifecycle::TerminalStatus;
-use crate::protocols::dca::DcaAdapter;
-use crate::protocols::kamino::KaminoAdapter;
-use crate::protocols::limit_v1::LimitV1Adapter;
-use crate::protocols::limit_v2::LimitV2Adapter;
-use crate::protocols::{self, EventType, Protocol};
+use crate::lifecycle::{LifecycleEngine, SnapshotDelta, TerminalStatus};
+use crate::protocols::dca::{DcaAdapter, DcaCreateArgs};
+use crate::protocols::kamino::{KaminoAdapter, KaminoCreateArgs};
+use crate::protocols::limit_v1::{LimitV1Adapter, LimitV1CreateArgs, LimitV1ExpirySettlement};
+use crate::protocols::limit_v2::{LimitV2Adapter, LimitV2CreateArgs};
+use crate::protocols::noop::NoOpAdapter;
+use crate::protocols::va::{VaAdapter, VaCreateArgs};
+use crate::protocols::{self, EventType, InstructionCategory, ProgramId, Protocol};
 use crate::types::{RawEvent, RawInstruction, ResolveContext};
 
 /// Whether (and how) an event was correlated to an order PDA.
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// Variant order is part of the `binary` encoding: append new variants, never reorder.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[non_exhaustive]
 pub enum CorrelationOutcome {
     /// Correlation is not meaningful for this event type
     /// (e.g. Kamino `UserSwapBalancesEvent` is diagnostic-only).
@@ -20,28 +28,335 @@ pub enum CorrelationOutcome {
     Uncorrelated { reason: String },
 }
 
+impl CorrelationOutcome {
+    /// Combines two outcomes for the same event (e.g. payload PDA and pre-fetched instruction PDAs).
+    ///
+    /// Two `Correlated` lists are unioned, keeping first-seen order without duplicates.
+    /// `Correlated` beats `Uncorrelated`; `NotRequired` yields to the other side. Two
+    /// `Uncorrelated` reasons are joined with `"; "`.
+    #[must_use]
+    pub fn merge(self, other: Self) -> Self {
+        match (self, other) {
+            (Self::Correlated(mut pdas), Self::Correlated(more)) => {
+                for pda in more {
+                    if !pdas.contains(&pda) {
+                        pdas.push(pda);
+                    }
+                }
+                Self::Correlated(pdas)
+            }
+            (Self::NotRequired, other) | (other, Self::NotRequired) => other,
+            (correlated @ Self::Correlated(_), Self::Uncorrelated { .. })
+            | (Self::Uncorrelated { .. }, correlated @ Self::Correlated(_)) => correlated,
+            (
+                Self::Uncorrelated { reason },
+                Self::Uncorrelated {
+                    reason: other_reason,
+                },
+            ) => Self::Uncorrelated {
+                reason: format!("{reason}; {other_reason}"),
+            },
+        }
+    }
+}
+
+/// `counterparty` placeholder used when a `TradeEvent` has no `taker`.
+pub(crate) const UNKNOWN_COUNTERPARTY: &str = "unknown";
+
 /// Protocol-specific data extracted from a resolved event.
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// Variant and field order is part of the `binary` encoding: append, never reorder.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[non_exhaustive]
 pub enum EventPayload {
     /// No extra payload beyond the event type itself.
     None,
-    /// Jupiter DCA fill amounts.
-    DcaFill { in_amount: i64, out_amount: i64 },
-    /// Jupiter DCA closed event with derived terminal status.
-    DcaClosed { status: TerminalStatus },
+    /// Jupiter DCA fill amounts (shared by Value Averaging).
+    ///
+    /// `saturated` is set when an amount was clamped (see [`ResolveContext::saturate_amounts`]).
+    DcaFill {
+        in_amount: i64,
+        out_amount: i64,
+        saturated: bool,
+        /// Schedule cycle the fill belongs to; `None` when the event omits it (and for VA).
+        cycle: Option<i64>,
+    },
+    /// Jupiter DCA closed event with derived terminal status (shared by Value Averaging).
+    DcaClosed {
+        status: TerminalStatus,
+        /// Input returned to the user over the order's life; 0 when the event omits it.
+        total_in_withdrawn: i64,
+        /// Output withdrawn by the user over the order's life; 0 when the event omits it.
+        total_out_withdrawn: i64,
+    },
     /// Jupiter Limit Order fill amounts (shared by V1 and V2).
     LimitFill {
         in_amount: i64,
         out_amount: i64,
         remaining_in_amount: i64,
         counterparty: String,
+        /// Order owner, when the `TradeEvent` carries a `maker`/`owner` field.
+        maker: Option<String>,
+        /// An amount was clamped (see [`ResolveContext::saturate_amounts`]).
+        saturated: bool,
+        /// Output (taking) side still owed to the order after this fill.
+        remaining_out_amount: i64,
+        /// The raw remaining exceeded [`ResolveContext::order_original_making_amount`] (a wrapped
+        /// negative), so `remaining_in_amount` was clamped to `0`.
+        remaining_anomaly: bool,
     },
     /// Kamino order display snapshot with optional terminal status.
     KaminoDisplay {
         remaining_input_amount: i64,
         filled_output_amount: i64,
         terminal_status: Option<TerminalStatus>,
+        /// An amount was clamped (see [`ResolveContext::saturate_amounts`]).
+        saturated: bool,
     },
+    /// Kamino host tip withdrawn by the protocol.
+    HostTip { amount: i64 },
+    /// Jupiter DCA schedule from an `OpenedEvent` carrying the schedule fields.
+    DcaCreated {
+        cycle_frequency: i64,
+        in_amount_per_cycle: i64,
+        in_deposited: i64,
+        /// An amount was clamped (see [`ResolveContext::saturate_amounts`]).
+        saturated: bool,
+        /// `1` for `OpenDca`, `2` for `OpenDcaV2` (inferred from v2-only event fields).
+        dca_version: u8,
+    },
+    /// Kamino `LogUserSwapBalances` balances around a swap, for slippage analysis.
+    SwapBalances { pre: i64, post: i64 },
+    /// Terminal close whose status is derived without settled totals (e.g. a Limit v2 cancel of
+    /// a fully filled order).
+    Closed { status: TerminalStatus },
+}
+
+impl EventPayload {
+    /// Name of this payload's variant, e.g. `"DcaFill"` (see [`crate::expected_payload_kind`]).
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::None => "None",
+            Self::DcaFill { .. } => "DcaFill",
+            Self::DcaClosed { .. } => "DcaClosed",
+            Self::LimitFill { .. } => "LimitFill",
+            Self::KaminoDisplay { .. } => "KaminoDisplay",
+            Self::HostTip { .. } => "HostTip",
+            Self::DcaCreated { .. } => "DcaCreated",
+            Self::SwapBalances { .. } => "SwapBalances",
+            Self::Closed { .. } => "Closed",
+        }
+    }
+
+    /// Whether a limit fill's taker is the order's own maker (wash-trading signal).
+    ///
+    /// `None` for non-fill payloads, or when the maker or taker is unknown.
+    pub fn is_self_fill(&self) -> Option<bool> {
+        match self {
+            Self::LimitFill {
+                counterparty,
+                maker: Some(maker),
+                ..
+            } if counterparty != UNKNOWN_COUNTERPARTY => Some(maker == counterparty),
+            _ => None,
+        }
+    }
+
+    /// `(in, out)` amounts carried by the payload.
+    ///
+    /// Per-fill amounts for `DcaFill` and `LimitFill`. For `KaminoDisplay` the pair is the
+    /// snapshot's `(remaining_input_amount, filled_output_amount)`: order-level running values,
+    /// not a single fill (see [`Self::kamino_output_delta`]). `None` for every other variant.
+    pub fn amounts(&self) -> Option<(i64, i64)> {
+        match self {
+            Self::DcaFill {
+                in_amount,
+                out_amount,
+                ..
+            }
+            | Self::LimitFill {
+                in_amount,
+                out_amount,
+                ..
+            } => Some((*in_amount, *out_amount)),
+            Self::KaminoDisplay {
+                remaining_input_amount,
+                filled_output_amount,
+                ..
+            } => Some((*remaining_input_amount, *filled_output_amount)),
+            _ => None,
+        }
+    }
+
+    /// Converts a Kamino cumulative `filled_output_amount` into a delta against `stored_total`.
+    ///
+    /// Returns `None` for non-Kamino payloads. See [`LifecycleEngine::normalize_snapshot_to_delta`].
+    pub fn kamino_output_delta(&self, stored_total: i64) -> Option<SnapshotDelta> {
+        match self {
+            Self::KaminoDisplay {
+                filled_output_amount,
+                ..
+            } => Some(LifecycleEngine::normalize_snapshot_to_delta(
+                stored_total,
+                *filled_output_amount,
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// Protocol-specific arguments parsed from an instruction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InstructionArgs {
+    /// No args parsed (non-create instruction, or args absent).
+    None,
+    DcaCreate(DcaCreateArgs),
+    LimitV1Create(LimitV1CreateArgs),
+    LimitV2Create(LimitV2CreateArgs),
+    KaminoCreate(KaminoCreateArgs),
+    VaCreate(VaCreateArgs),
+    /// Fill settled by a Limit v1 `CancelExpiredOrder`, when the instruction carries one.
+    LimitV1ExpirySettlement(LimitV1ExpirySettlement),
+}
+
+/// A classified instruction with its order PDA and parsed args.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstructionOutcome {
+    pub event_type: EventType,
+    /// Order PDA from the instruction accounts, or `None` when accounts are absent.
+    pub order_pda: Option<String>,
+    pub args: InstructionArgs,
+}
+
+/// A fully resolved event: its classification, correlation, and extracted payload.
+pub type ResolvedEvent = (EventType, CorrelationOutcome, EventPayload);
+
+/// Outcome of [`ProtocolAdapter::classify_event`], splitting the `None` of
+/// [`ProtocolAdapter::classify_and_resolve_event`] into its two causes.
+#[derive(Debug)]
+pub enum ClassifyResult {
+    Resolved(ResolvedEvent),
+    /// Fields are present but the event is not one this protocol knows; carries the event's
+    /// `event_name`, for detecting decoder gaps (new upstream events).
+    UnknownVariant(String),
+    /// The event carries no `fields`.
+    NoFields,
+    Error(Error),
+}
+
+impl ClassifyResult {
+    /// Collapses back to the [`ProtocolAdapter::classify_and_resolve_event`] shape.
+    pub fn into_option(self) -> Option<Result<ResolvedEvent, Error>> {
+        match self {
+            Self::Resolved(resolved) => Some(Ok(resolved)),
+            Self::Error(err) => Some(Err(err)),
+            Self::UnknownVariant(_) | Self::NoFields => None,
+        }
+    }
+}
+
+/// [`ResolveContext`] inputs an adapter needs to fully resolve its events; see
+/// [`ProtocolAdapter::required_context`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ContextRequirements {
+    /// [`ResolveContext::pre_fetched_order_pdas`] must be populated from the transaction's
+    /// instructions, or some events resolve as [`CorrelationOutcome::Uncorrelated`].
+    pub order_pdas: bool,
+}
+
+/// Memoized [`ProtocolAdapter::resolve_cached`] results, keyed by `(signature, event_index)`.
+///
+/// Resolution depends on the [`ResolveContext`] (Kamino correlation on
+/// `pre_fetched_order_pdas`, Limit v2 expiry on the timestamps, amounts on `saturate_amounts`),
+/// so each entry records a fingerprint of the adapter's protocol and the context it was resolved
+/// under. A lookup under a different fingerprint re-resolves and replaces the entry rather than
+/// returning a stale result. Unrecognised events are never cached.
+#[derive(Debug, Default)]
+pub struct ResolveCache {
+    entries: HashMap<(String, i32), CacheEntry>,
+    hits: u64,
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    fingerprint: u64,
+    result: Result<ResolvedEvent, Error>,
+}
+
+impl ResolveCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of cached events.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Lookups answered from the cache without re-resolving.
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    fn get_or_resolve(
+        &mut self,
+        ev: &RawEvent,
+        fingerprint: u64,
+        resolve: impl FnOnce() -> Option<Result<ResolvedEvent, Error>>,
+    ) -> Option<&Result<ResolvedEvent, Error>> {
+        let key = (ev.signature.clone(), ev.event_index);
+        let cached = self
+            .entries
+            .get(&key)
+            .is_some_and(|entry| entry.fingerprint == fingerprint);
+        if cached {
+            self.hits += 1;
+        } else {
+            let Some(result) = resolve() else {
+                self.entries.remove(&key);
+                return None;
+            };
+            self.entries.insert(
+                key.clone(),
+                CacheEntry {
+                    fingerprint,
+                    result,
+                },
+            );
+        }
+        self.entries.get(&key).map(|entry| &entry.result)
+    }
+}
+
+/// Hash of everything besides the event itself that a resolution depends on.
+fn resolve_fingerprint(protocol: Protocol, ctx: &ResolveContext) -> u64 {
+    let ResolveContext {
+        pre_fetched_order_pdas,
+        block_time,
+        order_expired_at,
+        order_remaining_making_amount,
+        order_original_making_amount,
+        treat_filled_as_terminal,
+        max_correlated_pdas,
+        owner,
+        saturate_amounts,
+    } = ctx;
+    let mut hasher = DefaultHasher::new();
+    protocol.hash(&mut hasher);
+    pre_fetched_order_pdas.hash(&mut hasher);
+    block_time.hash(&mut hasher);
+    order_expired_at.hash(&mut hasher);
+    order_remaining_making_amount.hash(&mut hasher);
+    order_original_making_amount.hash(&mut hasher);
+    treat_filled_as_terminal.hash(&mut hasher);
+    max_correlated_pdas.hash(&mut hasher);
+    owner.hash(&mut hasher);
+    saturate_amounts.hash(&mut hasher);
+    hasher.finish()
 }
 
 /// Stateless adapter for classifying instructions and resolving events for a single protocol.
@@ -49,9 +364,43 @@ pub trait ProtocolAdapter: Sync {
     /// Which protocol this adapter handles.
     fn protocol(&self) -> Protocol;
 
+    /// On-chain program id of [`Self::protocol`].
+    fn program_id(&self) -> Option<ProgramId> {
+        ProgramId::for_protocol(self.protocol())
+    }
+
     /// Classifies a raw instruction into an [`EventType`], or `None` if unrecognised/irrelevant.
     fn classify_instruction(&self, ix: &RawInstruction) -> Option<EventType>;
 
+    /// Categorizes a raw instruction, distinguishing deliberate config/fee/balance operations
+    /// from unknown instructions.
+    ///
+    /// The default derives from [`Self::classify_instruction`], so anything it does not
+    /// classify is [`InstructionCategory::Unknown`]; built-in adapters consult their
+    /// `INSTRUCTION_CATEGORIES` tables and derive `classify_instruction` from this instead.
+    fn classify_instruction_category(&self, ix: &RawInstruction) -> InstructionCategory {
+        self.classify_instruction(ix).map_or(
+            InstructionCategory::Unknown,
+            InstructionCategory::OrderLifecycle,
+        )
+    }
+
+    /// Classifies a raw instruction and resolves its order PDA and create args in one call.
+    ///
+    /// Returns `None` when the instruction is unrecognised/irrelevant. The default
+    /// implementation only classifies; built-in adapters also extract the PDA and args.
+    fn resolve_instruction(
+        &self,
+        ix: &RawInstruction,
+    ) -> Option<Result<InstructionOutcome, Error>> {
+        let event_type = self.classify_instruction(ix)?;
+        Some(Ok(InstructionOutcome {
+            event_type,
+            order_pda: None,
+            args: InstructionArgs::None,
+        }))
+    }
+
     /// Classifies and resolves a raw event into an `(EventType, CorrelationOutcome, EventPayload)`.
     ///
     /// Returns `None` when `fields` is absent or the event name is unknown to this protocol.
@@ -59,32 +408,231 @@ pub trait ProtocolAdapter: Sync {
         &self,
         ev: &RawEvent,
         ctx: &ResolveContext,
-    ) -> Option<Result<(EventType, CorrelationOutcome, EventPayload), Error>>;
+    ) -> Option<Result<ResolvedEvent, Error>>;
+
+    /// [`Self::classify_and_resolve_event`], distinguishing an unknown variant from an event
+    /// without fields.
+    fn classify_event(&self, ev: &RawEvent, ctx: &ResolveContext) -> ClassifyResult {
+        match self.classify_and_resolve_event(ev, ctx) {
+            Some(Ok(resolved)) => ClassifyResult::Resolved(resolved),
+            Some(Err(err)) => ClassifyResult::Error(err),
+            None if matches!(ev.fields, None | Some(serde_json::Value::Null)) => {
+                ClassifyResult::NoFields
+            }
+            None => ClassifyResult::UnknownVariant(ev.event_name.clone()),
+        }
+    }
+
+    /// Which [`ResolveContext`] inputs this adapter needs to fully resolve its events, so a
+    /// generic driver can skip pre-fetching what it does not use. The default requires none.
+    fn required_context(&self) -> ContextRequirements {
+        ContextRequirements::default()
+    }
+
+    /// Order PDAs of a transaction's `instructions` that belong to this adapter, for
+    /// [`ResolveContext::pre_fetched_order_pdas`]. Adapters whose
+    /// [`Self::required_context`] asks for `order_pdas` override this (e.g.
+    /// [`KaminoAdapter::collect_order_pdas`]); the default collects none.
+    fn collect_order_pdas(&self, _instructions: &[RawInstruction]) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Resolves all of this adapter's events from one transaction; results align with
+    /// `events`. The default resolves each event independently; adapters that narrow
+    /// correlation across sibling events override it (e.g.
+    /// [`KaminoAdapter::resolve_transaction_events`]).
+    fn resolve_transaction_events(
+        &self,
+        events: &[&RawEvent],
+        ctx: &ResolveContext,
+    ) -> Vec<Option<Result<ResolvedEvent, Error>>> {
+        events
+            .iter()
+            .map(|&ev| self.classify_and_resolve_event(ev, ctx))
+            .collect()
+    }
+
+    /// Correlates a raw event to its order PDA(s) without building the payload.
+    ///
+    /// The default delegates to [`Self::classify_and_resolve_event`]; unrecognised events and
+    /// resolution errors become [`CorrelationOutcome::Uncorrelated`]. Adapters whose PDA
+    /// sourcing differs from the event's own key (e.g. Kamino) override this.
+    fn correlate(&self, ev: &RawEvent, ctx: &ResolveContext) -> CorrelationOutcome {
+        correlate_via_resolution(self, ev, ctx)
+    }
+
+    /// [`Self::classify_and_resolve_event`], memoized in `cache` by the event's
+    /// `(signature, event_index)`.
+    ///
+    /// Re-resolves when `ctx` differs from the context the entry was cached under; see
+    /// [`ResolveCache`].
+    fn resolve_cached<'c>(
+        &self,
+        ev: &RawEvent,
+        ctx: &ResolveContext,
+        cache: &'c mut ResolveCache,
+    ) -> Option<&'c Result<ResolvedEvent, Error>> {
+        cache.get_or_resolve(ev, resolve_fingerprint(self.protocol(), ctx), || {
+            self.classify_and_resolve_event(ev, ctx)
+        })
+    }
+}
+
+/// Default [`ProtocolAdapter::correlate`]: the correlation of a full resolution.
+pub(crate) fn correlate_via_resolution<A: ProtocolAdapter + ?Sized>(
+    adapter: &A,
+    ev: &RawEvent,
+    ctx: &ResolveContext,
+) -> CorrelationOutcome {
+    match adapter.classify_and_resolve_event(ev, ctx) {
+        Some(Ok((_, correlation, _))) => correlation,
+        Some(Err(err)) => CorrelationOutcome::Uncorrelated {
+            reason: err.to_string(),
+        },
+        None => CorrelationOutcome::Uncorrelated {
+            reason: format!(
+                "{} event {} not recognised",
+                adapter.protocol(),
+                ev.event_name
+            ),
+        },
+    }
 }
 
 /// Derives a [`TerminalStatus`] from a DCA `ClosedEvent` payload.
 ///
-/// Priority: `user_closed` → Cancelled, `unfilled_amount == 0` → Completed, else → Expired.
+/// See [`classify_dca_close`] for the priority order.
 pub fn dca_closed_terminal_status(closed: &protocols::dca::DcaClosedEvent) -> TerminalStatus {
-    if closed.user_closed {
+    classify_dca_close(closed.user_closed, closed.unfilled_amount)
+}
+
+/// Derives a [`TerminalStatus`] from raw DCA `ClosedEvent` fields.
+///
+/// Priority: `user_closed` → Cancelled, `unfilled_amount == 0` → Completed, else → Expired.
+pub fn classify_dca_close(user_closed: bool, unfilled_amount: i64) -> TerminalStatus {
+    if user_closed {
         TerminalStatus::Cancelled
-    } else if closed.unfilled_amount == 0 {
+    } else if unfilled_amount == 0 {
         TerminalStatus::Completed
     } else {
         TerminalStatus::Expired
     }
 }
 
+/// Fraction of a limit order filled, from its original `making_amount` (create args) and a
+/// `LimitFill`'s `remaining_in_amount`.
+///
+/// `None` when `original_making` is not positive. Clamped to `[0, 1]` so inconsistent data
+/// (remaining above the original, or negative) cannot escape the progress range.
+pub fn fill_ratio(original_making: i64, remaining_in: i64) -> Option<f64> {
+    if original_making <= 0 {
+        return None;
+    }
+    let filled = i128::from(original_making) - i128::from(remaining_in);
+    Some((filled as f64 / original_making as f64).clamp(0.0, 1.0))
+}
+
 /// Converts a Kamino display status code into an optional [`TerminalStatus`].
 ///
 /// Code 0 (Open) → `None`; codes 1–3 map to Completed/Cancelled/Expired.
 pub fn kamino_display_terminal_status(status_code: i64) -> Result<Option<TerminalStatus>, Error> {
-    let status = protocols::kamino::KaminoAdapter::parse_display_status(status_code)?;
-    match status {
-        protocols::kamino::KaminoDisplayStatus::Open => Ok(None),
-        protocols::kamino::KaminoDisplayStatus::Filled => Ok(Some(TerminalStatus::Completed)),
-        protocols::kamino::KaminoDisplayStatus::Cancelled => Ok(Some(TerminalStatus::Cancelled)),
-        protocols::kamino::KaminoDisplayStatus::Expired => Ok(Some(TerminalStatus::Expired)),
+    protocols::kamino::KaminoAdapter::parse_display_status(status_code).map(Into::into)
+}
+
+/// [`kamino_display_terminal_status`] under the
+/// [`EngineConfig::treat_filled_as_terminal`](crate::EngineConfig) policy.
+///
+/// With `treat_filled_as_terminal == false`, code 1 (Filled) with nonzero
+/// `remaining_input_amount` → `None`.
+pub fn kamino_display_terminal_status_with(
+    status_code: i64,
+    remaining_input_amount: u64,
+    treat_filled_as_terminal: bool,
+) -> Result<Option<TerminalStatus>, Error> {
+    let status = kamino_display_terminal_status(status_code)?;
+    if status == Some(TerminalStatus::Completed)
+        && remaining_input_amount > 0
+        && !treat_filled_as_terminal
+    {
+        return Ok(None);
+    }
+    Ok(status)
+}
+
+/// Tallies [`ProtocolAdapter::classify_instruction`] results over `instructions`.
+///
+/// Unclassified instructions (`None`) are skipped.
+pub fn classify_instruction_histogram(
+    protocol: Protocol,
+    instructions: &[RawInstruction],
+) -> BTreeMap<EventType, usize> {
+    let adapter = adapter_for(protocol);
+    let mut histogram = BTreeMap::new();
+    for event_type in instructions
+        .iter()
+        .filter_map(|ix| adapter.classify_instruction(ix))
+    {
+        *histogram.entry(event_type).or_insert(0) += 1;
+    }
+    histogram
+}
+
+/// Outcome of [`cross_check`]: whether an instruction and its event classify alike.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrossCheck {
+    /// Both sides classify as the same [`EventType`].
+    Agree(EventType),
+    /// Both sides classify, but differently.
+    Disagree {
+        instruction: EventType,
+        event: EventType,
+    },
+    /// At least one side is not recognised by the adapter, so there is nothing to compare.
+    Unclassified {
+        instruction: Option<EventType>,
+        event: Option<EventType>,
+    },
+}
+
+/// Classifies `ix` and resolves `ev` with `protocol`'s adapter and compares the event types.
+///
+/// For auditing that an instruction and the event it emitted (e.g. DCA `CloseDca` and
+/// `ClosedEvent`) agree. Event resolution errors are returned rather than treated as a mismatch.
+pub fn cross_check(
+    protocol: Protocol,
+    ix: &RawInstruction,
+    ev: &RawEvent,
+    ctx: &ResolveContext,
+) -> Result<CrossCheck, Error> {
+    let adapter = adapter_for(protocol);
+    let instruction = adapter.classify_instruction(ix);
+    let event = adapter
+        .classify_and_resolve_event(ev, ctx)
+        .transpose()?
+        .map(|(event_type, _, _)| event_type);
+    Ok(match (instruction, event) {
+        (Some(instruction), Some(event)) if instruction == event => CrossCheck::Agree(event),
+        (Some(instruction), Some(event)) => CrossCheck::Disagree { instruction, event },
+        (instruction, event) => CrossCheck::Unclassified { instruction, event },
+    })
+}
+
+/// Classifies and resolves a bare event `fields` payload without constructing a [`RawEvent`].
+///
+/// Same semantics as [`ProtocolAdapter::classify_and_resolve_event`]; never panics on arbitrary
+/// JSON, which makes it the entry point for fuzzing.
+pub fn resolve_event_value(
+    protocol: Protocol,
+    fields: &serde_json::Value,
+    ctx: &ResolveContext,
+) -> Option<Result<ResolvedEvent, Error>> {
+    match protocol {
+        Protocol::Dca => DcaAdapter::resolve_fields(fields, ctx),
+        Protocol::LimitV1 => LimitV1Adapter::resolve_fields(fields, ctx),
+        Protocol::LimitV2 => LimitV2Adapter::resolve_fields(fields, ctx),
+        Protocol::Kamino => KaminoAdapter::resolve_fields(fields, ctx),
+        Protocol::ValueAveraging => VaAdapter::resolve_fields(fields, ctx),
+        Protocol::Unknown => None,
     }
 }
 
@@ -95,51 +643,198 @@ pub fn adapter_for(protocol: Protocol) -> &'static dyn ProtocolAdapter {
         Protocol::LimitV1 => &LimitV1Adapter,
         Protocol::LimitV2 => &LimitV2Adapter,
         Protocol::Kamino => &KaminoAdapter,
+        Protocol::ValueAveraging => &VaAdapter,
+        Protocol::Unknown => &NoOpAdapter,
     }
 }
 
+/// Returns the static [`ProtocolAdapter`] for a base58 program id, falling back to
+/// [`NoOpAdapter`] for unsupported programs.
+///
+/// Unlike [`Protocol::from_program_id`], never `None`: unknown programs resolve to an adapter
+/// that recognises nothing, so callers can log and skip them uniformly.
+pub fn adapter_for_program_id(program_id: &str) -> &'static dyn ProtocolAdapter {
+    builtin_protocol(program_id).map_or(&NoOpAdapter, adapter_for)
+}
+
+/// Built-in [`Protocol`] whose base58 program id is `program_id`.
+///
+/// String comparison against [`Protocol::program_id_str`], so it works under every feature
+/// set, unlike the feature-gated [`Protocol::from_program_id`].
+fn builtin_protocol(program_id: &str) -> Option<Protocol> {
+    Protocol::ALL
+        .iter()
+        .copied()
+        .find(|protocol| protocol.program_id_str() == Some(program_id))
+}
+
+/// Resolves every event of one transaction, picking each event's adapter by its
+/// `inner_program_id` and building the [`ResolveContext`] from the sibling `instructions`.
+///
+/// Per adapter, the context carries the pre-fetched order PDAs when
+/// [`ProtocolAdapter::required_context`] asks for them ([`ProtocolAdapter::collect_order_pdas`]),
+/// and the events go through [`ProtocolAdapter::resolve_transaction_events`]. Events of
+/// unsupported programs, and events their adapter does not recognise, are skipped; the rest
+/// keep their relative order.
+pub fn resolve_transaction(
+    instructions: &[RawInstruction],
+    events: &[RawEvent],
+) -> Vec<(Protocol, Result<ResolvedEvent, Error>)> {
+    resolve_transaction_with(instructions, events, |program_id| {
+        builtin_protocol(program_id).map(adapter_for)
+    })
+}
+
+/// [`resolve_transaction`] with each event's adapter looked up by `adapter_for_program_id`;
+/// events it returns `None` for are skipped.
+pub(crate) fn resolve_transaction_with<'a>(
+    instructions: &[RawInstruction],
+    events: &[RawEvent],
+    adapter_for_program_id: impl Fn(&str) -> Option<&'a dyn ProtocolAdapter>,
+) -> Vec<(Protocol, Result<ResolvedEvent, Error>)> {
+    let mut groups: BTreeMap<&str, (&dyn ProtocolAdapter, Vec<usize>)> = BTreeMap::new();
+    for (idx, ev) in events.iter().enumerate() {
+        let program_id = ev.inner_program_id.as_str();
+        if let Some((_, indexes)) = groups.get_mut(program_id) {
+            indexes.push(idx);
+        } else if let Some(adapter) = adapter_for_program_id(program_id) {
+            groups.insert(program_id, (adapter, vec![idx]));
+        }
+    }
+
+    let mut resolved: Vec<(usize, Protocol, Result<ResolvedEvent, Error>)> = Vec::new();
+    for (adapter, indexes) in groups.into_values() {
+        let protocol = adapter.protocol();
+        let ctx = ResolveContext {
+            pre_fetched_order_pdas: adapter
+                .required_context()
+                .order_pdas
+                .then(|| adapter.collect_order_pdas(instructions)),
+            ..ResolveContext::default()
+        };
+        let group: Vec<&RawEvent> = indexes.iter().map(|&idx| &events[idx]).collect();
+        let results = adapter.resolve_transaction_events(&group, &ctx);
+        resolved.extend(
+            indexes
+                .into_iter()
+                .zip(results)
+                .filter_map(|(idx, result)| Some((idx, protocol, result?))),
+        );
+    }
+    resolved.sort_by_key(|(idx, _, _)| *idx);
+    resolved
+        .into_iter()
+        .map(|(_, protocol, result)| (protocol, result))
+        .collect()
+}
+
+/// Returns the static [`ProtocolAdapter`] for a snake_case protocol id (e.g. `"dca"`).
+///
+/// `None` when `id` does not parse as a [`Protocol`].
+pub fn adapter_for_id(id: &str) -> Option<&'static dyn ProtocolAdapter> {
+    id.parse().ok().map(adapter_for)
+}
+
 #[cfg(test)]
 #[expect(clippy::unwrap_used, reason = "test assertions")]
 mod tests {
     use super::*;
     use crate::protocols::{EventType, Protocol};
+    use crate::test_util::{RawEventBuilder, RawInstructionBuilder};
     use crate::types::{RawEvent, RawInstruction, ResolveContext};
 
     fn make_instruction(name: &str) -> RawInstruction {
-        RawInstruction {
-            id: 1,
-            signature: "sig".to_string(),
-            instruction_index: 0,
-            instruction_path: None,
-            program_id: "p".to_string(),
-            inner_program_id: "p".to_string(),
-            instruction_name: name.to_string(),
-            accounts: None,
-            args: None,
-            slot: 1,
-        }
+        RawInstructionBuilder::new(name).build()
     }
 
     fn make_event(event_name: &str, fields: Option<serde_json::Value>) -> RawEvent {
         RawEvent {
-            id: 1,
-            signature: "sig".to_string(),
-            event_index: 0,
-            event_path: None,
-            program_id: "p".to_string(),
-            inner_program_id: "p".to_string(),
-            event_name: event_name.to_string(),
             fields,
-            slot: 1,
+            ..RawEventBuilder::new(event_name).build()
         }
     }
 
+    #[test]
+    fn default_correlate_delegates_to_resolution() {
+        let adapter = adapter_for(Protocol::Dca);
+        let ctx = ResolveContext::default();
+        let opened = make_event(
+            "OpenedEvent",
+            Some(serde_json::json!({ "OpenedEvent": { "dca_key": "order1" } })),
+        );
+        assert_eq!(
+            adapter.correlate(&opened, &ctx),
+            CorrelationOutcome::Correlated(vec!["order1".to_string()])
+        );
+
+        let unknown = make_event(
+            "MysteryEvent",
+            Some(serde_json::json!({ "MysteryEvent": {} })),
+        );
+        assert!(matches!(
+            adapter.correlate(&unknown, &ctx),
+            CorrelationOutcome::Uncorrelated { reason } if reason.contains("MysteryEvent")
+        ));
+    }
+
     #[test]
     fn adapter_selection_matches_protocol() {
         assert_eq!(adapter_for(Protocol::Dca).protocol(), Protocol::Dca);
         assert_eq!(adapter_for(Protocol::LimitV1).protocol(), Protocol::LimitV1);
         assert_eq!(adapter_for(Protocol::LimitV2).protocol(), Protocol::LimitV2);
         assert_eq!(adapter_for(Protocol::Kamino).protocol(), Protocol::Kamino);
+        assert_eq!(
+            adapter_for(Protocol::ValueAveraging).protocol(),
+            Protocol::ValueAveraging
+        );
+    }
+
+    #[test]
+    fn adapter_for_id_parses_protocol_ids() {
+        assert_eq!(
+            adapter_for_id("kamino").unwrap().protocol(),
+            Protocol::Kamino
+        );
+        assert_eq!(
+            adapter_for_id("value_averaging").unwrap().protocol(),
+            Protocol::ValueAveraging
+        );
+        assert!(adapter_for_id("serum").is_none());
+        assert!(adapter_for_id("Kamino").is_none());
+    }
+
+    #[test]
+    fn unknown_program_ids_fall_back_to_noop_adapter() {
+        assert_eq!(
+            adapter_for_program_id(protocols::KAMINO_PROGRAM_ID).protocol(),
+            Protocol::Kamino
+        );
+        let unsupported = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+        assert_eq!(Protocol::from_program_id(unsupported), None);
+
+        let noop = adapter_for_program_id(unsupported);
+        assert_eq!(noop.protocol(), Protocol::Unknown);
+        assert_eq!(noop.program_id(), None);
+        assert_eq!(
+            noop.classify_instruction(&make_instruction("OpenDca")),
+            None
+        );
+        assert!(
+            noop.resolve_instruction(&make_instruction("OpenDca"))
+                .is_none()
+        );
+
+        let ev = make_event(
+            "FilledEvent",
+            Some(serde_json::json!({
+                "FilledEvent": { "dca_key": "order", "in_amount": 1_u64, "out_amount": 1_u64 }
+            })),
+        );
+        let ctx = ResolveContext::default();
+        assert!(noop.classify_and_resolve_event(&ev, &ctx).is_none());
+        assert_eq!(noop.correlate(&ev, &ctx), CorrelationOutcome::NotRequired);
+        assert!(Protocol::Unknown.possible_event_types().is_empty());
+        assert!(!Protocol::ALL.contains(&Protocol::Unknown));
     }
 
     #[test]
@@ -205,6 +900,23 @@ mod tests {
         assert_eq!(dca_closed_terminal_status(&closed), TerminalStatus::Expired);
     }
 
+    #[test]
+    fn classify_dca_close_covers_all_outcomes() {
+        let cases = [
+            (true, 500, TerminalStatus::Cancelled),
+            (true, 0, TerminalStatus::Cancelled),
+            (false, 0, TerminalStatus::Completed),
+            (false, 1000, TerminalStatus::Expired),
+        ];
+        for (user_closed, unfilled_amount, expected) in cases {
+            assert_eq!(
+                classify_dca_close(user_closed, unfilled_amount),
+                expected,
+                "user_closed={user_closed}, unfilled_amount={unfilled_amount}"
+            );
+        }
+    }
+
     #[test]
     fn kamino_display_terminal_status_all_codes() {
         assert_eq!(kamino_display_terminal_status(0).unwrap(), None);
@@ -222,6 +934,158 @@ mod tests {
         );
     }
 
+    #[test]
+    fn string_and_number_amounts_resolve_identically() {
+        let ctx = ResolveContext {
+            pre_fetched_order_pdas: Some(vec!["order_pda".to_string()]),
+            ..ResolveContext::default()
+        };
+        let amount = 21_041_666_667_u64;
+        let payloads = |amount: serde_json::Value| {
+            [
+                (
+                    Protocol::Dca,
+                    serde_json::json!({ "FilledEvent": {
+                        "dca_key": "dca_pda", "in_amount": amount, "out_amount": amount,
+                        "cycle": amount
+                    }}),
+                ),
+                (
+                    Protocol::Dca,
+                    serde_json::json!({ "ClosedEvent": {
+                        "dca_key": "dca_pda", "user_closed": false, "unfilled_amount": amount
+                    }}),
+                ),
+                (
+                    Protocol::ValueAveraging,
+                    serde_json::json!({ "ClosedEvent": {
+                        "value_average": "va_pda", "user_closed": false, "unfilled_amount": amount
+                    }}),
+                ),
+                (
+                    Protocol::LimitV1,
+                    serde_json::json!({ "TradeEvent": {
+                        "order_key": "order_pda", "taker": "taker",
+                        "in_amount": amount, "out_amount": amount,
+                        "remaining_in_amount": amount, "remaining_out_amount": amount
+                    }}),
+                ),
+                (
+                    Protocol::LimitV2,
+                    serde_json::json!({ "TradeEvent": {
+                        "order_key": "order_pda", "taker": "taker",
+                        "making_amount": amount, "taking_amount": amount,
+                        "remaining_making_amount": amount, "remaining_taking_amount": amount
+                    }}),
+                ),
+                (
+                    Protocol::Kamino,
+                    serde_json::json!({ "OrderDisplayEvent": {
+                        "remaining_input_amount": amount, "filled_output_amount": amount,
+                        "number_of_fills": amount, "status": 0
+                    }}),
+                ),
+            ]
+        };
+
+        let numbers = payloads(serde_json::json!(amount));
+        let strings = payloads(serde_json::json!(amount.to_string()));
+        for ((protocol, number), (_, string)) in numbers.iter().zip(strings.iter()) {
+            let from_number = resolve_event_value(*protocol, number, &ctx)
+                .unwrap()
+                .unwrap();
+            let from_string = resolve_event_value(*protocol, string, &ctx)
+                .unwrap()
+                .unwrap();
+            assert_eq!(from_number, from_string, "{protocol:?}");
+            assert_ne!(from_number.2, EventPayload::None, "{protocol:?}");
+        }
+
+        let bad = serde_json::json!({ "FilledEvent": {
+            "dca_key": "dca_pda", "in_amount": "12abc", "out_amount": 1
+        }});
+        assert!(
+            resolve_event_value(Protocol::Dca, &bad, &ctx)
+                .unwrap()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn string_and_number_instruction_amounts_parse_identically() {
+        let amount = 21_041_666_667_u64;
+        let number = serde_json::json!(amount);
+        let string = serde_json::json!(amount.to_string());
+
+        let dca = |amount: &serde_json::Value| {
+            DcaAdapter::parse_create_args(&serde_json::json!({
+                "in_amount": amount, "in_amount_per_cycle": amount, "cycle_frequency": 60,
+                "min_out_amount": amount, "max_out_amount": amount
+            }))
+            .unwrap()
+        };
+        assert_eq!(dca(&number), dca(&string));
+
+        let dlmm = |amount: &serde_json::Value| {
+            let accounts = [protocols::AccountInfo {
+                pubkey: "dca_pda".to_string(),
+                is_signer: false,
+                is_writable: true,
+                name: Some("dca".to_string()),
+            }];
+            let fill = DcaAdapter::parse_dlmm_fill_args(
+                &serde_json::json!({ "repay_amount": amount, "out_amount": amount }),
+                &accounts,
+            )
+            .unwrap();
+            (fill.in_amount, fill.out_amount)
+        };
+        assert_eq!(dlmm(&number), dlmm(&string));
+
+        let va = |amount: &serde_json::Value| {
+            VaAdapter::parse_create_args(&serde_json::json!({
+                "deposit_amount": amount, "increment_usdc_value": amount,
+                "order_interval": 60, "max_out_amount": amount
+            }))
+            .unwrap()
+        };
+        assert_eq!(va(&number), va(&string));
+
+        let limit_v1 = |amount: &serde_json::Value| {
+            LimitV1Adapter::parse_create_args(
+                &serde_json::json!({ "making_amount": amount, "taking_amount": amount }),
+            )
+            .unwrap()
+        };
+        assert_eq!(limit_v1(&number), limit_v1(&string));
+
+        let limit_v2 = |amount: &serde_json::Value| {
+            LimitV2Adapter::parse_create_args(
+                &serde_json::json!({ "params": { "making_amount": amount, "taking_amount": amount } }),
+            )
+            .unwrap()
+        };
+        assert_eq!(limit_v2(&number), limit_v2(&string));
+
+        let kamino = |amount: &serde_json::Value| {
+            KaminoAdapter::parse_create_args(
+                &serde_json::json!({ "input_amount": amount, "output_amount": amount }),
+            )
+            .unwrap()
+        };
+        assert_eq!(kamino(&number), kamino(&string));
+
+        let host_tip = |amount: &serde_json::Value| {
+            KaminoAdapter::resolve_host_tip(
+                &RawInstructionBuilder::new("WithdrawHostTip")
+                    .args(serde_json::json!({ "amount": amount }))
+                    .build(),
+            )
+            .unwrap()
+        };
+        assert_eq!(host_tip(&number), host_tip(&string));
+    }
+
     #[test]
     fn kamino_resolve_uncorrelated_without_context() {
         let adapter = adapter_for(Protocol::Kamino);
@@ -239,9 +1103,7 @@ mod tests {
                 })),
             )
         };
-        let ctx = ResolveContext {
-            pre_fetched_order_pdas: None,
-        };
+        let ctx = ResolveContext::default();
 
         let result = adapter
             .classify_and_resolve_event(&ev, &ctx)
@@ -267,12 +1129,7 @@ mod tests {
         );
 
         let (event_type, correlation, payload) = adapter
-            .classify_and_resolve_event(
-                &ev,
-                &ResolveContext {
-                    pre_fetched_order_pdas: None,
-                },
-            )
+            .classify_and_resolve_event(&ev, &ResolveContext::default())
             .unwrap()
             .unwrap();
 
@@ -294,12 +1151,7 @@ mod tests {
             })),
         );
         let (event_type_v1, _, _) = limit_v1
-            .classify_and_resolve_event(
-                &limit_v1_event,
-                &ResolveContext {
-                    pre_fetched_order_pdas: None,
-                },
-            )
+            .classify_and_resolve_event(&limit_v1_event, &ResolveContext::default())
             .unwrap()
             .unwrap();
         assert_eq!(event_type_v1, EventType::Created);
@@ -312,12 +1164,7 @@ mod tests {
             })),
         );
         let (event_type_v2, _, _) = limit_v2
-            .classify_and_resolve_event(
-                &limit_v2_event,
-                &ResolveContext {
-                    pre_fetched_order_pdas: None,
-                },
-            )
+            .classify_and_resolve_event(&limit_v2_event, &ResolveContext::default())
             .unwrap()
             .unwrap();
         assert_eq!(event_type_v2, EventType::Created);
@@ -326,9 +1173,7 @@ mod tests {
     #[test]
     fn classify_and_resolve_event_returns_none_when_fields_are_absent() {
         let ev = make_event("AnyEvent", None);
-        let ctx = ResolveContext {
-            pre_fetched_order_pdas: None,
-        };
+        let ctx = ResolveContext::default();
 
         assert!(
             adapter_for(Protocol::Dca)
@@ -351,4 +1196,435 @@ mod tests {
                 .is_none()
         );
     }
+
+    #[test]
+    fn correlation_merge_combines_outcomes() {
+        let correlated = |pdas: &[&str]| {
+            CorrelationOutcome::Correlated(pdas.iter().map(|p| (*p).to_string()).collect())
+        };
+        let uncorrelated = |reason: &str| CorrelationOutcome::Uncorrelated {
+            reason: reason.to_string(),
+        };
+
+        assert_eq!(
+            correlated(&["a", "b"]).merge(correlated(&["b", "c", "a"])),
+            correlated(&["a", "b", "c"])
+        );
+        assert_eq!(
+            correlated(&["a"]).merge(uncorrelated("missing")),
+            correlated(&["a"])
+        );
+        assert_eq!(
+            uncorrelated("missing").merge(correlated(&["a"])),
+            correlated(&["a"])
+        );
+        assert_eq!(
+            CorrelationOutcome::NotRequired.merge(correlated(&["a"])),
+            correlated(&["a"])
+        );
+        assert_eq!(
+            correlated(&["a"]).merge(CorrelationOutcome::NotRequired),
+            correlated(&["a"])
+        );
+        assert_eq!(
+            CorrelationOutcome::NotRequired.merge(uncorrelated("missing")),
+            uncorrelated("missing")
+        );
+        assert_eq!(
+            uncorrelated("missing").merge(CorrelationOutcome::NotRequired),
+            uncorrelated("missing")
+        );
+        assert_eq!(
+            CorrelationOutcome::NotRequired.merge(CorrelationOutcome::NotRequired),
+            CorrelationOutcome::NotRequired
+        );
+        assert_eq!(
+            uncorrelated("x").merge(uncorrelated("y")),
+            uncorrelated("x; y")
+        );
+    }
+
+    #[test]
+    fn kamino_output_delta_tracks_snapshots() {
+        let payload = EventPayload::KaminoDisplay {
+            remaining_input_amount: 10,
+            filled_output_amount: 150,
+            terminal_status: None,
+            saturated: false,
+        };
+        assert_eq!(
+            payload.kamino_output_delta(100),
+            Some(SnapshotDelta {
+                delta: 50,
+                regression: false,
+            })
+        );
+        assert_eq!(
+            payload.kamino_output_delta(200),
+            Some(SnapshotDelta {
+                delta: 0,
+                regression: true,
+            })
+        );
+        assert_eq!(
+            EventPayload::DcaFill {
+                in_amount: 1,
+                out_amount: 1,
+                saturated: false,
+                cycle: None,
+            }
+            .kamino_output_delta(0),
+            None
+        );
+    }
+
+    #[test]
+    fn required_context_flags_order_pdas_only_for_kamino() {
+        assert!(adapter_for(Protocol::Kamino).required_context().order_pdas);
+        assert_eq!(
+            adapter_for(Protocol::Dca).required_context(),
+            ContextRequirements::default()
+        );
+        assert!(!adapter_for(Protocol::LimitV2).required_context().order_pdas);
+    }
+
+    #[test]
+    fn classify_event_distinguishes_unknown_variant_from_missing_fields() {
+        let adapter = adapter_for(Protocol::Dca);
+        let ctx = ResolveContext::default();
+
+        let unknown = make_event(
+            "BrandNewEvent",
+            Some(serde_json::json!({ "BrandNewEvent": { "dca_key": "pda" } })),
+        );
+        let ClassifyResult::UnknownVariant(name) = adapter.classify_event(&unknown, &ctx) else {
+            panic!("expected UnknownVariant");
+        };
+        assert_eq!(name, "BrandNewEvent");
+
+        let bare = make_event("FilledEvent", None);
+        assert!(matches!(
+            adapter.classify_event(&bare, &ctx),
+            ClassifyResult::NoFields
+        ));
+        assert!(adapter.classify_event(&bare, &ctx).into_option().is_none());
+
+        let fill = make_event(
+            "FilledEvent",
+            Some(serde_json::json!({
+                "FilledEvent": { "dca_key": "pda", "in_amount": 1, "out_amount": 1 }
+            })),
+        );
+        assert!(matches!(
+            adapter.classify_event(&fill, &ctx),
+            ClassifyResult::Resolved((EventType::FillCompleted, _, _))
+        ));
+    }
+
+    #[test]
+    fn fill_ratio_is_clamped_progress() {
+        assert_eq!(fill_ratio(1_000, 1_000), Some(0.0));
+        assert_eq!(fill_ratio(1_000, 500), Some(0.5));
+        assert_eq!(fill_ratio(1_000, 0), Some(1.0));
+        assert_eq!(fill_ratio(1_000, -200), Some(1.0));
+        assert_eq!(fill_ratio(1_000, 1_500), Some(0.0));
+        assert_eq!(fill_ratio(0, 0), None);
+    }
+
+    #[test]
+    fn amounts_cover_every_payload_variant() {
+        let cases = [
+            (EventPayload::None, None),
+            (
+                EventPayload::DcaFill {
+                    in_amount: 10,
+                    out_amount: 4,
+                    saturated: false,
+                    cycle: None,
+                },
+                Some((10, 4)),
+            ),
+            (
+                EventPayload::DcaClosed {
+                    status: TerminalStatus::Completed,
+                    total_in_withdrawn: 0,
+                    total_out_withdrawn: 0,
+                },
+                None,
+            ),
+            (
+                EventPayload::LimitFill {
+                    in_amount: 7,
+                    out_amount: 3,
+                    remaining_in_amount: 93,
+                    counterparty: "taker".to_string(),
+                    maker: None,
+                    saturated: false,
+                    remaining_out_amount: 40,
+                    remaining_anomaly: false,
+                },
+                Some((7, 3)),
+            ),
+            (
+                EventPayload::KaminoDisplay {
+                    remaining_input_amount: 60,
+                    filled_output_amount: 25,
+                    terminal_status: None,
+                    saturated: false,
+                },
+                Some((60, 25)),
+            ),
+            (EventPayload::HostTip { amount: 5 }, None),
+            (
+                EventPayload::DcaCreated {
+                    cycle_frequency: 60,
+                    in_amount_per_cycle: 100,
+                    in_deposited: 1_000,
+                    saturated: false,
+                    dca_version: 2,
+                },
+                None,
+            ),
+            (EventPayload::SwapBalances { pre: 1, post: 2 }, None),
+            (
+                EventPayload::Closed {
+                    status: TerminalStatus::Completed,
+                },
+                None,
+            ),
+        ];
+        for (payload, expected) in cases {
+            assert_eq!(payload.amounts(), expected, "{payload:?}");
+        }
+    }
+
+    fn lcg_next(state: &mut u64) -> u64 {
+        *state = state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1);
+        *state >> 33
+    }
+
+    const FUZZ_KEYS: &[&str] = &[
+        "OpenedEvent",
+        "FilledEvent",
+        "ClosedEvent",
+        "TradeEvent",
+        "CreateOrderEvent",
+        "CancelOrderEvent",
+        "OrderDisplayEvent",
+        "UserSwapBalancesEvent",
+        "dca_key",
+        "order_key",
+        "in_amount",
+        "out_amount",
+        "making_amount",
+        "taking_amount",
+        "remaining_in_amount",
+        "remaining_input_amount",
+        "filled_output_amount",
+        "user_closed",
+        "unfilled_amount",
+        "status",
+        "taker",
+    ];
+
+    fn random_json(state: &mut u64, depth: u32) -> serde_json::Value {
+        let pick = if depth == 0 {
+            lcg_next(state) % 6
+        } else {
+            lcg_next(state) % 8
+        };
+        match pick {
+            0 => serde_json::Value::Null,
+            1 => serde_json::Value::Bool(lcg_next(state).is_multiple_of(2)),
+            2 => serde_json::json!(u64::MAX - lcg_next(state) % 3),
+            3 => serde_json::json!(-i64::try_from(lcg_next(state)).unwrap_or(0)),
+            4 => serde_json::json!(lcg_next(state) % 5),
+            5 => serde_json::Value::String(
+                FUZZ_KEYS[usize::try_from(lcg_next(state)).unwrap_or(0) % FUZZ_KEYS.len()]
+                    .to_string(),
+            ),
+            6 => serde_json::Value::Array(
+                (0..lcg_next(state) % 3)
+                    .map(|_| random_json(state, depth - 1))
+                    .collect(),
+            ),
+            _ => serde_json::Value::Object(
+                (0..lcg_next(state) % 4)
+                    .map(|_| {
+                        let key = FUZZ_KEYS
+                            [usize::try_from(lcg_next(state)).unwrap_or(0) % FUZZ_KEYS.len()];
+                        (key.to_string(), random_json(state, depth - 1))
+                    })
+                    .collect(),
+            ),
+        }
+    }
+
+    #[test]
+    fn resolve_event_value_never_panics_on_random_json() {
+        let contexts = [
+            ResolveContext::default(),
+            ResolveContext {
+                pre_fetched_order_pdas: Some(vec![]),
+                ..ResolveContext::default()
+            },
+            ResolveContext {
+                pre_fetched_order_pdas: Some(vec!["order".to_string()]),
+                ..ResolveContext::default()
+            },
+        ];
+        let mut state = 0x5eed_u64;
+        for _ in 0..5_000 {
+            let fields = random_json(&mut state, 3);
+            for &protocol in Protocol::ALL {
+                for ctx in &contexts {
+                    let _ = resolve_event_value(protocol, &fields, ctx);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn classify_instruction_histogram_tallies_event_types() {
+        let names = [
+            "OpenDca",
+            "OpenDcaV2",
+            "CloseDca",
+            "FulfillFlashFill",
+            "FulfillDlmmFill",
+            "InitiateFlashFill",
+            "Transfer",
+            "NotAnInstruction",
+        ];
+        let instructions: Vec<RawInstruction> = names
+            .iter()
+            .map(|name| RawInstructionBuilder::new(*name).build())
+            .collect();
+
+        let histogram = classify_instruction_histogram(Protocol::Dca, &instructions);
+        let expected = BTreeMap::from([
+            (EventType::Created, 2),
+            (EventType::FillInitiated, 1),
+            (EventType::FillCompleted, 2),
+            (EventType::Closed, 1),
+        ]);
+        assert_eq!(histogram, expected);
+        assert!(classify_instruction_histogram(Protocol::Kamino, &instructions).is_empty());
+    }
+
+    #[test]
+    fn cross_check_compares_instruction_and_event_types() {
+        let ctx = ResolveContext::default();
+        let closed = make_event(
+            "ClosedEvent",
+            Some(serde_json::json!({
+                "ClosedEvent": { "dca_key": "order", "user_closed": false, "unfilled_amount": 0_u64 }
+            })),
+        );
+        assert_eq!(
+            cross_check(Protocol::Dca, &make_instruction("CloseDca"), &closed, &ctx).unwrap(),
+            CrossCheck::Agree(EventType::Closed)
+        );
+        assert_eq!(
+            cross_check(
+                Protocol::Dca,
+                &make_instruction("FulfillFlashFill"),
+                &closed,
+                &ctx
+            )
+            .unwrap(),
+            CrossCheck::Disagree {
+                instruction: EventType::FillCompleted,
+                event: EventType::Closed,
+            }
+        );
+        assert_eq!(
+            cross_check(Protocol::Dca, &make_instruction("Deposit"), &closed, &ctx).unwrap(),
+            CrossCheck::Unclassified {
+                instruction: None,
+                event: Some(EventType::Closed),
+            }
+        );
+
+        let malformed = make_event(
+            "ClosedEvent",
+            Some(serde_json::json!({ "ClosedEvent": { "dca_key": "order" } })),
+        );
+        assert!(
+            cross_check(
+                Protocol::Dca,
+                &make_instruction("CloseDca"),
+                &malformed,
+                &ctx
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn resolve_event_value_matches_raw_event_path() {
+        let fields = serde_json::json!({
+            "FilledEvent": { "dca_key": "order", "in_amount": 10_u64, "out_amount": 4_u64 }
+        });
+        let ctx = ResolveContext::default();
+        let via_value = resolve_event_value(Protocol::Dca, &fields, &ctx)
+            .unwrap()
+            .unwrap();
+        let via_event = adapter_for(Protocol::Dca)
+            .classify_and_resolve_event(&make_event("FilledEvent", Some(fields)), &ctx)
+            .unwrap()
+            .unwrap();
+        assert_eq!(via_value, via_event);
+    }
+
+    #[test]
+    fn resolve_cached_hits_on_repeat_and_rekeys_on_context_change() {
+        let ev = make_event(
+            "FilledEvent",
+            Some(serde_json::json!({
+                "FilledEvent": { "dca_key": "order", "in_amount": 10_u64, "out_amount": 4_u64 }
+            })),
+        );
+        let adapter = adapter_for(Protocol::Dca);
+        let ctx = ResolveContext::default();
+        let mut cache = ResolveCache::new();
+
+        let first = adapter
+            .resolve_cached(&ev, &ctx, &mut cache)
+            .unwrap()
+            .as_ref()
+            .unwrap()
+            .clone();
+        assert_eq!(cache.hits(), 0);
+        let second = adapter
+            .resolve_cached(&ev, &ctx, &mut cache)
+            .unwrap()
+            .as_ref()
+            .unwrap()
+            .clone();
+        assert_eq!(cache.hits(), 1);
+        assert_eq!(first, second);
+        assert_eq!(cache.len(), 1);
+
+        let saturating = ResolveContext {
+            saturate_amounts: true,
+            ..ResolveContext::default()
+        };
+        assert!(
+            adapter
+                .resolve_cached(&ev, &saturating, &mut cache)
+                .is_some()
+        );
+        assert_eq!(cache.hits(), 1);
+
+        let mut unknown = make_event(
+            "MysteryEvent",
+            Some(serde_json::json!({ "MysteryEvent": {} })),
+        );
+        unknown.event_index = ev.event_index + 1;
+        assert!(adapter.resolve_cached(&unknown, &ctx, &mut cache).is_none());
+        assert_eq!(cache.len(), 1);
+    }
 }
@@ -1,6 +1,18 @@
-use crate::lifecycle::{LifecycleTransition, TerminalStatus};
+use crate::lifecycle::{LifecycleTransition, MetadataReason, TerminalStatus};
 use crate::protocols::EventType;
 
+/// Opt-in adjustments to [`event_type_to_transition`].
+///
+/// [`Default`] reproduces the canonical mapping.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MappingOptions {
+    /// Map [`EventType::FillInitiated`] to `MetadataOnly` instead of `FillDelta`.
+    ///
+    /// Flash-fill starts carry no amounts; the real delta arrives with `FillCompleted`.
+    /// Defaults to `false` for one release, after which it becomes the default.
+    pub fill_initiated_is_metadata: bool,
+}
+
 /// Canonical mapping from [`EventType`] to [`LifecycleTransition`].
 ///
 /// `closed_status` is only used when `event_type` is [`EventType::Closed`] — it provides
@@ -9,9 +21,23 @@ use crate::protocols::EventType;
 pub fn event_type_to_transition(
     event_type: &EventType,
     closed_status: Option<TerminalStatus>,
+) -> LifecycleTransition {
+    event_type_to_transition_with(event_type, closed_status, MappingOptions::default())
+}
+
+/// [`event_type_to_transition`] with explicit [`MappingOptions`].
+pub fn event_type_to_transition_with(
+    event_type: &EventType,
+    closed_status: Option<TerminalStatus>,
+    options: MappingOptions,
 ) -> LifecycleTransition {
     match event_type {
         EventType::Created => LifecycleTransition::Create,
+        EventType::FillInitiated if options.fill_initiated_is_metadata => {
+            LifecycleTransition::MetadataOnly {
+                reason: MetadataReason::Diagnostic,
+            }
+        }
         EventType::FillInitiated | EventType::FillCompleted => LifecycleTransition::FillDelta,
         EventType::Cancelled => LifecycleTransition::Close {
             status: TerminalStatus::Cancelled,
@@ -21,11 +47,22 @@ pub fn event_type_to_transition(
         },
         EventType::Closed => match closed_status {
             Some(s) => LifecycleTransition::Close { status: s },
-            None => LifecycleTransition::MetadataOnly,
+            None => LifecycleTransition::MetadataOnly {
+                reason: MetadataReason::CloseWithoutStatus,
+            },
+        },
+        EventType::FeeCollected => LifecycleTransition::MetadataOnly {
+            reason: MetadataReason::Fee,
+        },
+        EventType::Withdrawn => LifecycleTransition::MetadataOnly {
+            reason: MetadataReason::Withdraw,
+        },
+        EventType::Deposited => LifecycleTransition::MetadataOnly {
+            reason: MetadataReason::Deposit,
+        },
+        EventType::Diagnostic => LifecycleTransition::MetadataOnly {
+            reason: MetadataReason::Diagnostic,
         },
-        EventType::FeeCollected | EventType::Withdrawn | EventType::Deposited => {
-            LifecycleTransition::MetadataOnly
-        }
     }
 }
 
@@ -43,7 +80,7 @@ pub fn transition_to_display(transition: &LifecycleTransition) -> String {
             );
             format!("Close({capitalized})")
         }
-        LifecycleTransition::MetadataOnly => "MetadataOnly".to_string(),
+        LifecycleTransition::MetadataOnly { .. } => "MetadataOnly".to_string(),
     }
 }
 
@@ -56,14 +93,78 @@ pub fn transition_target(transition: &LifecycleTransition) -> Option<&'static st
             TerminalStatus::Cancelled => Some("cancelled"),
             TerminalStatus::Expired => Some("expired"),
         },
-        LifecycleTransition::FillDelta | LifecycleTransition::MetadataOnly => None,
+        LifecycleTransition::FillDelta | LifecycleTransition::MetadataOnly { .. } => None,
+    }
+}
+
+/// Default leading characters kept by [`abbreviate_pubkey`] in summaries.
+pub const DEFAULT_PUBKEY_PREFIX: usize = 5;
+/// Default trailing characters kept by [`abbreviate_pubkey`] in summaries.
+pub const DEFAULT_PUBKEY_SUFFIX: usize = 5;
+
+/// Shortens a pubkey to `prefix` leading and `suffix` trailing characters joined by `…`
+/// (e.g. `"3nsTj…NR3YX"`).
+///
+/// Strings no longer than `prefix + suffix` are returned unchanged.
+pub fn abbreviate_pubkey(pubkey: &str, prefix: usize, suffix: usize) -> String {
+    let len = pubkey.chars().count();
+    if len <= prefix.saturating_add(suffix) {
+        return pubkey.to_string();
     }
+    let head: String = pubkey.chars().take(prefix).collect();
+    let tail: String = pubkey.chars().skip(len - suffix).collect();
+    format!("{head}…{tail}")
 }
 
 #[cfg(test)]
+#[expect(clippy::panic, reason = "test assertions")]
 mod tests {
     use super::*;
 
+    fn metadata(reason: MetadataReason) -> LifecycleTransition {
+        LifecycleTransition::MetadataOnly { reason }
+    }
+
+    #[test]
+    fn fill_initiated_mapping_follows_option() {
+        let fill_initiated_is_metadata = MappingOptions {
+            fill_initiated_is_metadata: true,
+        };
+        assert_eq!(
+            event_type_to_transition_with(
+                &EventType::FillInitiated,
+                None,
+                MappingOptions::default()
+            ),
+            LifecycleTransition::FillDelta
+        );
+        assert_eq!(
+            event_type_to_transition_with(
+                &EventType::FillInitiated,
+                None,
+                fill_initiated_is_metadata
+            ),
+            metadata(MetadataReason::Diagnostic)
+        );
+        assert_eq!(
+            event_type_to_transition_with(
+                &EventType::FillCompleted,
+                None,
+                fill_initiated_is_metadata
+            ),
+            LifecycleTransition::FillDelta
+        );
+        for event_type in EventType::ALL
+            .iter()
+            .filter(|et| **et != EventType::FillInitiated)
+        {
+            assert_eq!(
+                event_type_to_transition_with(event_type, None, fill_initiated_is_metadata),
+                event_type_to_transition(event_type, None)
+            );
+        }
+    }
+
     #[test]
     fn event_type_to_transition_covers_all_variants() {
         assert_eq!(
@@ -98,22 +199,44 @@ mod tests {
         );
         assert_eq!(
             event_type_to_transition(&EventType::Closed, None),
-            LifecycleTransition::MetadataOnly
+            metadata(MetadataReason::CloseWithoutStatus)
         );
         assert_eq!(
             event_type_to_transition(&EventType::FeeCollected, None),
-            LifecycleTransition::MetadataOnly
+            metadata(MetadataReason::Fee)
         );
         assert_eq!(
             event_type_to_transition(&EventType::Withdrawn, None),
-            LifecycleTransition::MetadataOnly
+            metadata(MetadataReason::Withdraw)
         );
         assert_eq!(
             event_type_to_transition(&EventType::Deposited, None),
-            LifecycleTransition::MetadataOnly
+            metadata(MetadataReason::Deposit)
+        );
+        assert_eq!(
+            event_type_to_transition(&EventType::Diagnostic, None),
+            metadata(MetadataReason::Diagnostic)
         );
     }
 
+    #[test]
+    fn metadata_reason_distinguishes_fee_and_deposit() {
+        let LifecycleTransition::MetadataOnly { reason: fee } =
+            event_type_to_transition(&EventType::FeeCollected, None)
+        else {
+            panic!("FeeCollected should be MetadataOnly");
+        };
+        let LifecycleTransition::MetadataOnly { reason: deposit } =
+            event_type_to_transition(&EventType::Deposited, None)
+        else {
+            panic!("Deposited should be MetadataOnly");
+        };
+        assert_eq!(fee, MetadataReason::Fee);
+        assert_eq!(deposit, MetadataReason::Deposit);
+        assert_ne!(fee, deposit);
+        assert_eq!(fee.as_ref(), "fee");
+    }
+
     #[test]
     fn transition_display_strings() {
         assert_eq!(
@@ -137,7 +260,7 @@ mod tests {
             "Close(Cancelled)"
         );
         assert_eq!(
-            transition_to_display(&LifecycleTransition::MetadataOnly),
+            transition_to_display(&metadata(MetadataReason::Diagnostic)),
             "MetadataOnly"
         );
     }
@@ -155,6 +278,25 @@ mod tests {
             Some("completed")
         );
         assert_eq!(transition_target(&LifecycleTransition::FillDelta), None);
-        assert_eq!(transition_target(&LifecycleTransition::MetadataOnly), None);
+        assert_eq!(
+            transition_target(&metadata(MetadataReason::Diagnostic)),
+            None
+        );
+    }
+
+    #[test]
+    fn abbreviate_pubkey_keeps_prefix_and_suffix() {
+        let pubkey = "3nsTjVJTwwGvXqDRgqNCZAQKwt4QMVhHHqvyseCNR3YX";
+        assert_eq!(
+            abbreviate_pubkey(pubkey, DEFAULT_PUBKEY_PREFIX, DEFAULT_PUBKEY_SUFFIX),
+            "3nsTj…NR3YX"
+        );
+        assert_eq!(abbreviate_pubkey(pubkey, 4, 0), "3nsT…");
+    }
+
+    #[test]
+    fn abbreviate_pubkey_leaves_short_strings_unchanged() {
+        assert_eq!(abbreviate_pubkey("order1", 5, 5), "order1");
+        assert_eq!(abbreviate_pubkey("0123456789", 5, 5), "0123456789");
     }
 }
@@ -1,5 +1,12 @@
 pub mod adapters;
 pub mod mapping;
+pub mod order;
+pub mod registry;
+pub mod replay;
+pub mod typed;
+
+use crate::protocols::EventType;
+use mapping::MappingOptions;
 
 /// Terminal state of a DeFi order lifecycle.
 #[derive(
@@ -11,8 +18,12 @@ pub mod mapping;
     strum_macros::Display,
     strum_macros::EnumString,
     strum_macros::AsRefStr,
+    serde::Serialize,
+    serde::Deserialize,
 )]
 #[strum(serialize_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
 pub enum TerminalStatus {
     /// All fills executed — order fully satisfied.
     Completed,
@@ -22,8 +33,93 @@ pub enum TerminalStatus {
     Expired,
 }
 
+impl TerminalStatus {
+    /// `true` only for [`TerminalStatus::Completed`].
+    pub fn is_success(&self) -> bool {
+        matches!(self, Self::Completed)
+    }
+
+    /// `true` for every terminal status except [`TerminalStatus::Completed`].
+    pub fn is_failure(&self) -> bool {
+        !self.is_success()
+    }
+
+    /// Stable UI styling token: `"success"`, `"neutral"`, or `"warning"`.
+    pub fn display_class(&self) -> &'static str {
+        match self {
+            Self::Completed => "success",
+            Self::Cancelled => "neutral",
+            Self::Expired => "warning",
+        }
+    }
+
+    /// Finality precedence used by [`Ord`]: `Completed` (2) > `Cancelled` (1) > `Expired` (0).
+    ///
+    /// A completion is directly evidenced by fills, a cancel by an explicit user or protocol
+    /// action, while expiry is often inferred from time alone, so stronger evidence wins when
+    /// racing sources disagree.
+    pub fn precedence(&self) -> u8 {
+        match self {
+            Self::Completed => 2,
+            Self::Cancelled => 1,
+            Self::Expired => 0,
+        }
+    }
+
+    /// The higher-[`precedence`](Self::precedence) of two terminal candidates.
+    pub fn max_of(a: Self, b: Self) -> Self {
+        a.max(b)
+    }
+}
+
+impl PartialOrd for TerminalStatus {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TerminalStatus {
+    /// Orders by [`TerminalStatus::precedence`], not declaration order.
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.precedence().cmp(&other.precedence())
+    }
+}
+
+/// Why a transition is [`LifecycleTransition::MetadataOnly`], for observability.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    strum_macros::Display,
+    strum_macros::EnumString,
+    strum_macros::AsRefStr,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum MetadataReason {
+    /// Protocol or host fee collection.
+    Fee,
+    /// Funds deposited into an order.
+    Deposit,
+    /// Funds withdrawn from an order.
+    Withdraw,
+    /// Diagnostic or informational event with no state effect.
+    Diagnostic,
+    /// A `Closed` event whose terminal status could not be derived.
+    CloseWithoutStatus,
+}
+
 /// A state-mutating action the consumer wants to apply to an order.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///
+/// Serializes internally tagged, e.g. `{"type": "Close", "status": "completed"}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type")]
+#[non_exhaustive]
 pub enum LifecycleTransition {
     /// Order was created on-chain.
     Create,
@@ -33,16 +129,20 @@ pub enum LifecycleTransition {
     Close { status: TerminalStatus },
     /// Non-state-mutating update (e.g. diagnostic events, display snapshots).
     /// Always accepted, even after the order is terminal.
-    MetadataOnly,
+    MetadataOnly { reason: MetadataReason },
 }
 
 /// Result of [`LifecycleEngine::decide_transition`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum TransitionDecision {
     /// Transition is valid — the consumer should apply it.
     Apply,
     /// Order is already terminal; this state-mutating transition is rejected.
     IgnoreTerminalViolation,
+    /// A `Close` re-targeting the terminal status the order already holds: a harmless
+    /// duplicate, ignored without being treated as a violation.
+    IgnoreNoChange,
 }
 
 /// The result of converting a cumulative snapshot into an incremental delta.
@@ -57,30 +157,138 @@ pub struct SnapshotDelta {
     pub regression: bool,
 }
 
-/// Stateless decision engine for order lifecycle state machines.
-pub struct LifecycleEngine;
+/// Result of [`LifecycleEngine::simulate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimulationResult {
+    /// Decision for each input transition, in order.
+    pub decisions: Vec<TransitionDecision>,
+    /// Terminal status after the whole sequence; `None` if the order never closed.
+    pub final_terminal: Option<TerminalStatus>,
+}
+
+/// Tunable engine behaviors, aggregated in one place.
+///
+/// [`Default`] reproduces the canonical behavior of the associated-function API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EngineConfig {
+    /// Options for the [`EventType`](crate::EventType) → [`LifecycleTransition`] mapping.
+    pub mapping: MappingOptions,
+    /// Treat a Kamino display status `Filled` as `Completed` even with nonzero remaining input.
+    ///
+    /// When `false`, such a snapshot stays a non-terminal fill: a partially filled order can
+    /// report an intermediate `Filled` before it is cancelled. Defaults to `true`. Applied to
+    /// resolution through [`ResolveContext::treat_filled_as_terminal`](crate::ResolveContext).
+    pub treat_filled_as_terminal: bool,
+}
+
+impl Default for EngineConfig {
+    fn default() -> Self {
+        Self {
+            mapping: MappingOptions::default(),
+            treat_filled_as_terminal: true,
+        }
+    }
+}
+
+/// Decision engine for order lifecycle state machines.
+///
+/// The associated functions are stateless and use default behavior. A value built with
+/// [`LifecycleEngine::with_config`] carries an [`EngineConfig`] for the config-aware methods.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LifecycleEngine {
+    config: EngineConfig,
+}
 
 impl LifecycleEngine {
+    /// Creates an engine carrying `config`.
+    pub fn with_config(config: EngineConfig) -> Self {
+        Self { config }
+    }
+
+    /// Configuration this engine was built with.
+    pub fn config(&self) -> &EngineConfig {
+        &self.config
+    }
+
+    /// Maps `event_type` to a transition using this engine's [`MappingOptions`].
+    ///
+    /// See [`mapping::event_type_to_transition_with`].
+    pub fn transition_for(
+        &self,
+        event_type: &EventType,
+        closed_status: Option<TerminalStatus>,
+    ) -> LifecycleTransition {
+        mapping::event_type_to_transition_with(event_type, closed_status, self.config.mapping)
+    }
+
     /// Decides whether `transition` should be applied given the order's current terminal state.
     ///
     /// Non-terminal orders (`None`) accept all transitions.
-    /// Terminal orders only accept [`LifecycleTransition::MetadataOnly`].
+    /// Terminal orders only accept [`LifecycleTransition::MetadataOnly`]; a `Close` to the status
+    /// they already hold is [`TransitionDecision::IgnoreNoChange`].
     pub fn decide_transition(
         current_terminal: Option<TerminalStatus>,
         transition: LifecycleTransition,
     ) -> TransitionDecision {
-        if current_terminal.is_none() {
+        let Some(current) = current_terminal else {
             return TransitionDecision::Apply;
-        }
+        };
 
         match transition {
-            LifecycleTransition::MetadataOnly => TransitionDecision::Apply,
+            LifecycleTransition::MetadataOnly { .. } => TransitionDecision::Apply,
+            LifecycleTransition::Close { status } if status == current => {
+                TransitionDecision::IgnoreNoChange
+            }
             LifecycleTransition::Create
             | LifecycleTransition::FillDelta
             | LifecycleTransition::Close { .. } => TransitionDecision::IgnoreTerminalViolation,
         }
     }
 
+    /// `true` iff [`Self::decide_transition`] yields [`TransitionDecision::Apply`].
+    pub fn can_apply(
+        current_terminal: Option<TerminalStatus>,
+        transition: LifecycleTransition,
+    ) -> bool {
+        Self::decide_transition(current_terminal, transition) == TransitionDecision::Apply
+    }
+
+    /// Decides `transition` and returns the terminal status the order holds afterwards.
+    ///
+    /// The terminal status only changes on an applied [`LifecycleTransition::Close`];
+    /// rejected and non-close transitions return `current_terminal` unchanged.
+    pub fn next_state(
+        current_terminal: Option<TerminalStatus>,
+        transition: LifecycleTransition,
+    ) -> (TransitionDecision, Option<TerminalStatus>) {
+        let decision = Self::decide_transition(current_terminal, transition);
+        let next = match (decision, transition) {
+            (TransitionDecision::Apply, LifecycleTransition::Close { status }) => Some(status),
+            _ => current_terminal,
+        };
+        (decision, next)
+    }
+
+    /// Folds [`Self::next_state`] over `transitions`, starting from a non-terminal order.
+    ///
+    /// `decisions` is index-aligned with `transitions`. Slots do not affect decisions; they are
+    /// accepted so sequences recorded per slot can be validated unchanged.
+    pub fn simulate(transitions: &[(i64, LifecycleTransition)]) -> SimulationResult {
+        let mut final_terminal = None;
+        let decisions = transitions
+            .iter()
+            .map(|&(_, transition)| {
+                let (decision, next) = Self::next_state(final_terminal, transition);
+                final_terminal = next;
+                decision
+            })
+            .collect();
+        SimulationResult {
+            decisions,
+            final_terminal,
+        }
+    }
+
     /// Converts a cumulative snapshot into a non-negative delta relative to `stored_total`.
     ///
     /// If the snapshot regressed, delta is clamped to 0 and `regression` is flagged.
@@ -94,9 +302,15 @@ impl LifecycleEngine {
 }
 
 #[cfg(test)]
+#[expect(clippy::unwrap_used, reason = "test assertions")]
 mod tests {
     use super::{
-        LifecycleEngine, LifecycleTransition, SnapshotDelta, TerminalStatus, TransitionDecision,
+        LifecycleEngine, LifecycleTransition, MetadataReason, SimulationResult, SnapshotDelta,
+        TerminalStatus, TransitionDecision,
+    };
+
+    const DIAGNOSTIC: LifecycleTransition = LifecycleTransition::MetadataOnly {
+        reason: MetadataReason::Diagnostic,
     };
 
     fn lcg_next(state: &mut u64) -> u64 {
@@ -119,7 +333,75 @@ mod tests {
             4 => LifecycleTransition::Close {
                 status: TerminalStatus::Expired,
             },
-            _ => LifecycleTransition::MetadataOnly,
+            _ => DIAGNOSTIC,
+        }
+    }
+
+    #[test]
+    fn transition_serde_roundtrip() {
+        let transitions = [
+            LifecycleTransition::Create,
+            LifecycleTransition::FillDelta,
+            LifecycleTransition::Close {
+                status: TerminalStatus::Completed,
+            },
+            LifecycleTransition::Close {
+                status: TerminalStatus::Cancelled,
+            },
+            LifecycleTransition::Close {
+                status: TerminalStatus::Expired,
+            },
+            LifecycleTransition::MetadataOnly {
+                reason: MetadataReason::Fee,
+            },
+            LifecycleTransition::MetadataOnly {
+                reason: MetadataReason::CloseWithoutStatus,
+            },
+        ];
+        for transition in transitions {
+            let json = serde_json::to_value(transition).unwrap();
+            let back: LifecycleTransition = serde_json::from_value(json.clone()).unwrap();
+            assert_eq!(back, transition, "{json}");
+        }
+
+        assert_eq!(
+            serde_json::to_value(LifecycleTransition::Close {
+                status: TerminalStatus::Expired
+            })
+            .unwrap(),
+            serde_json::json!({"type": "Close", "status": "expired"})
+        );
+        assert_eq!(
+            serde_json::to_value(LifecycleTransition::MetadataOnly {
+                reason: MetadataReason::CloseWithoutStatus
+            })
+            .unwrap(),
+            serde_json::json!({"type": "MetadataOnly", "reason": "close_without_status"})
+        );
+    }
+
+    #[test]
+    fn terminal_status_success_predicates() {
+        let cases = [
+            (TerminalStatus::Completed, true),
+            (TerminalStatus::Cancelled, false),
+            (TerminalStatus::Expired, false),
+        ];
+        for (status, success) in cases {
+            assert_eq!(status.is_success(), success, "{status}");
+            assert_eq!(status.is_failure(), !success, "{status}");
+        }
+    }
+
+    #[test]
+    fn terminal_status_display_classes_are_pinned() {
+        let cases = [
+            (TerminalStatus::Completed, "success"),
+            (TerminalStatus::Cancelled, "neutral"),
+            (TerminalStatus::Expired, "warning"),
+        ];
+        for (status, class) in cases {
+            assert_eq!(status.display_class(), class, "{status}");
         }
     }
 
@@ -141,6 +423,21 @@ mod tests {
         assert_eq!(TerminalStatus::Completed.to_string(), "completed");
     }
 
+    #[test]
+    fn completed_wins_terminal_precedence() {
+        for other in [TerminalStatus::Expired, TerminalStatus::Cancelled] {
+            assert_eq!(
+                TerminalStatus::max_of(TerminalStatus::Completed, other),
+                TerminalStatus::Completed
+            );
+            assert_eq!(
+                TerminalStatus::max_of(other, TerminalStatus::Completed),
+                TerminalStatus::Completed
+            );
+        }
+        assert!(TerminalStatus::Cancelled > TerminalStatus::Expired);
+    }
+
     #[test]
     fn terminal_orders_reject_state_mutating_transitions() {
         let current = Some(TerminalStatus::Completed);
@@ -162,7 +459,7 @@ mod tests {
             TransitionDecision::IgnoreTerminalViolation
         );
         assert_eq!(
-            LifecycleEngine::decide_transition(current, LifecycleTransition::MetadataOnly),
+            LifecycleEngine::decide_transition(current, DIAGNOSTIC),
             TransitionDecision::Apply
         );
     }
@@ -226,9 +523,12 @@ mod tests {
                 let transition = random_transition(&mut seed);
                 let decision = LifecycleEngine::decide_transition(Some(status), transition);
                 match transition {
-                    LifecycleTransition::MetadataOnly => {
+                    LifecycleTransition::MetadataOnly { .. } => {
                         assert_eq!(decision, TransitionDecision::Apply);
                     }
+                    LifecycleTransition::Close { status: target } if target == status => {
+                        assert_eq!(decision, TransitionDecision::IgnoreNoChange);
+                    }
                     LifecycleTransition::Create
                     | LifecycleTransition::FillDelta
                     | LifecycleTransition::Close { .. } => {
@@ -250,6 +550,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn can_apply_agrees_with_decide_transition() {
+        let currents = [
+            None,
+            Some(TerminalStatus::Completed),
+            Some(TerminalStatus::Cancelled),
+            Some(TerminalStatus::Expired),
+        ];
+        let mut seed = 0x5EED_CAFE_u64;
+
+        for current in currents {
+            for _ in 0..2_000 {
+                let transition = random_transition(&mut seed);
+                assert_eq!(
+                    LifecycleEngine::can_apply(current, transition),
+                    LifecycleEngine::decide_transition(current, transition)
+                        == TransitionDecision::Apply,
+                    "{transition:?} with terminal {current:?}"
+                );
+            }
+        }
+    }
+
     fn apply_sequence(steps: &[(LifecycleTransition, TransitionDecision)]) {
         let mut current_terminal: Option<TerminalStatus> = None;
 
@@ -287,6 +610,34 @@ mod tests {
         ]);
     }
 
+    #[test]
+    fn simulate_reports_dca_happy_path() {
+        let close = LifecycleTransition::Close {
+            status: TerminalStatus::Completed,
+        };
+        let result = LifecycleEngine::simulate(&[
+            (10, LifecycleTransition::Create),
+            (11, LifecycleTransition::FillDelta),
+            (12, LifecycleTransition::FillDelta),
+            (13, close),
+            (14, LifecycleTransition::FillDelta),
+        ]);
+        assert_eq!(
+            result,
+            SimulationResult {
+                decisions: vec![
+                    TransitionDecision::Apply,
+                    TransitionDecision::Apply,
+                    TransitionDecision::Apply,
+                    TransitionDecision::Apply,
+                    TransitionDecision::IgnoreTerminalViolation,
+                ],
+                final_terminal: Some(TerminalStatus::Completed),
+            }
+        );
+        assert_eq!(LifecycleEngine::simulate(&[]).final_terminal, None);
+    }
+
     #[test]
     fn lifecycle_sequence_limit_cancel() {
         apply_sequence(&[
@@ -332,8 +683,8 @@ mod tests {
                 },
                 TransitionDecision::Apply,
             ),
-            (LifecycleTransition::MetadataOnly, TransitionDecision::Apply),
-            (LifecycleTransition::MetadataOnly, TransitionDecision::Apply),
+            (DIAGNOSTIC, TransitionDecision::Apply),
+            (DIAGNOSTIC, TransitionDecision::Apply),
             (
                 LifecycleTransition::Close {
                     status: TerminalStatus::Cancelled,
@@ -342,4 +693,74 @@ mod tests {
             ),
         ]);
     }
+
+    #[test]
+    fn duplicate_terminal_close_is_no_change_not_violation() {
+        let cancelled = Some(TerminalStatus::Cancelled);
+        assert_eq!(
+            LifecycleEngine::next_state(
+                cancelled,
+                LifecycleTransition::Close {
+                    status: TerminalStatus::Cancelled
+                }
+            ),
+            (TransitionDecision::IgnoreNoChange, cancelled)
+        );
+        assert_eq!(
+            LifecycleEngine::next_state(
+                cancelled,
+                LifecycleTransition::Close {
+                    status: TerminalStatus::Completed
+                }
+            ),
+            (TransitionDecision::IgnoreTerminalViolation, cancelled)
+        );
+    }
+
+    #[test]
+    fn next_state_sets_terminal_on_accepted_close() {
+        assert_eq!(
+            LifecycleEngine::next_state(
+                None,
+                LifecycleTransition::Close {
+                    status: TerminalStatus::Cancelled
+                }
+            ),
+            (TransitionDecision::Apply, Some(TerminalStatus::Cancelled))
+        );
+    }
+
+    #[test]
+    fn next_state_keeps_terminal_on_rejected_close() {
+        assert_eq!(
+            LifecycleEngine::next_state(
+                Some(TerminalStatus::Completed),
+                LifecycleTransition::Close {
+                    status: TerminalStatus::Expired
+                }
+            ),
+            (
+                TransitionDecision::IgnoreTerminalViolation,
+                Some(TerminalStatus::Completed)
+            )
+        );
+    }
+
+    #[test]
+    fn next_state_leaves_terminal_unchanged_on_fill() {
+        assert_eq!(
+            LifecycleEngine::next_state(None, LifecycleTransition::FillDelta),
+            (TransitionDecision::Apply, None)
+        );
+        assert_eq!(
+            LifecycleEngine::next_state(
+                Some(TerminalStatus::Expired),
+                LifecycleTransition::FillDelta
+            ),
+            (
+                TransitionDecision::IgnoreTerminalViolation,
+                Some(TerminalStatus::Expired)
+            )
+        );
+    }
 }
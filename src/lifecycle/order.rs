@@ -0,0 +1,1036 @@
+use std::sync::Arc;
+
+use crate::error::{Error, ErrorCode};
+use crate::lifecycle::adapters::EventPayload;
+use crate::lifecycle::{
+    EngineConfig, LifecycleEngine, LifecycleTransition, TerminalStatus, TransitionDecision,
+};
+use crate::protocols::EventType;
+
+/// Running fill totals for a single order.
+///
+/// Totals use checked arithmetic: an overflowing fill is rejected with [`Error::Protocol`]
+/// and leaves the aggregate untouched, rather than silently clamping.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FillAggregate {
+    /// Cumulative input amount across all fills.
+    pub total_in: i64,
+    /// Cumulative output amount across all fills.
+    pub total_out: i64,
+    /// Number of fills accumulated.
+    pub fill_count: u64,
+}
+
+impl FillAggregate {
+    /// Adds one fill to the running totals.
+    ///
+    /// All-or-nothing: if any total would overflow, no field is updated.
+    pub fn add_fill(&mut self, in_amount: i64, out_amount: i64) -> Result<(), Error> {
+        let total_in = Self::checked_accumulate("total_in", self.total_in, in_amount)?;
+        let total_out = Self::checked_accumulate("total_out", self.total_out, out_amount)?;
+        let fill_count = self
+            .fill_count
+            .checked_add(1)
+            .ok_or_else(|| Error::Protocol {
+                code: ErrorCode::AmountOverflow,
+                reason: format!("fill_count overflow: {} + 1", self.fill_count),
+            })?;
+
+        self.total_in = total_in;
+        self.total_out = total_out;
+        self.fill_count = fill_count;
+        Ok(())
+    }
+
+    fn checked_accumulate(field: &str, current: i64, amount: i64) -> Result<i64, Error> {
+        current.checked_add(amount).ok_or_else(|| Error::Protocol {
+            code: ErrorCode::AmountOverflow,
+            reason: format!("{field} overflow: {current} + {amount} exceeds i64 range"),
+        })
+    }
+}
+
+/// State change made by one accepted transition, kept so [`OrderLifecycle::rollback_to_slot`]
+/// can undo it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AppliedChange {
+    Created {
+        prior_slot: Option<i64>,
+    },
+    Filled {
+        in_amount: i64,
+        out_amount: i64,
+    },
+    Closed {
+        prior_terminal: Option<TerminalStatus>,
+        prior_slot: Option<i64>,
+    },
+    Reconciled {
+        authoritative_out: i64,
+    },
+}
+
+/// Result of [`OrderLifecycle::reconcile_on_close`]: the accumulated output total versus the
+/// authoritative total reported at close.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReconcileReport {
+    /// `total_out` accumulated from applied fills, before reconciliation.
+    pub accumulated_out: i64,
+    pub authoritative_out: i64,
+}
+
+impl ReconcileReport {
+    /// `authoritative_out - accumulated_out` (saturating); positive when fills were missed.
+    pub fn discrepancy_out(&self) -> i64 {
+        self.authoritative_out.saturating_sub(self.accumulated_out)
+    }
+
+    /// `true` when the output total disagreed with the authoritative value.
+    pub fn has_discrepancy(&self) -> bool {
+        self.accumulated_out != self.authoritative_out
+    }
+}
+
+/// Result of [`OrderLifecycle::apply`]: the engine's decision, the resulting terminal status,
+/// and the fill delta that was counted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransitionOutcome {
+    pub decision: TransitionDecision,
+    /// Terminal status after the transition (unchanged when ignored).
+    pub terminal: Option<TerminalStatus>,
+    /// Input amount added to [`FillAggregate::total_in`]; `0` unless a fill was counted.
+    pub applied_in: i64,
+    /// Output amount added to [`FillAggregate::total_out`]; `0` unless a fill was counted.
+    pub applied_out: i64,
+    /// 1-based position of this fill on the order (1st, 2nd, ...), set only for a counted
+    /// [`LifecycleTransition::FillDelta`]. Rolled-back fills release their numbers.
+    pub fill_seq: Option<u64>,
+}
+
+/// What [`OrderLifecycle::apply`] would do, computed by [`OrderLifecycle::preview`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LifecyclePreview {
+    pub decision: TransitionDecision,
+    /// Terminal status after the transition.
+    pub terminal: Option<TerminalStatus>,
+    /// Fill totals after the transition.
+    pub fills: FillAggregate,
+    /// Ordinal the fill would receive (see [`TransitionOutcome::fill_seq`]).
+    pub fill_seq: Option<u64>,
+}
+
+/// A transition [`OrderLifecycle`] refused, passed to the [`OrderLifecycle::on_reject`] hook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RejectRecord<'a> {
+    /// Signature of the triggering row, when applied via [`OrderLifecycle::apply_signed`].
+    pub signature: Option<&'a str>,
+    pub slot: i64,
+    /// The transition that was attempted.
+    pub transition: LifecycleTransition,
+    pub decision: TransitionDecision,
+    /// Terminal status the order was already in.
+    pub terminal: Option<TerminalStatus>,
+    /// Fill totals at the time of the rejection.
+    pub fills: FillAggregate,
+}
+
+type RejectHook = Arc<dyn Fn(&RejectRecord<'_>) + Send + Sync>;
+
+/// Optional reject callback; not part of the order's state, so it never affects equality.
+#[derive(Clone, Default)]
+struct RejectHookSlot(Option<RejectHook>);
+
+impl std::fmt::Debug for RejectHookSlot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(if self.0.is_some() {
+            "Some(<hook>)"
+        } else {
+            "None"
+        })
+    }
+}
+
+impl PartialEq for RejectHookSlot {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for RejectHookSlot {}
+
+/// Minimal in-memory order state: terminal status, accumulated fills, and create/close slots.
+///
+/// Transitions are gated through [`LifecycleEngine::decide_transition`], so a terminal
+/// order ignores further fills and closes. Every accepted create, counted fill, and close is
+/// logged with its slot for [`Self::rollback_to_slot`], so memory grows with the fill count.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OrderLifecycle {
+    terminal: Option<TerminalStatus>,
+    fills: FillAggregate,
+    created_slot: Option<i64>,
+    closed_slot: Option<i64>,
+    clock_anomaly: bool,
+    engine: LifecycleEngine,
+    applied: Vec<(i64, AppliedChange)>,
+    reject_hook: RejectHookSlot,
+}
+
+impl OrderLifecycle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an order whose [`Self::apply_event`] mapping follows `config`.
+    pub fn with_config(config: EngineConfig) -> Self {
+        Self {
+            engine: LifecycleEngine::with_config(config),
+            ..Self::default()
+        }
+    }
+
+    /// Configuration this order was built with.
+    pub fn config(&self) -> &EngineConfig {
+        self.engine.config()
+    }
+
+    /// Registers a callback invoked with a [`RejectRecord`] for every rejected transition,
+    /// replacing any previous hook. Clones of the order share the hook.
+    ///
+    /// Lets consumers audit rejections with their own logging; the hook runs synchronously
+    /// inside [`Self::apply`] and cannot alter the decision. Duplicate closes
+    /// ([`TransitionDecision::IgnoreNoChange`]) are not rejections and are not reported.
+    pub fn on_reject(&mut self, hook: Box<dyn Fn(&RejectRecord<'_>) + Send + Sync>) {
+        self.reject_hook = RejectHookSlot(Some(Arc::from(hook)));
+    }
+
+    /// Terminal status, or `None` while the order is still active.
+    pub fn terminal(&self) -> Option<TerminalStatus> {
+        self.terminal
+    }
+
+    /// Fill totals accumulated so far.
+    pub fn fills(&self) -> &FillAggregate {
+        &self.fills
+    }
+
+    /// Volume-weighted average execution price over all applied fills, as output per unit input.
+    ///
+    /// `total_out / total_in` from [`Self::fills`]; `None` before any fill or when `total_in`
+    /// is zero.
+    pub fn vwap_out_per_in(&self) -> Option<f64> {
+        let FillAggregate {
+            total_in,
+            total_out,
+            fill_count,
+        } = self.fills;
+        if fill_count == 0 || total_in == 0 {
+            return None;
+        }
+        Some(total_out as f64 / total_in as f64)
+    }
+
+    /// Slot of the accepted `Create`, if seen.
+    pub fn created_slot(&self) -> Option<i64> {
+        self.created_slot
+    }
+
+    /// Slot of the accepted `Close`, if seen.
+    pub fn closed_slot(&self) -> Option<i64> {
+        self.closed_slot
+    }
+
+    /// `true` when the close slot precedes the create slot (e.g. reordered ingestion).
+    pub fn clock_anomaly(&self) -> bool {
+        self.clock_anomaly
+    }
+
+    /// Slots elapsed between create and close, or `None` until both are known.
+    ///
+    /// Also `None` when [`Self::clock_anomaly`] is set, since the difference would be negative.
+    pub fn duration_slots(&self) -> Option<i64> {
+        let (created, closed) = (self.created_slot?, self.closed_slot?);
+        closed.checked_sub(created).filter(|d| *d >= 0)
+    }
+
+    /// Maps `event_type` through the configured engine, then [`Self::apply`]s the transition.
+    pub fn apply_event(
+        &mut self,
+        event_type: &EventType,
+        closed_status: Option<TerminalStatus>,
+        payload: &EventPayload,
+        slot: i64,
+    ) -> Result<TransitionOutcome, Error> {
+        let transition = self.engine.transition_for(event_type, closed_status);
+        self.apply(transition, payload, slot)
+    }
+
+    /// Computes the decision and resulting terminal status and fill totals of applying
+    /// `transition`, without mutating the order.
+    ///
+    /// Errors exactly when [`Self::apply`] would (fill total overflow). Pair with `apply` for a
+    /// two-phase commit: preview, persist, then apply the same transition.
+    pub fn preview(
+        &self,
+        transition: LifecycleTransition,
+        payload: &EventPayload,
+    ) -> Result<LifecyclePreview, Error> {
+        let (decision, next_terminal) = LifecycleEngine::next_state(self.terminal, transition);
+        let mut fills = self.fills;
+        if decision != TransitionDecision::Apply {
+            return Ok(LifecyclePreview {
+                decision,
+                terminal: self.terminal,
+                fills,
+                fill_seq: None,
+            });
+        }
+
+        let mut fill_seq = None;
+        if let Some((in_amount, out_amount)) = Self::counted_fill(transition, payload) {
+            fills.add_fill(in_amount, out_amount)?;
+            fill_seq = Some(fills.fill_count);
+        }
+        Ok(LifecyclePreview {
+            decision,
+            terminal: next_terminal,
+            fills,
+            fill_seq,
+        })
+    }
+
+    /// Applies `transition` observed at `slot`, reading fill amounts from `payload` for
+    /// [`LifecycleTransition::FillDelta`].
+    ///
+    /// Fill payloads without amounts (e.g. a fill-initiated instruction) are accepted but not counted.
+    /// `slot` is the triggering `RawEvent`/`RawInstruction` slot; it is recorded for accepted
+    /// `Create` and `Close` transitions. Returns a [`TransitionOutcome`]; on error the order state
+    /// is unchanged.
+    pub fn apply(
+        &mut self,
+        transition: LifecycleTransition,
+        payload: &EventPayload,
+        slot: i64,
+    ) -> Result<TransitionOutcome, Error> {
+        self.apply_inner(None, transition, payload, slot)
+    }
+
+    /// [`Self::apply`], tagging any [`RejectRecord`] with the triggering row's `signature`.
+    pub fn apply_signed(
+        &mut self,
+        signature: &str,
+        transition: LifecycleTransition,
+        payload: &EventPayload,
+        slot: i64,
+    ) -> Result<TransitionOutcome, Error> {
+        self.apply_inner(Some(signature), transition, payload, slot)
+    }
+
+    fn apply_inner(
+        &mut self,
+        signature: Option<&str>,
+        transition: LifecycleTransition,
+        payload: &EventPayload,
+        slot: i64,
+    ) -> Result<TransitionOutcome, Error> {
+        let LifecyclePreview {
+            decision,
+            terminal,
+            fills,
+            fill_seq,
+        } = self.preview(transition, payload)?;
+        let mut outcome = TransitionOutcome {
+            decision,
+            terminal,
+            applied_in: 0,
+            applied_out: 0,
+            fill_seq,
+        };
+        if decision != TransitionDecision::Apply {
+            if decision == TransitionDecision::IgnoreTerminalViolation
+                && let Some(hook) = &self.reject_hook.0
+            {
+                hook(&RejectRecord {
+                    signature,
+                    slot,
+                    transition,
+                    decision,
+                    terminal: self.terminal,
+                    fills: self.fills,
+                });
+            }
+            return Ok(outcome);
+        }
+
+        if let Some((in_amount, out_amount)) = Self::counted_fill(transition, payload) {
+            self.applied.push((
+                slot,
+                AppliedChange::Filled {
+                    in_amount,
+                    out_amount,
+                },
+            ));
+            outcome.applied_in = in_amount;
+            outcome.applied_out = out_amount;
+        }
+        self.fills = fills;
+        match transition {
+            LifecycleTransition::Create => {
+                self.applied.push((
+                    slot,
+                    AppliedChange::Created {
+                        prior_slot: self.created_slot,
+                    },
+                ));
+                self.created_slot = Some(slot);
+            }
+            LifecycleTransition::Close { .. } => {
+                self.applied.push((
+                    slot,
+                    AppliedChange::Closed {
+                        prior_terminal: self.terminal,
+                        prior_slot: self.closed_slot,
+                    },
+                ));
+                self.closed_slot = Some(slot);
+            }
+            LifecycleTransition::FillDelta | LifecycleTransition::MetadataOnly { .. } => {}
+        }
+        self.terminal = terminal;
+        self.refresh_clock_anomaly();
+        Ok(outcome)
+    }
+
+    /// Fill amounts counted by a `FillDelta`; payloads without amounts are not counted.
+    fn counted_fill(transition: LifecycleTransition, payload: &EventPayload) -> Option<(i64, i64)> {
+        if transition != LifecycleTransition::FillDelta {
+            return None;
+        }
+        match payload {
+            EventPayload::DcaFill {
+                in_amount,
+                out_amount,
+                ..
+            }
+            | EventPayload::LimitFill {
+                in_amount,
+                out_amount,
+                ..
+            } => Some((*in_amount, *out_amount)),
+            _ => None,
+        }
+    }
+
+    /// Undoes every accepted create, fill, and close observed at a slot after `slot`
+    /// (e.g. when a reorg invalidates those blocks).
+    ///
+    /// Fill totals are re-derived by replaying the kept fills and reconciliations in log order,
+    /// so a kept reconciliation never discards the fills around it. Creates and closes are
+    /// reverted newest-first, each restoring the slot (and terminal status) that preceded it.
+    /// Transitions at or before `slot` are kept, even if they were applied after a rolled-back one.
+    ///
+    /// Errors with [`ErrorCode::AmountOverflow`] when the kept fills no longer sum within range
+    /// (a corrupted fill history); the order is then left unchanged.
+    pub fn rollback_to_slot(&mut self, slot: i64) -> Result<(), Error> {
+        let (kept, undone): (Vec<_>, Vec<_>) = self
+            .applied
+            .iter()
+            .copied()
+            .partition(|(applied_slot, _)| *applied_slot <= slot);
+
+        let mut fills = FillAggregate::default();
+        for (_, change) in &kept {
+            match *change {
+                AppliedChange::Filled {
+                    in_amount,
+                    out_amount,
+                } => fills.add_fill(in_amount, out_amount)?,
+                AppliedChange::Reconciled { authoritative_out } => {
+                    fills.total_out = authoritative_out;
+                }
+                AppliedChange::Created { .. } | AppliedChange::Closed { .. } => {}
+            }
+        }
+
+        for (_, change) in undone.into_iter().rev() {
+            match change {
+                AppliedChange::Created { prior_slot } => self.created_slot = prior_slot,
+                AppliedChange::Closed {
+                    prior_terminal,
+                    prior_slot,
+                } => {
+                    self.terminal = prior_terminal;
+                    self.closed_slot = prior_slot;
+                }
+                AppliedChange::Filled { .. } | AppliedChange::Reconciled { .. } => {}
+            }
+        }
+        self.applied = kept;
+        self.fills = fills;
+        self.refresh_clock_anomaly();
+        Ok(())
+    }
+
+    /// Compares the accumulated output total with the authoritative output reported at close
+    /// (e.g. DCA `total_out_withdrawn`) and snaps `total_out` to it.
+    ///
+    /// Only the output side is reconciled: the close-reported input (DCA `total_in_withdrawn`)
+    /// is unspent input returned to the user, not input filled. `total_in` and `fill_count` are
+    /// unchanged. The correction is logged at the close slot, so
+    /// [`Self::rollback_to_slot`] undoes it together with the close. `None`, leaving the order
+    /// untouched, when the order has not closed.
+    pub fn reconcile_on_close(&mut self, authoritative_out: i64) -> Option<ReconcileReport> {
+        self.terminal?;
+        let closed_slot = self.closed_slot?;
+        let report = ReconcileReport {
+            accumulated_out: self.fills.total_out,
+            authoritative_out,
+        };
+        if report.has_discrepancy() {
+            self.applied
+                .push((closed_slot, AppliedChange::Reconciled { authoritative_out }));
+            self.fills.total_out = authoritative_out;
+        }
+        Some(report)
+    }
+
+    fn refresh_clock_anomaly(&mut self) {
+        self.clock_anomaly = matches!(
+            (self.created_slot, self.closed_slot),
+            (Some(created), Some(closed)) if closed < created
+        );
+    }
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used, clippy::panic, reason = "test assertions")]
+mod tests {
+    use super::*;
+    use crate::lifecycle::mapping::MappingOptions;
+
+    fn dca_fill(in_amount: i64, out_amount: i64) -> EventPayload {
+        EventPayload::DcaFill {
+            in_amount,
+            out_amount,
+            saturated: false,
+            cycle: None,
+        }
+    }
+
+    #[test]
+    fn vwap_blends_fills_by_volume() {
+        let mut order = OrderLifecycle::new();
+        order
+            .apply(LifecycleTransition::Create, &EventPayload::None, 1)
+            .unwrap();
+        assert_eq!(order.vwap_out_per_in(), None);
+
+        order
+            .apply(LifecycleTransition::FillDelta, &dca_fill(100, 50), 2)
+            .unwrap();
+        assert_eq!(order.vwap_out_per_in(), Some(0.5));
+        order
+            .apply(LifecycleTransition::FillDelta, &dca_fill(300, 30), 3)
+            .unwrap();
+        assert_eq!(order.vwap_out_per_in(), Some(0.2));
+
+        let mut zero_in = OrderLifecycle::new();
+        zero_in
+            .apply(LifecycleTransition::FillDelta, &dca_fill(0, 10), 1)
+            .unwrap();
+        assert_eq!(zero_in.fills().fill_count, 1);
+        assert_eq!(zero_in.vwap_out_per_in(), None);
+    }
+
+    #[test]
+    fn fills_accumulate_until_close() {
+        let mut order = OrderLifecycle::new();
+        order
+            .apply(LifecycleTransition::Create, &EventPayload::None, 1)
+            .unwrap();
+        order
+            .apply(LifecycleTransition::FillDelta, &dca_fill(100, 40), 2)
+            .unwrap();
+        order
+            .apply(LifecycleTransition::FillDelta, &EventPayload::None, 3)
+            .unwrap();
+        order
+            .apply(LifecycleTransition::FillDelta, &dca_fill(50, 20), 4)
+            .unwrap();
+        order
+            .apply(
+                LifecycleTransition::Close {
+                    status: TerminalStatus::Completed,
+                },
+                &EventPayload::None,
+                5,
+            )
+            .unwrap();
+
+        assert_eq!(
+            *order.fills(),
+            FillAggregate {
+                total_in: 150,
+                total_out: 60,
+                fill_count: 2,
+            }
+        );
+        assert_eq!(order.terminal(), Some(TerminalStatus::Completed));
+
+        let decision = order
+            .apply(LifecycleTransition::FillDelta, &dca_fill(1, 1), 6)
+            .unwrap()
+            .decision;
+        assert_eq!(decision, TransitionDecision::IgnoreTerminalViolation);
+        assert_eq!(order.fills().fill_count, 2);
+    }
+
+    #[test]
+    fn counted_fills_return_sequence_numbers() {
+        let mut order = OrderLifecycle::new();
+        let create = order
+            .apply(LifecycleTransition::Create, &EventPayload::None, 1)
+            .unwrap();
+        assert_eq!(create.fill_seq, None);
+
+        let mut seqs = Vec::new();
+        for slot in 2..5 {
+            seqs.push(
+                order
+                    .apply(LifecycleTransition::FillDelta, &dca_fill(10, 5), slot)
+                    .unwrap()
+                    .fill_seq,
+            );
+            let uncounted = order
+                .apply(LifecycleTransition::FillDelta, &EventPayload::None, slot)
+                .unwrap();
+            assert_eq!(uncounted.fill_seq, None);
+            let metadata = order
+                .apply(
+                    LifecycleTransition::MetadataOnly {
+                        reason: crate::lifecycle::MetadataReason::Fee,
+                    },
+                    &dca_fill(1, 1),
+                    slot,
+                )
+                .unwrap();
+            assert_eq!(metadata.fill_seq, None);
+        }
+        assert_eq!(seqs, [Some(1), Some(2), Some(3)]);
+
+        order
+            .apply(
+                LifecycleTransition::Close {
+                    status: TerminalStatus::Completed,
+                },
+                &EventPayload::None,
+                6,
+            )
+            .unwrap();
+        let rejected = order
+            .apply(LifecycleTransition::FillDelta, &dca_fill(1, 1), 7)
+            .unwrap();
+        assert_eq!(
+            rejected,
+            TransitionOutcome {
+                decision: TransitionDecision::IgnoreTerminalViolation,
+                terminal: Some(TerminalStatus::Completed),
+                applied_in: 0,
+                applied_out: 0,
+                fill_seq: None,
+            }
+        );
+    }
+
+    #[test]
+    fn reconcile_on_close_snaps_output_total_to_authoritative_value() {
+        let mut order = OrderLifecycle::new();
+        order
+            .apply(LifecycleTransition::FillDelta, &dca_fill(100, 40), 1)
+            .unwrap();
+        order
+            .apply(LifecycleTransition::FillDelta, &dca_fill(100, 38), 2)
+            .unwrap();
+        assert_eq!(order.reconcile_on_close(120), None);
+        assert_eq!((order.fills().total_in, order.fills().total_out), (200, 78));
+        order
+            .apply(
+                LifecycleTransition::Close {
+                    status: TerminalStatus::Completed,
+                },
+                &EventPayload::None,
+                3,
+            )
+            .unwrap();
+
+        let report = order.reconcile_on_close(120).unwrap();
+        assert_eq!(
+            report,
+            ReconcileReport {
+                accumulated_out: 78,
+                authoritative_out: 120,
+            }
+        );
+        assert!(report.has_discrepancy());
+        assert_eq!(report.discrepancy_out(), 42);
+        assert_eq!(
+            *order.fills(),
+            FillAggregate {
+                total_in: 200,
+                total_out: 120,
+                fill_count: 2,
+            }
+        );
+        assert!(!order.reconcile_on_close(120).unwrap().has_discrepancy());
+
+        order.rollback_to_slot(3).unwrap();
+        assert_eq!(
+            (order.fills().total_in, order.fills().total_out),
+            (200, 120)
+        );
+        order.rollback_to_slot(2).unwrap();
+        assert_eq!(order.terminal(), None);
+        assert_eq!((order.fills().total_in, order.fills().total_out), (200, 78));
+    }
+
+    #[test]
+    fn on_reject_hook_collects_rejected_transitions() {
+        let rejects = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = Arc::clone(&rejects);
+        let mut order = OrderLifecycle::new();
+        order.on_reject(Box::new(move |record| {
+            sink.lock().unwrap().push((
+                record.signature.map(str::to_string),
+                record.slot,
+                record.transition,
+                record.terminal,
+                record.fills.fill_count,
+            ));
+        }));
+
+        order
+            .apply(LifecycleTransition::FillDelta, &dca_fill(10, 4), 1)
+            .unwrap();
+        let close = LifecycleTransition::Close {
+            status: TerminalStatus::Cancelled,
+        };
+        order.apply(close, &EventPayload::None, 2).unwrap();
+        assert!(rejects.lock().unwrap().is_empty());
+
+        order
+            .apply_signed(
+                "late_fill_sig",
+                LifecycleTransition::FillDelta,
+                &dca_fill(5, 2),
+                3,
+            )
+            .unwrap();
+        let conflicting_close = LifecycleTransition::Close {
+            status: TerminalStatus::Completed,
+        };
+        order
+            .apply(conflicting_close, &EventPayload::None, 4)
+            .unwrap();
+        let duplicate = order.apply(close, &EventPayload::None, 6).unwrap();
+        assert_eq!(duplicate.decision, TransitionDecision::IgnoreNoChange);
+        order
+            .apply(
+                LifecycleTransition::MetadataOnly {
+                    reason: crate::lifecycle::MetadataReason::Diagnostic,
+                },
+                &EventPayload::None,
+                5,
+            )
+            .unwrap();
+
+        assert_eq!(
+            *rejects.lock().unwrap(),
+            vec![
+                (
+                    Some("late_fill_sig".to_string()),
+                    3,
+                    LifecycleTransition::FillDelta,
+                    Some(TerminalStatus::Cancelled),
+                    1,
+                ),
+                (
+                    None,
+                    4,
+                    conflicting_close,
+                    Some(TerminalStatus::Cancelled),
+                    1
+                ),
+            ]
+        );
+        assert_eq!(order.clone(), order);
+    }
+
+    #[test]
+    fn accepted_fill_reports_outcome_fields() {
+        let mut order = OrderLifecycle::new();
+        order
+            .apply(LifecycleTransition::FillDelta, &dca_fill(7, 3), 1)
+            .unwrap();
+        let outcome = order
+            .apply(LifecycleTransition::FillDelta, &dca_fill(100, 40), 2)
+            .unwrap();
+        assert_eq!(
+            outcome,
+            TransitionOutcome {
+                decision: TransitionDecision::Apply,
+                terminal: None,
+                applied_in: 100,
+                applied_out: 40,
+                fill_seq: Some(2),
+            }
+        );
+
+        let close = order
+            .apply(
+                LifecycleTransition::Close {
+                    status: TerminalStatus::Expired,
+                },
+                &EventPayload::None,
+                3,
+            )
+            .unwrap();
+        assert_eq!(close.terminal, Some(TerminalStatus::Expired));
+        assert_eq!((close.applied_in, close.applied_out), (0, 0));
+    }
+
+    #[test]
+    fn preview_matches_apply_without_mutating() {
+        let mut order = OrderLifecycle::new();
+        order
+            .apply(LifecycleTransition::Create, &EventPayload::None, 1)
+            .unwrap();
+        let steps = [
+            (LifecycleTransition::FillDelta, dca_fill(100, 40)),
+            (LifecycleTransition::FillDelta, EventPayload::None),
+            (
+                LifecycleTransition::Close {
+                    status: TerminalStatus::Completed,
+                },
+                EventPayload::None,
+            ),
+            (LifecycleTransition::FillDelta, dca_fill(1, 1)),
+        ];
+
+        for (slot, (transition, payload)) in (2..).zip(steps) {
+            let before = order.clone();
+            let preview = order.preview(transition, &payload).unwrap();
+            assert_eq!(order, before, "preview must not mutate");
+
+            let applied = order.apply(transition, &payload, slot).unwrap();
+            assert_eq!(preview.decision, applied.decision);
+            assert_eq!(preview.fill_seq, applied.fill_seq);
+            assert_eq!(preview.terminal, order.terminal());
+            assert_eq!(preview.fills, *order.fills());
+        }
+
+        let mut overflowing = OrderLifecycle::new();
+        overflowing
+            .apply(LifecycleTransition::FillDelta, &dca_fill(i64::MAX, 1), 1)
+            .unwrap();
+        assert!(
+            overflowing
+                .preview(LifecycleTransition::FillDelta, &dca_fill(1, 1))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn overflowing_fill_is_rejected_with_operands() {
+        let mut order = OrderLifecycle::new();
+        order
+            .apply(
+                LifecycleTransition::FillDelta,
+                &dca_fill(i64::MAX - 1, 5),
+                7,
+            )
+            .unwrap();
+
+        let err = order
+            .apply(LifecycleTransition::FillDelta, &dca_fill(2, 5), 8)
+            .unwrap_err();
+        let Error::Protocol { reason, .. } = err else {
+            panic!("expected protocol error");
+        };
+        assert_eq!(
+            reason,
+            format!("total_in overflow: {} + 2 exceeds i64 range", i64::MAX - 1)
+        );
+        assert_eq!(
+            *order.fills(),
+            FillAggregate {
+                total_in: i64::MAX - 1,
+                total_out: 5,
+                fill_count: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn custom_config_maps_fill_initiated_as_metadata() {
+        let config = EngineConfig {
+            mapping: MappingOptions {
+                fill_initiated_is_metadata: true,
+            },
+            ..EngineConfig::default()
+        };
+        let close = |order: &mut OrderLifecycle| {
+            order
+                .apply_event(
+                    &EventType::Closed,
+                    Some(TerminalStatus::Completed),
+                    &EventPayload::None,
+                    2,
+                )
+                .unwrap();
+        };
+
+        let mut default_order = OrderLifecycle::new();
+        close(&mut default_order);
+        let decision = default_order
+            .apply_event(&EventType::FillInitiated, None, &EventPayload::None, 3)
+            .unwrap()
+            .decision;
+        assert_eq!(decision, TransitionDecision::IgnoreTerminalViolation);
+
+        let mut configured = OrderLifecycle::with_config(config);
+        assert_eq!(*configured.config(), config);
+        close(&mut configured);
+        let decision = configured
+            .apply_event(&EventType::FillInitiated, None, &EventPayload::None, 3)
+            .unwrap()
+            .decision;
+        assert_eq!(decision, TransitionDecision::Apply);
+        assert_eq!(configured.terminal(), Some(TerminalStatus::Completed));
+    }
+
+    #[test]
+    fn duration_spans_create_to_close() {
+        let mut order = OrderLifecycle::new();
+        order
+            .apply(LifecycleTransition::Create, &EventPayload::None, 100)
+            .unwrap();
+        assert_eq!(order.duration_slots(), None);
+        order
+            .apply(LifecycleTransition::FillDelta, &dca_fill(1, 1), 120)
+            .unwrap();
+        order
+            .apply(
+                LifecycleTransition::Close {
+                    status: TerminalStatus::Completed,
+                },
+                &EventPayload::None,
+                150,
+            )
+            .unwrap();
+
+        assert_eq!(order.created_slot(), Some(100));
+        assert_eq!(order.closed_slot(), Some(150));
+        assert_eq!(order.duration_slots(), Some(50));
+        assert!(!order.clock_anomaly());
+    }
+
+    #[test]
+    fn reordered_close_flags_clock_anomaly() {
+        let mut order = OrderLifecycle::new();
+        order
+            .apply(LifecycleTransition::Create, &EventPayload::None, 200)
+            .unwrap();
+        order
+            .apply(
+                LifecycleTransition::Close {
+                    status: TerminalStatus::Cancelled,
+                },
+                &EventPayload::None,
+                190,
+            )
+            .unwrap();
+
+        assert_eq!(order.duration_slots(), None);
+        assert!(order.clock_anomaly());
+    }
+
+    #[test]
+    fn rollback_reports_fill_history_that_no_longer_sums() {
+        let mut order = OrderLifecycle::new();
+        for (slot, in_amount) in [(1, i64::MAX - 5), (3, -10), (2, 10)] {
+            order
+                .apply(
+                    LifecycleTransition::FillDelta,
+                    &dca_fill(in_amount, 1),
+                    slot,
+                )
+                .unwrap();
+        }
+        let before = order.clone();
+
+        let err = order.rollback_to_slot(2).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::AmountOverflow);
+        assert_eq!(order, before);
+    }
+
+    #[test]
+    fn rollback_reverses_fills_after_target_slot() {
+        let mut order = OrderLifecycle::new();
+        order
+            .apply(LifecycleTransition::Create, &EventPayload::None, 10)
+            .unwrap();
+        for (slot, amounts) in [(11, (100, 40)), (12, (50, 20)), (13, (25, 10))] {
+            order
+                .apply(
+                    LifecycleTransition::FillDelta,
+                    &dca_fill(amounts.0, amounts.1),
+                    slot,
+                )
+                .unwrap();
+        }
+
+        order.rollback_to_slot(11).unwrap();
+
+        assert_eq!(
+            *order.fills(),
+            FillAggregate {
+                total_in: 100,
+                total_out: 40,
+                fill_count: 1,
+            }
+        );
+        assert_eq!(order.created_slot(), Some(10));
+
+        order
+            .apply(LifecycleTransition::FillDelta, &dca_fill(7, 3), 12)
+            .unwrap();
+        assert_eq!(order.fills().total_in, 107);
+    }
+
+    #[test]
+    fn rollback_reopens_closed_order() {
+        let mut order = OrderLifecycle::new();
+        order
+            .apply(LifecycleTransition::Create, &EventPayload::None, 10)
+            .unwrap();
+        order
+            .apply(LifecycleTransition::FillDelta, &dca_fill(100, 40), 11)
+            .unwrap();
+        order
+            .apply(
+                LifecycleTransition::Close {
+                    status: TerminalStatus::Completed,
+                },
+                &EventPayload::None,
+                12,
+            )
+            .unwrap();
+
+        order.rollback_to_slot(11).unwrap();
+        assert_eq!(order.terminal(), None);
+        assert_eq!(order.closed_slot(), None);
+        assert_eq!(order.fills().fill_count, 1);
+
+        order.rollback_to_slot(5).unwrap();
+        assert_eq!(order, OrderLifecycle::new());
+    }
+}
@@ -0,0 +1,219 @@
+use std::collections::HashMap;
+
+use crate::error::Error;
+use crate::lifecycle::adapters::{ProtocolAdapter, ResolvedEvent, resolve_transaction_with};
+use crate::protocols::Protocol;
+use crate::protocols::dca::DcaAdapter;
+use crate::protocols::kamino::KaminoAdapter;
+use crate::protocols::limit_v1::LimitV1Adapter;
+use crate::protocols::limit_v2::LimitV2Adapter;
+use crate::protocols::noop::NoOpAdapter;
+use crate::protocols::va::VaAdapter;
+use crate::types::{RawEvent, RawInstruction};
+
+/// Runtime lookup of [`ProtocolAdapter`]s keyed by base58 program id.
+///
+/// [`AdapterRegistry::new`] pre-registers the built-in adapters; [`AdapterRegistry::register`]
+/// adds out-of-tree protocols. The static [`adapter_for`](crate::lifecycle::adapters::adapter_for)
+/// remains the zero-cost path for built-ins. Looked-up adapters drive replay through
+/// [`LifecycleStream::with_adapter`](crate::LifecycleStream::with_adapter) and
+/// [`replay_events_with`](crate::replay_events_with).
+///
+/// Out-of-tree adapters have no [`Protocol`] of their own and should report
+/// [`Protocol::Unknown`]: the registry key is their only program id, since
+/// [`ProtocolAdapter::program_id`] then returns `None`. A [`ResolveCache`]
+/// fingerprints entries by protocol, so give each out-of-tree adapter its own cache.
+///
+/// [`ResolveCache`]: crate::lifecycle::adapters::ResolveCache
+pub struct AdapterRegistry {
+    by_program_id: HashMap<String, Box<dyn ProtocolAdapter>>,
+}
+
+impl Default for AdapterRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AdapterRegistry {
+    /// Creates a registry with every built-in adapter registered under its program id.
+    pub fn new() -> Self {
+        let mut registry = Self::empty();
+        for (program_id, protocol) in Self::builtin_program_ids() {
+            registry.register(program_id, Self::builtin_adapter(protocol));
+        }
+        registry
+    }
+
+    /// Creates a registry with no adapters.
+    pub fn empty() -> Self {
+        Self {
+            by_program_id: HashMap::new(),
+        }
+    }
+
+    /// Registers `adapter` for `program_id`, returning any adapter it replaced.
+    pub fn register(
+        &mut self,
+        program_id: impl Into<String>,
+        adapter: Box<dyn ProtocolAdapter>,
+    ) -> Option<Box<dyn ProtocolAdapter>> {
+        self.by_program_id.insert(program_id.into(), adapter)
+    }
+
+    /// Returns the adapter registered for `program_id`, or `None` if unregistered.
+    pub fn adapter_for_program_id(&self, program_id: &str) -> Option<&dyn ProtocolAdapter> {
+        self.by_program_id.get(program_id).map(AsRef::as_ref)
+    }
+
+    /// [`resolve_transaction`](crate::lifecycle::adapters::resolve_transaction), picking each
+    /// event's adapter from this registry. Events of unregistered programs are skipped; results
+    /// are labelled with their adapter's [`ProtocolAdapter::protocol`].
+    pub fn resolve_transaction(
+        &self,
+        instructions: &[RawInstruction],
+        events: &[RawEvent],
+    ) -> Vec<(Protocol, Result<ResolvedEvent, Error>)> {
+        resolve_transaction_with(instructions, events, |program_id| {
+            self.adapter_for_program_id(program_id)
+        })
+    }
+
+    fn builtin_adapter(protocol: Protocol) -> Box<dyn ProtocolAdapter> {
+        match protocol {
+            Protocol::Dca => Box::new(DcaAdapter),
+            Protocol::LimitV1 => Box::new(LimitV1Adapter),
+            Protocol::LimitV2 => Box::new(LimitV2Adapter),
+            Protocol::Kamino => Box::new(KaminoAdapter),
+            Protocol::ValueAveraging => Box::new(VaAdapter),
+            Protocol::Unknown => Box::new(NoOpAdapter),
+        }
+    }
+
+    fn builtin_program_ids() -> impl Iterator<Item = (String, Protocol)> {
+        Protocol::ALL
+            .iter()
+            .filter_map(|&protocol| Some((protocol.program_id_str()?.to_string(), protocol)))
+    }
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used, reason = "test assertions")]
+mod tests {
+    use super::*;
+    use crate::lifecycle::adapters::{ContextRequirements, CorrelationOutcome, EventPayload};
+    use crate::lifecycle::replay::replay_events_with;
+    use crate::protocols::EventType;
+    use crate::test_util::{RawEventBuilder, RawInstructionBuilder};
+    use crate::types::ResolveContext;
+
+    struct PingAdapter;
+
+    impl ProtocolAdapter for PingAdapter {
+        fn protocol(&self) -> Protocol {
+            Protocol::Unknown
+        }
+
+        fn classify_instruction(&self, ix: &RawInstruction) -> Option<EventType> {
+            (ix.instruction_name == "Ping").then_some(EventType::Created)
+        }
+
+        fn classify_and_resolve_event(
+            &self,
+            ev: &RawEvent,
+            ctx: &ResolveContext,
+        ) -> Option<Result<ResolvedEvent, Error>> {
+            let pdas = ctx.pre_fetched_order_pdas.clone().unwrap_or_default();
+            (ev.event_name == "Pinged").then_some(Ok((
+                EventType::Created,
+                CorrelationOutcome::Correlated(pdas),
+                EventPayload::None,
+            )))
+        }
+
+        fn required_context(&self) -> ContextRequirements {
+            ContextRequirements { order_pdas: true }
+        }
+
+        /// The first account of each `Ping` instruction.
+        fn collect_order_pdas(&self, instructions: &[RawInstruction]) -> Vec<String> {
+            instructions
+                .iter()
+                .filter(|ix| ix.instruction_name == "Ping")
+                .filter_map(|ix| ix.accounts.as_ref()?[0]["pubkey"].as_str())
+                .map(str::to_string)
+                .collect()
+        }
+    }
+
+    #[test]
+    fn custom_adapter_resolves_through_registry() {
+        let mut registry = AdapterRegistry::new();
+        assert!(
+            registry
+                .register("custom_program", Box::new(PingAdapter))
+                .is_none()
+        );
+
+        let ix = RawInstructionBuilder::new("Ping")
+            .program_id("custom_program")
+            .build();
+        let adapter = registry.adapter_for_program_id(&ix.program_id).unwrap();
+        assert_eq!(adapter.classify_instruction(&ix), Some(EventType::Created));
+
+        assert!(registry.adapter_for_program_id("unknown_program").is_none());
+        assert!(
+            AdapterRegistry::empty()
+                .adapter_for_program_id("custom_program")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn custom_adapter_drives_transaction_resolution_and_replay() {
+        let mut registry = AdapterRegistry::new();
+        registry.register("custom_program", Box::new(PingAdapter));
+        let pinged = || {
+            RawEventBuilder::new("Pinged")
+                .program_id("custom_program")
+                .slot(7)
+                .build()
+        };
+        let unregistered = RawEventBuilder::new("Pinged")
+            .program_id("other_program")
+            .index(1)
+            .build();
+
+        let ping = RawInstructionBuilder::new("Ping")
+            .program_id("custom_program")
+            .accounts(serde_json::json!([{ "pubkey": "order1" }]))
+            .build();
+
+        let resolved = registry.resolve_transaction(&[ping], &[pinged(), unregistered]);
+        assert_eq!(resolved.len(), 1);
+        assert_eq!(resolved[0].0, Protocol::Unknown);
+        let (event_type, correlation, _) = resolved[0].1.as_ref().unwrap();
+        assert_eq!(*event_type, EventType::Created);
+        assert_eq!(
+            *correlation,
+            CorrelationOutcome::Correlated(vec!["order1".to_string()])
+        );
+
+        let adapter = registry.adapter_for_program_id("custom_program").unwrap();
+        let summary = replay_events_with(adapter, [pinged()], ResolveContext::default());
+        assert!(summary.errors.is_empty());
+        assert_eq!(summary.order.created_slot(), Some(7));
+    }
+
+    #[cfg(feature = "native")]
+    #[test]
+    fn builtins_are_preregistered() {
+        let registry = AdapterRegistry::new();
+        for key in Protocol::all_program_ids() {
+            let program_id = key.to_string();
+            let expected = Protocol::from_program_id(&program_id).unwrap();
+            let adapter = registry.adapter_for_program_id(&program_id).unwrap();
+            assert_eq!(adapter.protocol(), expected);
+        }
+    }
+}
@@ -0,0 +1,515 @@
+use crate::error::Error;
+use std::collections::HashSet;
+
+use crate::lifecycle::adapters::{
+    CorrelationOutcome, EventPayload, InstructionArgs, ProtocolAdapter, adapter_for,
+};
+use crate::lifecycle::mapping;
+use crate::lifecycle::order::OrderLifecycle;
+use crate::lifecycle::{EngineConfig, LifecycleTransition, TerminalStatus, TransitionDecision};
+use crate::protocols::{EventType, Protocol};
+use crate::types::{RawEvent, RawInstruction, ResolveContext};
+
+/// One row of a chronological stream that interleaves instructions and events.
+pub enum Row {
+    Instruction(RawInstruction),
+    Event(RawEvent),
+}
+
+/// Whether a [`Row`] was an instruction or an event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowKind {
+    Instruction,
+    Event,
+}
+
+/// Identifying metadata of a [`Row`], yielded alongside its transition.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RowMeta {
+    pub kind: RowKind,
+    /// Database row id.
+    pub id: i64,
+    pub signature: String,
+    /// `instruction_index` or `event_index`.
+    pub index: i32,
+    /// `instruction_name` or `event_name`.
+    pub name: String,
+    pub slot: i64,
+}
+
+impl Row {
+    /// Metadata identifying this row.
+    pub fn meta(&self) -> RowMeta {
+        match self {
+            Self::Instruction(ix) => RowMeta {
+                kind: RowKind::Instruction,
+                id: ix.id,
+                signature: ix.signature.clone(),
+                index: ix.instruction_index,
+                name: ix.instruction_name.clone(),
+                slot: ix.slot,
+            },
+            Self::Event(ev) => RowMeta {
+                kind: RowKind::Event,
+                id: ev.id,
+                signature: ev.signature.clone(),
+                index: ev.event_index,
+                name: ev.event_name.clone(),
+                slot: ev.slot,
+            },
+        }
+    }
+
+    fn signature(&self) -> &str {
+        match self {
+            Self::Instruction(ix) => &ix.signature,
+            Self::Event(ev) => &ev.signature,
+        }
+    }
+
+    /// Event type and order PDAs this row resolves to; no PDAs when they cannot be determined.
+    fn classify_with_orders(
+        &self,
+        adapter: &dyn ProtocolAdapter,
+        ctx: &ResolveContext,
+    ) -> Option<(EventType, Vec<String>)> {
+        match self {
+            Self::Instruction(ix) => {
+                let outcome = adapter.resolve_instruction(ix)?.ok()?;
+                Some((outcome.event_type, outcome.order_pda.into_iter().collect()))
+            }
+            Self::Event(ev) => {
+                let (event_type, correlation, _) =
+                    adapter.classify_and_resolve_event(ev, ctx)?.ok()?;
+                let pdas = match correlation {
+                    CorrelationOutcome::Correlated(pdas) => pdas,
+                    CorrelationOutcome::NotRequired | CorrelationOutcome::Uncorrelated { .. } => {
+                        Vec::new()
+                    }
+                };
+                Some((event_type, pdas))
+            }
+        }
+    }
+}
+
+/// Drops `FillInitiated` rows whose fill is also reported by a `FillCompleted` row, so a flash
+/// fill's start and completion are not both processed.
+///
+/// Matching key: `(signature, order PDA)`. An instruction's PDA comes from its accounts and an
+/// event's from its [`CorrelationOutcome::Correlated`] PDAs; a `FillInitiated` is dropped when
+/// any `FillCompleted` in the same transaction shares one of its PDAs. Rows whose order cannot
+/// be determined never match, so they are kept. Row order is preserved.
+pub fn coalesce_fills(protocol: Protocol, rows: Vec<Row>, ctx: &ResolveContext) -> Vec<Row> {
+    let adapter = adapter_for(protocol);
+    let classified: Vec<_> = rows
+        .iter()
+        .map(|row| row.classify_with_orders(adapter, ctx))
+        .collect();
+    let completed: HashSet<(&str, &str)> = rows
+        .iter()
+        .zip(&classified)
+        .filter_map(|(row, classified)| match classified {
+            Some((EventType::FillCompleted, pdas)) => Some((row.signature(), pdas)),
+            _ => None,
+        })
+        .flat_map(|(signature, pdas)| pdas.iter().map(move |pda| (signature, pda.as_str())))
+        .collect();
+    let covered: Vec<bool> = rows
+        .iter()
+        .zip(&classified)
+        .map(|(row, classified)| match classified {
+            Some((EventType::FillInitiated, pdas)) => pdas
+                .iter()
+                .any(|pda| completed.contains(&(row.signature(), pda.as_str()))),
+            _ => false,
+        })
+        .collect();
+    rows.into_iter()
+        .zip(covered)
+        .filter_map(|(row, covered)| (!covered).then_some(row))
+        .collect()
+}
+
+/// Replays one order's interleaved instruction/event rows through an [`OrderLifecycle`].
+///
+/// Each recognised row is resolved with the protocol's adapter, mapped to a transition, and
+/// applied; the iterator yields the row's metadata, the transition, and the engine's decision.
+/// Rows the adapter does not recognise are skipped. Resolve or apply errors are yielded and the
+/// stream continues. Rows must already be in chronological order.
+///
+/// [`Self::new`] uses the built-in adapter for a [`Protocol`]; [`Self::with_adapter`] accepts
+/// any adapter, e.g. one looked up in an [`AdapterRegistry`](crate::AdapterRegistry).
+pub struct LifecycleStream<'a, I> {
+    adapter: &'a dyn ProtocolAdapter,
+    rows: I,
+    ctx: ResolveContext,
+    order: OrderLifecycle,
+}
+
+impl<I: Iterator<Item = Row>> LifecycleStream<'static, I> {
+    pub fn new(
+        protocol: Protocol,
+        rows: impl IntoIterator<IntoIter = I>,
+        ctx: ResolveContext,
+    ) -> Self {
+        Self::with_config(protocol, rows, ctx, EngineConfig::default())
+    }
+
+    /// Like [`Self::new`], with the order's event mapping following `config`.
+    ///
+    /// `config.treat_filled_as_terminal` fills [`ResolveContext::treat_filled_as_terminal`]
+    /// unless `ctx` already sets it.
+    pub fn with_config(
+        protocol: Protocol,
+        rows: impl IntoIterator<IntoIter = I>,
+        ctx: ResolveContext,
+        config: EngineConfig,
+    ) -> Self {
+        LifecycleStream::with_adapter(adapter_for(protocol), rows, ctx, config)
+    }
+}
+
+impl<'a, I: Iterator<Item = Row>> LifecycleStream<'a, I> {
+    /// Like [`Self::with_config`], resolving rows with `adapter` instead of a built-in one.
+    pub fn with_adapter(
+        adapter: &'a dyn ProtocolAdapter,
+        rows: impl IntoIterator<IntoIter = I>,
+        mut ctx: ResolveContext,
+        config: EngineConfig,
+    ) -> Self {
+        ctx.treat_filled_as_terminal
+            .get_or_insert(config.treat_filled_as_terminal);
+        Self {
+            adapter,
+            rows: rows.into_iter(),
+            ctx,
+            order: OrderLifecycle::with_config(config),
+        }
+    }
+
+    /// Order state after the rows consumed so far.
+    pub fn order(&self) -> &OrderLifecycle {
+        &self.order
+    }
+
+    /// Consumes the stream, returning the accumulated order state.
+    pub fn into_order(self) -> OrderLifecycle {
+        self.order
+    }
+
+    fn step(
+        &mut self,
+        row: &Row,
+    ) -> Option<Result<(LifecycleTransition, TransitionDecision), Error>> {
+        let resolved = match row {
+            Row::Instruction(ix) => self.adapter.resolve_instruction(ix)?.map(|outcome| {
+                (
+                    outcome.event_type,
+                    None,
+                    instruction_payload(outcome.args),
+                    ix.slot,
+                )
+            }),
+            Row::Event(ev) => self.adapter.classify_and_resolve_event(ev, &self.ctx)?.map(
+                |(event_type, _, payload)| {
+                    (
+                        event_type,
+                        payload_closed_status(&payload),
+                        payload,
+                        ev.slot,
+                    )
+                },
+            ),
+        };
+        let signature = row.signature();
+        Some(
+            resolved.and_then(|(event_type, closed_status, payload, slot)| {
+                let transition = mapping::event_type_to_transition_with(
+                    &event_type,
+                    closed_status,
+                    self.order.config().mapping,
+                );
+                let outcome = self
+                    .order
+                    .apply_signed(signature, transition, &payload, slot)?;
+                Ok((transition, outcome.decision))
+            }),
+        )
+    }
+}
+
+impl<I: Iterator<Item = Row>> Iterator for LifecycleStream<'_, I> {
+    type Item = Result<(RowMeta, LifecycleTransition, TransitionDecision), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let row = self.rows.next()?;
+            if let Some(result) = self.step(&row) {
+                return Some(
+                    result.map(|(transition, decision)| (row.meta(), transition, decision)),
+                );
+            }
+        }
+    }
+}
+
+/// Outcome of [`replay_events`].
+#[derive(Debug)]
+pub struct ReplaySummary {
+    /// Order state after every event was applied.
+    pub order: OrderLifecycle,
+    /// Recognised events the engine declined (any decision other than
+    /// [`TransitionDecision::Apply`]).
+    pub ignored: usize,
+    /// Resolve or apply errors, in stream order; the replay continues past them.
+    pub errors: Vec<Error>,
+}
+
+/// Replays one order's chronological `events` through a [`LifecycleStream`] and summarises
+/// the result, for callers that only need the final state.
+pub fn replay_events(
+    protocol: Protocol,
+    events: impl IntoIterator<Item = RawEvent>,
+    ctx: ResolveContext,
+) -> ReplaySummary {
+    replay_events_with(adapter_for(protocol), events, ctx)
+}
+
+/// Like [`replay_events`], resolving events with `adapter` instead of a built-in one.
+pub fn replay_events_with(
+    adapter: &dyn ProtocolAdapter,
+    events: impl IntoIterator<Item = RawEvent>,
+    ctx: ResolveContext,
+) -> ReplaySummary {
+    let mut stream = LifecycleStream::with_adapter(
+        adapter,
+        events.into_iter().map(Row::Event),
+        ctx,
+        EngineConfig::default(),
+    );
+    let mut ignored = 0;
+    let mut errors = Vec::new();
+    for step in stream.by_ref() {
+        match step {
+            Ok((_, _, TransitionDecision::Apply)) => {}
+            Ok(_) => ignored += 1,
+            Err(err) => errors.push(err),
+        }
+    }
+    ReplaySummary {
+        order: stream.into_order(),
+        ignored,
+        errors,
+    }
+}
+
+/// Fill payload carried by instruction args, if any. Instructions never carry a close status;
+/// closes take theirs from the paired event.
+fn instruction_payload(args: InstructionArgs) -> EventPayload {
+    match args {
+        InstructionArgs::LimitV1ExpirySettlement(settlement) => settlement.fill_payload(),
+        InstructionArgs::None
+        | InstructionArgs::DcaCreate(_)
+        | InstructionArgs::LimitV1Create(_)
+        | InstructionArgs::LimitV2Create(_)
+        | InstructionArgs::KaminoCreate(_)
+        | InstructionArgs::VaCreate(_) => EventPayload::None,
+    }
+}
+
+/// Close status carried by an event payload, if any.
+fn payload_closed_status(payload: &EventPayload) -> Option<TerminalStatus> {
+    match payload {
+        EventPayload::DcaClosed { status, .. } | EventPayload::Closed { status } => Some(*status),
+        EventPayload::KaminoDisplay {
+            terminal_status, ..
+        } => *terminal_status,
+        EventPayload::None
+        | EventPayload::DcaFill { .. }
+        | EventPayload::LimitFill { .. }
+        | EventPayload::HostTip { .. }
+        | EventPayload::DcaCreated { .. }
+        | EventPayload::SwapBalances { .. } => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{RawEventBuilder, RawInstructionBuilder};
+
+    #[test]
+    fn interleaved_rows_drive_one_order_to_terminal() {
+        let open = RawInstructionBuilder::new("OpenDcaV2")
+            .accounts(serde_json::json!([
+                { "pubkey": "order_pda", "name": "dca" },
+                { "pubkey": "user", "is_signer": true, "name": "user" }
+            ]))
+            .args(serde_json::json!({
+                "in_amount": 1_000_u64,
+                "in_amount_per_cycle": 100_u64,
+                "cycle_frequency": 60_i64
+            }))
+            .slot(10)
+            .build();
+        let fill = RawEventBuilder::new("FilledEvent")
+            .fields(serde_json::json!({
+                "FilledEvent": { "dca_key": "order_pda", "in_amount": 100, "out_amount": 40 }
+            }))
+            .slot(11)
+            .build();
+        let close = RawEventBuilder::new("ClosedEvent")
+            .fields(serde_json::json!({
+                "ClosedEvent": { "dca_key": "order_pda", "user_closed": false, "unfilled_amount": 0 }
+            }))
+            .slot(12)
+            .build();
+        let unknown = RawEventBuilder::new("MysteryEvent")
+            .fields(serde_json::json!({ "MysteryEvent": {} }))
+            .build();
+        let late_fill = RawEventBuilder::new("FilledEvent")
+            .fields(serde_json::json!({
+                "FilledEvent": { "dca_key": "order_pda", "in_amount": 5, "out_amount": 2 }
+            }))
+            .slot(13)
+            .build();
+
+        let rows = vec![
+            Row::Instruction(open),
+            Row::Event(fill),
+            Row::Event(unknown),
+            Row::Event(close),
+            Row::Event(late_fill),
+        ];
+        let mut stream = LifecycleStream::new(Protocol::Dca, rows, ResolveContext::default());
+        let steps: Vec<_> = stream.by_ref().map(Result::unwrap).collect();
+
+        let summary: Vec<_> = steps
+            .iter()
+            .map(|(meta, transition, decision)| (meta.kind, meta.slot, *transition, *decision))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    RowKind::Instruction,
+                    10,
+                    LifecycleTransition::Create,
+                    TransitionDecision::Apply
+                ),
+                (
+                    RowKind::Event,
+                    11,
+                    LifecycleTransition::FillDelta,
+                    TransitionDecision::Apply
+                ),
+                (
+                    RowKind::Event,
+                    12,
+                    LifecycleTransition::Close {
+                        status: TerminalStatus::Completed
+                    },
+                    TransitionDecision::Apply
+                ),
+                (
+                    RowKind::Event,
+                    13,
+                    LifecycleTransition::FillDelta,
+                    TransitionDecision::IgnoreTerminalViolation
+                ),
+            ]
+        );
+        assert_eq!(steps[0].0.name, "OpenDcaV2");
+
+        let order = stream.into_order();
+        assert_eq!(order.terminal(), Some(TerminalStatus::Completed));
+        assert_eq!(order.fills().total_in, 100);
+        assert_eq!(order.duration_slots(), Some(2));
+    }
+
+    #[test]
+    fn replay_events_summarises_ignored_and_errors() {
+        let event = |name: &str, fields: serde_json::Value| {
+            RawEventBuilder::new(name)
+                .fields(serde_json::json!({ name: fields }))
+                .build()
+        };
+        let events = vec![
+            event("OpenedEvent", serde_json::json!({ "dca_key": "pda" })),
+            event(
+                "FilledEvent",
+                serde_json::json!({ "dca_key": "pda", "in_amount": 100, "out_amount": 40 }),
+            ),
+            event("FilledEvent", serde_json::json!({ "dca_key": "pda" })),
+            event(
+                "ClosedEvent",
+                serde_json::json!({ "dca_key": "pda", "user_closed": true, "unfilled_amount": 5 }),
+            ),
+            event("OpenedEvent", serde_json::json!({ "dca_key": "pda" })),
+        ];
+        let summary = replay_events(Protocol::Dca, events, ResolveContext::default());
+        assert_eq!(summary.order.terminal(), Some(TerminalStatus::Cancelled));
+        assert_eq!(summary.order.fills().total_out, 40);
+        assert_eq!(summary.ignored, 1);
+        assert_eq!(summary.errors.len(), 1);
+    }
+
+    #[test]
+    fn with_config_threads_filled_policy_into_resolve_context() {
+        let filled_is_terminal = |ctx: ResolveContext, treat_filled_as_terminal: bool| {
+            let config = EngineConfig {
+                treat_filled_as_terminal,
+                ..EngineConfig::default()
+            };
+            LifecycleStream::with_config(Protocol::Kamino, Vec::<Row>::new(), ctx, config)
+                .ctx
+                .filled_is_terminal()
+        };
+
+        assert!(filled_is_terminal(ResolveContext::default(), true));
+        assert!(!filled_is_terminal(ResolveContext::default(), false));
+        // An explicit context setting wins over the engine config.
+        let explicit = ResolveContext {
+            treat_filled_as_terminal: Some(true),
+            ..ResolveContext::default()
+        };
+        assert!(filled_is_terminal(explicit, false));
+    }
+
+    #[test]
+    fn coalesce_fills_drops_initiation_paired_in_same_transaction() {
+        let initiate = |signature: &str| {
+            Row::Instruction(
+                RawInstructionBuilder::new("InitiateFlashFill")
+                    .signature(signature)
+                    .accounts(serde_json::json!([{ "pubkey": "order_pda", "name": "dca" }]))
+                    .build(),
+            )
+        };
+        let filled = Row::Event(
+            RawEventBuilder::new("FilledEvent")
+                .signature("paired")
+                .fields(serde_json::json!({
+                    "FilledEvent": { "dca_key": "order_pda", "in_amount": 100, "out_amount": 40 }
+                }))
+                .build(),
+        );
+        let rows = vec![initiate("paired"), filled, initiate("dangling")];
+
+        let kept: Vec<_> = coalesce_fills(Protocol::Dca, rows, &ResolveContext::default())
+            .iter()
+            .map(|row| {
+                let meta = row.meta();
+                (meta.signature, meta.name)
+            })
+            .collect();
+        assert_eq!(
+            kept,
+            vec![
+                ("paired".to_string(), "FilledEvent".to_string()),
+                ("dangling".to_string(), "InitiateFlashFill".to_string()),
+            ]
+        );
+    }
+}
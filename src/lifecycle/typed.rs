@@ -0,0 +1,281 @@
+use crate::error::Error;
+use crate::lifecycle::TerminalStatus;
+use crate::lifecycle::adapters::{CorrelationOutcome, EventPayload, ResolvedEvent, adapter_for};
+use crate::protocols::Protocol;
+use crate::protocols::dca::DcaFillEvent;
+use crate::types::{RawEvent, ResolveContext};
+
+/// Terminal close of a single order, with its derived status.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrderClosedEvent {
+    pub order_pda: String,
+    pub status: TerminalStatus,
+}
+
+/// Limit order fill (V1 or V2) correlated to its order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LimitFillEvent {
+    pub order_pda: String,
+    pub in_amount: i64,
+    pub out_amount: i64,
+    pub remaining_in_amount: i64,
+    pub remaining_out_amount: i64,
+    pub counterparty: String,
+    pub maker: Option<String>,
+}
+
+/// Kamino `OrderDisplayEvent` snapshot with the PDAs it correlated to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KaminoDisplaySnapshot {
+    pub order_pdas: Vec<String>,
+    pub remaining_input_amount: i64,
+    pub filled_output_amount: i64,
+    pub terminal_status: Option<TerminalStatus>,
+}
+
+/// Protocol-tagged, strongly-typed alternative to the `(EventType, CorrelationOutcome, EventPayload)`
+/// tuple.
+///
+/// Built on top of adapter resolution, so classification and errors are identical; `saturated`
+/// flags are not carried over.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TypedEvent {
+    DcaFilled(DcaFillEvent),
+    DcaClosed(OrderClosedEvent),
+    VaFilled(DcaFillEvent),
+    VaClosed(OrderClosedEvent),
+    LimitV1Filled(LimitFillEvent),
+    LimitV2Filled(LimitFillEvent),
+    /// Limit v2 cancel of a fully filled order.
+    LimitV2Closed(OrderClosedEvent),
+    KaminoDisplay(KaminoDisplaySnapshot),
+    /// Any event without a typed variant (creates, cancels, fees, uncorrelated events, ...).
+    Other {
+        protocol: Protocol,
+        event: ResolvedEvent,
+    },
+}
+
+impl TypedEvent {
+    /// Resolves `ev` with `protocol`'s adapter and lifts the result into a typed variant.
+    ///
+    /// `None` when the adapter does not recognise the event (see
+    /// [`crate::ProtocolAdapter::classify_and_resolve_event`]).
+    pub fn from_raw(
+        protocol: Protocol,
+        ev: &RawEvent,
+        ctx: &ResolveContext,
+    ) -> Option<Result<Self, Error>> {
+        let resolved = adapter_for(protocol).classify_and_resolve_event(ev, ctx)?;
+        Some(resolved.map(|event| Self::from_resolved(protocol, event)))
+    }
+
+    /// Lifts an already-resolved event into a typed variant.
+    ///
+    /// Single-order variants require exactly one correlated PDA; anything else becomes
+    /// [`TypedEvent::Other`].
+    pub fn from_resolved(protocol: Protocol, event: ResolvedEvent) -> Self {
+        let (event_type, correlation, payload) = event;
+        let single_pda = match &correlation {
+            CorrelationOutcome::Correlated(pdas) if pdas.len() == 1 => Some(pdas[0].clone()),
+            _ => None,
+        };
+        match (protocol, payload, single_pda) {
+            (
+                Protocol::Dca | Protocol::ValueAveraging,
+                EventPayload::DcaFill {
+                    in_amount,
+                    out_amount,
+                    ..
+                },
+                Some(order_pda),
+            ) => {
+                let fill = DcaFillEvent {
+                    order_pda,
+                    in_amount,
+                    out_amount,
+                };
+                if protocol == Protocol::Dca {
+                    Self::DcaFilled(fill)
+                } else {
+                    Self::VaFilled(fill)
+                }
+            }
+            (
+                Protocol::Dca | Protocol::ValueAveraging,
+                EventPayload::DcaClosed { status, .. },
+                Some(order_pda),
+            ) => {
+                let closed = OrderClosedEvent { order_pda, status };
+                if protocol == Protocol::Dca {
+                    Self::DcaClosed(closed)
+                } else {
+                    Self::VaClosed(closed)
+                }
+            }
+            (
+                Protocol::LimitV1 | Protocol::LimitV2,
+                EventPayload::LimitFill {
+                    in_amount,
+                    out_amount,
+                    remaining_in_amount,
+                    counterparty,
+                    maker,
+                    remaining_out_amount,
+                    ..
+                },
+                Some(order_pda),
+            ) => {
+                let fill = LimitFillEvent {
+                    order_pda,
+                    in_amount,
+                    out_amount,
+                    remaining_in_amount,
+                    remaining_out_amount,
+                    counterparty,
+                    maker,
+                };
+                if protocol == Protocol::LimitV1 {
+                    Self::LimitV1Filled(fill)
+                } else {
+                    Self::LimitV2Filled(fill)
+                }
+            }
+            (Protocol::LimitV2, EventPayload::Closed { status }, Some(order_pda)) => {
+                Self::LimitV2Closed(OrderClosedEvent { order_pda, status })
+            }
+            (
+                Protocol::Kamino,
+                EventPayload::KaminoDisplay {
+                    remaining_input_amount,
+                    filled_output_amount,
+                    terminal_status,
+                    ..
+                },
+                _,
+            ) => Self::KaminoDisplay(KaminoDisplaySnapshot {
+                order_pdas: match correlation {
+                    CorrelationOutcome::Correlated(pdas) => pdas,
+                    _ => Vec::new(),
+                },
+                remaining_input_amount,
+                filled_output_amount,
+                terminal_status,
+            }),
+            (_, payload, _) => Self::Other {
+                protocol,
+                event: (event_type, correlation, payload),
+            },
+        }
+    }
+
+    /// Protocol the event was resolved with.
+    pub fn protocol(&self) -> Protocol {
+        match self {
+            Self::DcaFilled(_) | Self::DcaClosed(_) => Protocol::Dca,
+            Self::VaFilled(_) | Self::VaClosed(_) => Protocol::ValueAveraging,
+            Self::LimitV1Filled(_) => Protocol::LimitV1,
+            Self::LimitV2Filled(_) | Self::LimitV2Closed(_) => Protocol::LimitV2,
+            Self::KaminoDisplay(_) => Protocol::Kamino,
+            Self::Other { protocol, .. } => *protocol,
+        }
+    }
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used, reason = "test assertions")]
+mod tests {
+    use super::*;
+    use crate::protocols::EventType;
+    use crate::test_util::RawEventBuilder;
+
+    #[test]
+    fn payload_variants_lift_into_protocol_tagged_events() {
+        let va_fill = TypedEvent::from_resolved(
+            Protocol::ValueAveraging,
+            (
+                EventType::FillCompleted,
+                CorrelationOutcome::Correlated(vec!["va1".to_string()]),
+                EventPayload::DcaFill {
+                    in_amount: 10,
+                    out_amount: 4,
+                    saturated: false,
+                    cycle: None,
+                },
+            ),
+        );
+        assert_eq!(
+            va_fill,
+            TypedEvent::VaFilled(DcaFillEvent {
+                order_pda: "va1".to_string(),
+                in_amount: 10,
+                out_amount: 4,
+            })
+        );
+        assert_eq!(va_fill.protocol(), Protocol::ValueAveraging);
+
+        let uncorrelated = (
+            EventType::Closed,
+            CorrelationOutcome::Uncorrelated {
+                reason: "missing".to_string(),
+            },
+            EventPayload::DcaClosed {
+                status: TerminalStatus::Completed,
+                total_in_withdrawn: 0,
+                total_out_withdrawn: 0,
+            },
+        );
+        assert_eq!(
+            TypedEvent::from_resolved(Protocol::Dca, uncorrelated.clone()),
+            TypedEvent::Other {
+                protocol: Protocol::Dca,
+                event: uncorrelated,
+            }
+        );
+    }
+
+    #[test]
+    fn from_raw_skips_unknown_events() {
+        let ev = RawEventBuilder::new("MysteryEvent")
+            .fields(serde_json::json!({ "MysteryEvent": {} }))
+            .build();
+        assert!(TypedEvent::from_raw(Protocol::Dca, &ev, &ResolveContext::default()).is_none());
+
+        let opened = RawEventBuilder::new("OpenedEvent")
+            .fields(serde_json::json!({ "OpenedEvent": { "dca_key": "order1" } }))
+            .build();
+        let typed = TypedEvent::from_raw(Protocol::Dca, &opened, &ResolveContext::default())
+            .unwrap()
+            .unwrap();
+        assert!(matches!(
+            typed,
+            TypedEvent::Other {
+                protocol: Protocol::Dca,
+                event: (EventType::Created, _, EventPayload::None),
+            }
+        ));
+    }
+
+    #[test]
+    fn limit_v2_completed_cancel_lifts_into_closed_event() {
+        let cancel = RawEventBuilder::new("CancelOrderEvent")
+            .fields(serde_json::json!({ "CancelOrderEvent": { "order_key": "order1" } }))
+            .build();
+        let ctx = ResolveContext {
+            order_remaining_making_amount: Some(0),
+            ..ResolveContext::default()
+        };
+        let typed = TypedEvent::from_raw(Protocol::LimitV2, &cancel, &ctx)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            typed,
+            TypedEvent::LimitV2Closed(OrderClosedEvent {
+                order_pda: "order1".to_string(),
+                status: TerminalStatus::Completed,
+            })
+        );
+        assert_eq!(typed.protocol(), Protocol::LimitV2);
+    }
+}
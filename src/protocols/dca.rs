@@ -1,17 +1,19 @@
-use crate::error::Error;
+use crate::error::{Error, ErrorCode};
 use crate::lifecycle::adapters::{
-    CorrelationOutcome, EventPayload, ProtocolAdapter, dca_closed_terminal_status,
+    CorrelationOutcome, EventPayload, InstructionArgs, InstructionOutcome, ProtocolAdapter,
+    ResolvedEvent, dca_closed_terminal_status,
+};
+use crate::protocols::{
+    AccountInfo, AmountCaster, EventType, InstructionCategory, Protocol, ProtocolHelpers,
 };
-use crate::protocols::{AccountInfo, EventType, Protocol, ProtocolHelpers};
 use crate::types::{RawEvent, RawInstruction, ResolveContext};
-use strum::VariantNames;
 
 /// Serde-tagged envelope for Jupiter DCA event variants.
 ///
 /// Variant names mirror the Carbon decoder crate exactly.
 #[derive(serde::Deserialize, strum_macros::VariantNames)]
 pub enum DcaEventEnvelope {
-    OpenedEvent(DcaKeyHolder),
+    OpenedEvent(OpenedEventFields),
     FilledEvent(FilledEventFields),
     ClosedEvent(ClosedEventFields),
     CollectedFeeEvent(DcaKeyHolder),
@@ -22,7 +24,7 @@ pub enum DcaEventEnvelope {
 /// Serde-tagged envelope for Jupiter DCA instruction variants.
 ///
 /// Inner `Value` is unused at runtime — serde consumes it during deserialization.
-#[derive(serde::Deserialize)]
+#[derive(serde::Deserialize, strum_macros::VariantNames)]
 pub enum DcaInstructionKind {
     OpenDca(serde_json::Value),
     OpenDcaV2(serde_json::Value),
@@ -49,6 +51,15 @@ pub const INSTRUCTION_EVENT_TYPES: &[(&str, EventType)] = &[
     ("EndAndClose", EventType::Closed),
 ];
 
+/// Known instructions outside [`INSTRUCTION_EVENT_TYPES`], with their [`InstructionCategory`].
+pub const INSTRUCTION_CATEGORIES: &[(&str, InstructionCategory)] = &[
+    ("Deposit", InstructionCategory::Balance),
+    ("Withdraw", InstructionCategory::Balance),
+    // Keeper payout of output tokens (`dca_out_ata` → `user_out_ata`); the owner is unchanged.
+    ("Transfer", InstructionCategory::Balance),
+    ("WithdrawFees", InstructionCategory::Fee),
+];
+
 pub const EVENT_EVENT_TYPES: &[(&str, EventType)] = &[
     ("OpenedEvent", EventType::Created),
     ("FilledEvent", EventType::FillCompleted),
@@ -58,31 +69,109 @@ pub const EVENT_EVENT_TYPES: &[(&str, EventType)] = &[
     ("DepositEvent", EventType::Deposited),
 ];
 
+/// [`crate::EventPayload`] variant each event in [`EVENT_EVENT_TYPES`] resolves to.
+/// `OpenedEvent` falls back to `None` when the schedule fields are absent.
+pub const EVENT_PAYLOAD_KINDS: &[(&str, &str)] = &[
+    ("OpenedEvent", "DcaCreated"),
+    ("FilledEvent", "DcaFill"),
+    ("ClosedEvent", "DcaClosed"),
+    ("CollectedFeeEvent", "None"),
+    ("WithdrawEvent", "None"),
+    ("DepositEvent", "None"),
+];
+
+/// Event variants whose [`EventType`] depends on [`ResolveContext`], with the alternative type.
+///
+/// No DCA event changes type with the context.
+pub const CONTEXT_EVENT_TYPES: &[(&str, EventType)] = &[];
+
+/// Every event type the adapter can produce: the union of [`INSTRUCTION_EVENT_TYPES`],
+/// [`EVENT_EVENT_TYPES`] and the context-derived [`CONTEXT_EVENT_TYPES`].
+pub const POSSIBLE_EVENT_TYPES: &[EventType] = &[
+    EventType::Created,
+    EventType::FillInitiated,
+    EventType::FillCompleted,
+    EventType::Closed,
+    EventType::FeeCollected,
+    EventType::Withdrawn,
+    EventType::Deposited,
+];
+
 pub const CLOSED_VARIANTS: &[&str] = &["Completed", "Cancelled", "Expired"];
 
+/// [`DcaEventEnvelope`] variant names as a compile-time set (see [`crate::protocols::is_known_variant`]).
+pub static EVENT_VARIANTS: phf::Set<&'static str> = phf::phf_set! {
+    "OpenedEvent",
+    "FilledEvent",
+    "ClosedEvent",
+    "CollectedFeeEvent",
+    "WithdrawEvent",
+    "DepositEvent",
+};
+
 /// Jupiter DCA protocol adapter (zero-sized, stored as a static).
 #[derive(Debug)]
 pub struct DcaAdapter;
 
+/// Serde intermediate for `OpenedEvent` payload fields.
+///
+/// Schedule fields are optional so key-only payloads still correlate. v2-only fields default,
+/// so both `OpenDca` and `OpenDcaV2` payloads parse.
+#[derive(serde::Deserialize)]
+pub struct OpenedEventFields {
+    #[serde(deserialize_with = "crate::protocols::de_nonempty_pubkey")]
+    dca_key: String,
+    #[serde(default, deserialize_with = "crate::protocols::de_opt_u64_flexible")]
+    cycle_frequency: Option<u64>,
+    #[serde(default, deserialize_with = "crate::protocols::de_opt_u64_flexible")]
+    in_amount_per_cycle: Option<u64>,
+    #[serde(default, deserialize_with = "crate::protocols::de_opt_u64_flexible")]
+    in_deposited: Option<u64>,
+    /// Rent payer, emitted only by `OpenDcaV2` (which splits payer from user).
+    #[serde(default)]
+    payer: Option<String>,
+}
+
+impl OpenedEventFields {
+    /// `2` when a v2-only field is present, otherwise `1`.
+    fn dca_version(&self) -> u8 {
+        if self.payer.is_some() { 2 } else { 1 }
+    }
+}
+
 /// Serde intermediate for `FilledEvent` payload fields.
 #[derive(serde::Deserialize)]
 pub struct FilledEventFields {
+    #[serde(deserialize_with = "crate::protocols::de_nonempty_pubkey")]
     dca_key: String,
+    #[serde(deserialize_with = "crate::protocols::de_u64_flexible")]
     in_amount: u64,
+    #[serde(deserialize_with = "crate::protocols::de_u64_flexible")]
     out_amount: u64,
+    #[serde(default, deserialize_with = "crate::protocols::de_opt_u64_flexible")]
+    cycle: Option<u64>,
 }
 
 /// Serde intermediate for `ClosedEvent` payload fields.
+///
+/// Withdrawn totals default to 0 so minimal payloads without them still parse.
 #[derive(serde::Deserialize)]
 pub struct ClosedEventFields {
+    #[serde(deserialize_with = "crate::protocols::de_nonempty_pubkey")]
     dca_key: String,
     user_closed: bool,
+    #[serde(deserialize_with = "crate::protocols::de_u64_flexible")]
     unfilled_amount: u64,
+    #[serde(default, deserialize_with = "crate::protocols::de_u64_flexible")]
+    total_in_withdrawn: u64,
+    #[serde(default, deserialize_with = "crate::protocols::de_u64_flexible")]
+    total_out_withdrawn: u64,
 }
 
 /// Serde intermediate for events that only carry a `dca_key`.
 #[derive(serde::Deserialize)]
 pub struct DcaKeyHolder {
+    #[serde(deserialize_with = "crate::protocols::de_nonempty_pubkey")]
     dca_key: String,
 }
 
@@ -93,7 +182,8 @@ pub struct DcaClosedEvent {
     pub unfilled_amount: i64,
 }
 
-/// Extracted DCA fill event with checked-cast amounts.
+/// Extracted DCA fill event with checked-cast amounts (also the typed Value Averaging fill).
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DcaFillEvent {
     pub order_pda: String,
     pub in_amount: i64,
@@ -101,6 +191,7 @@ pub struct DcaFillEvent {
 }
 
 /// Parsed arguments from an `OpenDca`/`OpenDcaV2` instruction.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DcaCreateArgs {
     pub in_amount: i64,
     pub in_amount_per_cycle: i64,
@@ -110,18 +201,96 @@ pub struct DcaCreateArgs {
     pub start_at: Option<i64>,
 }
 
+/// Pairs DCA fill initiations with their completions, transaction by transaction.
+///
+/// A `FillInitiated` (`InitiateFlashFill`/`InitiateDlmmFill`) with no later `FillCompleted` in
+/// the same transaction means the fill reverted and should not be counted. Feed classified
+/// instructions in order; unpaired initiations of a transaction become dangling once a different
+/// signature arrives or [`Self::finish`] is called.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DcaFlashFillTracker {
+    signature: Option<String>,
+    pending_inits: u64,
+    paired_fills: u64,
+    dangling_fill_inits: u64,
+}
+
+impl DcaFlashFillTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Classifies `ix` with [`DcaAdapter`] and [`Self::observe`]s the result, if any.
+    pub fn observe_instruction(&mut self, ix: &RawInstruction) {
+        if let Some(event_type) = DcaAdapter.classify_instruction(ix) {
+            self.observe(&ix.signature, event_type);
+        }
+    }
+
+    /// Records one classified instruction of transaction `signature`.
+    ///
+    /// A `FillCompleted` without a pending initiation (e.g. a `FilledEvent`) is ignored.
+    pub fn observe(&mut self, signature: &str, event_type: EventType) {
+        if self.signature.as_deref() != Some(signature) {
+            self.finish();
+            self.signature = Some(signature.to_string());
+        }
+        match event_type {
+            EventType::FillInitiated => self.pending_inits += 1,
+            EventType::FillCompleted if self.pending_inits > 0 => {
+                self.pending_inits -= 1;
+                self.paired_fills += 1;
+            }
+            _ => {}
+        }
+    }
+
+    /// Closes the current transaction, counting its unpaired initiations as dangling.
+    pub fn finish(&mut self) {
+        self.dangling_fill_inits += self.pending_inits;
+        self.pending_inits = 0;
+    }
+
+    /// Initiations matched by a completion in the same transaction.
+    pub fn paired_fills(&self) -> u64 {
+        self.paired_fills
+    }
+
+    /// Initiations whose transaction ended without a completion (reverted fills).
+    pub fn dangling_fill_inits(&self) -> u64 {
+        self.dangling_fill_inits
+    }
+}
+
 /// Input and output mint addresses extracted from a DCA create instruction.
 pub struct DcaCreateMints {
     pub input_mint: String,
     pub output_mint: String,
 }
 
+/// `FulfillDlmmFill` args. On-chain only `repay_amount` exists; `out_amount` is optional
+/// decoder enrichment (e.g. from the `out_ata` balance change).
+#[derive(serde::Deserialize)]
+struct DlmmFillFields {
+    #[serde(
+        alias = "in_amount",
+        deserialize_with = "crate::protocols::de_u64_flexible"
+    )]
+    repay_amount: u64,
+    #[serde(default, deserialize_with = "crate::protocols::de_u64_flexible")]
+    out_amount: u64,
+}
+
 #[derive(serde::Deserialize)]
 struct OpenDcaFields {
+    #[serde(deserialize_with = "crate::protocols::de_u64_flexible")]
     in_amount: u64,
+    #[serde(deserialize_with = "crate::protocols::de_u64_flexible")]
     in_amount_per_cycle: u64,
     cycle_frequency: i64,
+    #[serde(default, deserialize_with = "crate::protocols::de_opt_u64_flexible")]
     min_out_amount: Option<u64>,
+    #[serde(default, deserialize_with = "crate::protocols::de_opt_u64_flexible")]
     max_out_amount: Option<u64>,
     start_at: Option<i64>,
 }
@@ -132,73 +301,136 @@ impl ProtocolAdapter for DcaAdapter {
     }
 
     fn classify_instruction(&self, ix: &RawInstruction) -> Option<EventType> {
-        ProtocolHelpers::lookup_event_type(&ix.instruction_name, INSTRUCTION_EVENT_TYPES)
+        self.classify_instruction_category(ix).event_type()
+    }
+
+    fn classify_instruction_category(&self, ix: &RawInstruction) -> InstructionCategory {
+        ProtocolHelpers::lookup_instruction_category(
+            &ix.instruction_name,
+            INSTRUCTION_EVENT_TYPES,
+            INSTRUCTION_CATEGORIES,
+        )
+    }
+
+    fn resolve_instruction(
+        &self,
+        ix: &RawInstruction,
+    ) -> Option<Result<InstructionOutcome, Error>> {
+        let event_type = self.classify_instruction(ix)?;
+        Some(ProtocolHelpers::resolve_classified_instruction(
+            ix,
+            event_type,
+            Self::extract_order_pda,
+            |args| Self::parse_create_args(args).map(InstructionArgs::DcaCreate),
+        ))
     }
 
     fn classify_and_resolve_event(
         &self,
         ev: &RawEvent,
-        _ctx: &ResolveContext,
-    ) -> Option<Result<(EventType, CorrelationOutcome, EventPayload), Error>> {
-        let fields = ev.fields.as_ref()?;
+        ctx: &ResolveContext,
+    ) -> Option<Result<ResolvedEvent, Error>> {
+        let fields = ProtocolHelpers::wrapped_event_fields(ev, &EVENT_VARIANTS)?;
+        Self::resolve_fields(&fields, ctx)
+    }
+}
+
+impl DcaAdapter {
+    /// Classifies and resolves a bare event `fields` payload (see [`crate::resolve_event_value`]).
+    pub fn resolve_fields(
+        fields: &serde_json::Value,
+        ctx: &ResolveContext,
+    ) -> Option<Result<ResolvedEvent, Error>> {
         let envelope: DcaEventEnvelope = match serde_json::from_value(fields.clone()) {
             Ok(e) => e,
             Err(err) => {
-                if !ProtocolHelpers::contains_known_variant(fields, DcaEventEnvelope::VARIANTS) {
+                if !ProtocolHelpers::contains_known_variant(fields, &EVENT_VARIANTS) {
                     return None;
                 }
                 return Some(Err(Error::Protocol {
+                    code: ErrorCode::MalformedPayload,
                     reason: format!("failed to parse DCA event payload: {err}"),
                 }));
             }
         };
 
-        Some(Self::resolve_event(envelope))
+        Some(Self::resolve_event(envelope, ctx))
     }
-}
 
-impl DcaAdapter {
     fn resolve_event(
         envelope: DcaEventEnvelope,
+        ctx: &ResolveContext,
     ) -> Result<(EventType, CorrelationOutcome, EventPayload), Error> {
+        let mut amounts = AmountCaster::new(ctx);
         match envelope {
             DcaEventEnvelope::FilledEvent(FilledEventFields {
                 dca_key,
                 in_amount,
                 out_amount,
+                cycle,
             }) => Ok((
                 EventType::FillCompleted,
                 CorrelationOutcome::Correlated(vec![dca_key]),
                 EventPayload::DcaFill {
-                    in_amount: ProtocolHelpers::checked_u64_to_i64(in_amount, "in_amount")?,
-                    out_amount: ProtocolHelpers::checked_u64_to_i64(out_amount, "out_amount")?,
+                    in_amount: amounts.cast(in_amount, "in_amount")?,
+                    out_amount: amounts.cast(out_amount, "out_amount")?,
+                    saturated: amounts.saturated(),
+                    cycle: cycle.and_then(ProtocolHelpers::optional_u64_to_i64),
                 },
             )),
             DcaEventEnvelope::ClosedEvent(ClosedEventFields {
                 dca_key,
                 user_closed,
                 unfilled_amount,
+                total_in_withdrawn,
+                total_out_withdrawn,
             }) => {
                 let closed = DcaClosedEvent {
                     order_pda: dca_key,
                     user_closed,
-                    unfilled_amount: ProtocolHelpers::checked_u64_to_i64(
-                        unfilled_amount,
-                        "unfilled_amount",
-                    )?,
+                    unfilled_amount: amounts.cast(unfilled_amount, "unfilled_amount")?,
                 };
                 let status = dca_closed_terminal_status(&closed);
                 Ok((
                     EventType::Closed,
                     CorrelationOutcome::Correlated(vec![closed.order_pda]),
-                    EventPayload::DcaClosed { status },
+                    EventPayload::DcaClosed {
+                        status,
+                        total_in_withdrawn: amounts
+                            .cast(total_in_withdrawn, "total_in_withdrawn")?,
+                        total_out_withdrawn: amounts
+                            .cast(total_out_withdrawn, "total_out_withdrawn")?,
+                    },
+                ))
+            }
+            DcaEventEnvelope::OpenedEvent(opened) => {
+                let dca_version = opened.dca_version();
+                let OpenedEventFields {
+                    dca_key,
+                    cycle_frequency,
+                    in_amount_per_cycle,
+                    in_deposited,
+                    payer: _,
+                } = opened;
+                let payload = match (cycle_frequency, in_amount_per_cycle, in_deposited) {
+                    (Some(cycle_frequency), Some(in_amount_per_cycle), Some(in_deposited)) => {
+                        EventPayload::DcaCreated {
+                            cycle_frequency: amounts.cast(cycle_frequency, "cycle_frequency")?,
+                            in_amount_per_cycle: amounts
+                                .cast(in_amount_per_cycle, "in_amount_per_cycle")?,
+                            in_deposited: amounts.cast(in_deposited, "in_deposited")?,
+                            saturated: amounts.saturated(),
+                            dca_version,
+                        }
+                    }
+                    _ => EventPayload::None,
+                };
+                Ok((
+                    EventType::Created,
+                    CorrelationOutcome::Correlated(vec![dca_key]),
+                    payload,
                 ))
             }
-            DcaEventEnvelope::OpenedEvent(DcaKeyHolder { dca_key }) => Ok((
-                EventType::Created,
-                CorrelationOutcome::Correlated(vec![dca_key]),
-                EventPayload::None,
-            )),
             DcaEventEnvelope::CollectedFeeEvent(DcaKeyHolder { dca_key }) => Ok((
                 EventType::FeeCollected,
                 CorrelationOutcome::Correlated(vec![dca_key]),
@@ -228,9 +460,24 @@ impl DcaAdapter {
             return Ok(acc.pubkey.clone());
         }
 
+        let idx = Self::order_pda_index(instruction_name)?;
+
+        accounts
+            .get(idx)
+            .map(|a| a.pubkey.clone())
+            .ok_or_else(|| Error::Protocol {
+                code: ErrorCode::OutOfBoundsAccount,
+                reason: format!("DCA account index {idx} out of bounds for {instruction_name}"),
+            })
+    }
+
+    /// Positional index of the `"dca"` account in `instruction_name`, used when the account
+    /// is not named.
+    pub fn order_pda_index(instruction_name: &str) -> Result<usize, Error> {
         let wrapper = serde_json::json!({ instruction_name: serde_json::Value::Null });
         let kind: DcaInstructionKind =
             serde_json::from_value(wrapper).map_err(|_| Error::Protocol {
+                code: ErrorCode::UnknownInstruction,
                 reason: format!("unknown DCA instruction: {instruction_name}"),
             })?;
 
@@ -240,28 +487,25 @@ impl DcaAdapter {
             | DcaInstructionKind::FulfillFlashFill(_)
             | DcaInstructionKind::InitiateDlmmFill(_)
             | DcaInstructionKind::FulfillDlmmFill(_) => 1,
-            DcaInstructionKind::CloseDca(_) | DcaInstructionKind::EndAndClose(_) => 1,
-            DcaInstructionKind::Transfer(_)
-            | DcaInstructionKind::Deposit(_)
+            DcaInstructionKind::CloseDca(_)
+            | DcaInstructionKind::EndAndClose(_)
+            | DcaInstructionKind::Transfer(_) => 1,
+            DcaInstructionKind::Deposit(_)
             | DcaInstructionKind::Withdraw(_)
             | DcaInstructionKind::WithdrawFees(_) => {
                 return Err(Error::Protocol {
+                    code: ErrorCode::UnsupportedInstruction,
                     reason: format!("DCA instruction {instruction_name} has no order PDA"),
                 });
             }
         };
-
-        accounts
-            .get(idx)
-            .map(|a| a.pubkey.clone())
-            .ok_or_else(|| Error::Protocol {
-                reason: format!("DCA account index {idx} out of bounds for {instruction_name}"),
-            })
+        Ok(idx)
     }
 
     /// Extracts input/output mint addresses from a DCA create instruction's accounts.
     ///
-    /// Prefers named accounts; falls back to positional indexes that differ between `OpenDca` and `OpenDcaV2`.
+    /// Prefers each named account independently; falls back to positional indexes (which differ
+    /// between `OpenDca` and `OpenDcaV2`) only for a side without a name.
     pub fn extract_create_mints(
         accounts: &[AccountInfo],
         instruction_name: &str,
@@ -281,6 +525,7 @@ impl DcaAdapter {
         let wrapper = serde_json::json!({ instruction_name: serde_json::Value::Null });
         let kind: DcaInstructionKind =
             serde_json::from_value(wrapper).map_err(|_| Error::Protocol {
+                code: ErrorCode::UnknownInstruction,
                 reason: format!("unknown DCA instruction: {instruction_name}"),
             })?;
 
@@ -289,23 +534,20 @@ impl DcaAdapter {
             DcaInstructionKind::OpenDcaV2(_) => (3, 4),
             _ => {
                 return Err(Error::Protocol {
+                    code: ErrorCode::UnsupportedInstruction,
                     reason: format!("not a DCA create instruction: {instruction_name}"),
                 });
             }
         };
 
-        let input_mint = accounts
-            .get(input_idx)
-            .map(|a| a.pubkey.clone())
-            .ok_or_else(|| Error::Protocol {
-                reason: format!("DCA input_mint index {input_idx} out of bounds"),
-            })?;
-        let output_mint = accounts
-            .get(output_idx)
-            .map(|a| a.pubkey.clone())
-            .ok_or_else(|| Error::Protocol {
-                reason: format!("DCA output_mint index {output_idx} out of bounds"),
-            })?;
+        let input_mint =
+            ProtocolHelpers::named_or_positional_account(accounts, "input_mint", input_idx, "DCA")?;
+        let output_mint = ProtocolHelpers::named_or_positional_account(
+            accounts,
+            "output_mint",
+            output_idx,
+            "DCA",
+        )?;
 
         Ok(DcaCreateMints {
             input_mint,
@@ -313,6 +555,29 @@ impl DcaAdapter {
         })
     }
 
+    /// Parses `FulfillDlmmFill` args into a checked [`DcaFillEvent`].
+    ///
+    /// `repay_amount` is the input consumed by the fill; `out_amount` defaults to 0 when the
+    /// decoder did not supply it. The order PDA comes from `accounts` (named `"dca"` or index 1).
+    pub fn parse_dlmm_fill_args(
+        args: &serde_json::Value,
+        accounts: &[AccountInfo],
+    ) -> Result<DcaFillEvent, Error> {
+        let DlmmFillFields {
+            repay_amount,
+            out_amount,
+        } = serde_json::from_value(args.clone()).map_err(|e| Error::Protocol {
+            code: ErrorCode::MalformedPayload,
+            reason: format!("failed to parse DCA DLMM fill args: {e}"),
+        })?;
+
+        Ok(DcaFillEvent {
+            order_pda: Self::extract_order_pda(accounts, "FulfillDlmmFill")?,
+            in_amount: ProtocolHelpers::checked_u64_to_i64(repay_amount, "repay_amount")?,
+            out_amount: ProtocolHelpers::checked_u64_to_i64(out_amount, "out_amount")?,
+        })
+    }
+
     /// Parses `OpenDca`/`OpenDcaV2` instruction args into checked [`DcaCreateArgs`].
     pub fn parse_create_args(args: &serde_json::Value) -> Result<DcaCreateArgs, Error> {
         let OpenDcaFields {
@@ -322,9 +587,7 @@ impl DcaAdapter {
             min_out_amount,
             max_out_amount,
             start_at,
-        } = serde_json::from_value(args.clone()).map_err(|e| Error::Protocol {
-            reason: format!("failed to parse DCA create args: {e}"),
-        })?;
+        } = ProtocolHelpers::parse_create_fields(args, "DCA")?;
 
         Ok(DcaCreateArgs {
             in_amount: ProtocolHelpers::checked_u64_to_i64(in_amount, "in_amount")?,
@@ -332,10 +595,16 @@ impl DcaAdapter {
                 in_amount_per_cycle,
                 "in_amount_per_cycle",
             )?,
-            cycle_frequency,
+            cycle_frequency: ProtocolHelpers::checked_timestamp(
+                cycle_frequency,
+                "cycle_frequency",
+            )?,
             min_out_amount: min_out_amount.and_then(ProtocolHelpers::optional_u64_to_i64),
             max_out_amount: max_out_amount.and_then(ProtocolHelpers::optional_u64_to_i64),
-            start_at: start_at.filter(|&ts| ts > 0),
+            start_at: start_at
+                .filter(|&ts| ts > 0)
+                .map(|ts| ProtocolHelpers::checked_timestamp(ts, "start_at"))
+                .transpose()?,
         })
     }
 
@@ -379,6 +648,7 @@ impl DcaAdapter {
 mod tests {
     use super::*;
     use crate::lifecycle::TerminalStatus;
+    use crate::test_util::{RawEventBuilder, RawInstructionBuilder};
 
     fn account(pubkey: &str, name: Option<&str>) -> AccountInfo {
         AccountInfo {
@@ -390,26 +660,14 @@ mod tests {
     }
 
     fn make_event(fields: serde_json::Value) -> RawEvent {
-        RawEvent {
-            id: 1,
-            signature: "sig".to_string(),
-            event_index: 0,
-            event_path: None,
-            program_id: "p".to_string(),
-            inner_program_id: "p".to_string(),
-            event_name: "test".to_string(),
-            fields: Some(fields),
-            slot: 1,
-        }
+        RawEventBuilder::default().fields(fields).build()
     }
 
     fn resolve(
         fields: serde_json::Value,
     ) -> Option<Result<(EventType, CorrelationOutcome, EventPayload), crate::error::Error>> {
         let ev = make_event(fields);
-        let ctx = ResolveContext {
-            pre_fetched_order_pdas: None,
-        };
+        let ctx = ResolveContext::default();
         DcaAdapter.classify_and_resolve_event(&ev, &ctx)
     }
 
@@ -431,18 +689,7 @@ mod tests {
             ("Unknown", None),
         ];
         for (name, expected) in cases {
-            let ix = RawInstruction {
-                id: 1,
-                signature: "sig".to_string(),
-                instruction_index: 0,
-                instruction_path: None,
-                program_id: "p".to_string(),
-                inner_program_id: "p".to_string(),
-                instruction_name: name.to_string(),
-                accounts: None,
-                args: None,
-                slot: 1,
-            };
+            let ix = RawInstructionBuilder::new(name).build();
             assert_eq!(
                 DcaAdapter.classify_instruction(&ix),
                 expected,
@@ -451,6 +698,39 @@ mod tests {
         }
     }
 
+    #[test]
+    fn flash_fill_tracker_pairs_inits_within_a_transaction() {
+        let ix = |signature: &str, name: &str| {
+            RawInstructionBuilder::new(name)
+                .signature(signature)
+                .build()
+        };
+
+        let mut paired = DcaFlashFillTracker::new();
+        for row in [
+            ix("tx1", "InitiateFlashFill"),
+            ix("tx1", "FulfillFlashFill"),
+            ix("tx2", "InitiateDlmmFill"),
+            ix("tx2", "Transfer"),
+            ix("tx2", "FulfillDlmmFill"),
+        ] {
+            paired.observe_instruction(&row);
+        }
+        paired.finish();
+        assert_eq!(paired.paired_fills(), 2);
+        assert_eq!(paired.dangling_fill_inits(), 0);
+
+        let mut dangling = DcaFlashFillTracker::new();
+        dangling.observe_instruction(&ix("tx1", "InitiateFlashFill"));
+        dangling.observe_instruction(&ix("tx2", "InitiateFlashFill"));
+        assert_eq!(dangling.dangling_fill_inits(), 1);
+        dangling.observe_instruction(&ix("tx2", "FulfillFlashFill"));
+        dangling.observe_instruction(&ix("tx3", "FulfillFlashFill"));
+        dangling.finish();
+        assert_eq!(dangling.paired_fills(), 1);
+        assert_eq!(dangling.dangling_fill_inits(), 1);
+    }
+
     #[test]
     fn resolve_fill_event_from_envelope() {
         let fields = serde_json::json!({
@@ -474,12 +754,28 @@ mod tests {
         let EventPayload::DcaFill {
             in_amount,
             out_amount,
+            saturated,
+            cycle,
         } = payload
         else {
             panic!("expected DcaFill");
         };
+        assert!(!saturated);
         assert_eq!(in_amount, 21_041_666_667);
         assert_eq!(out_amount, 569_529_644);
+        assert_eq!(cycle, None);
+    }
+
+    #[test]
+    fn resolve_fill_event_with_cycle() {
+        let fields = serde_json::json!({
+            "FilledEvent": { "dca_key": "pda1", "in_amount": 100, "out_amount": 40, "cycle": 3 }
+        });
+        let (_, _, payload) = resolve(fields).unwrap().unwrap();
+        let EventPayload::DcaFill { cycle, .. } = payload else {
+            panic!("expected DcaFill");
+        };
+        assert_eq!(cycle, Some(3));
     }
 
     #[test]
@@ -499,7 +795,9 @@ mod tests {
         assert_eq!(
             payload,
             EventPayload::DcaClosed {
-                status: TerminalStatus::Completed
+                status: TerminalStatus::Completed,
+                total_in_withdrawn: 0,
+                total_out_withdrawn: 0,
             }
         );
     }
@@ -519,6 +817,19 @@ mod tests {
             correlation,
             CorrelationOutcome::Correlated(vec!["my_pda".to_string()])
         );
+        assert_eq!(
+            payload,
+            EventPayload::DcaCreated {
+                cycle_frequency: 60,
+                in_amount_per_cycle: 100,
+                in_deposited: 500,
+                saturated: false,
+                dca_version: 1,
+            }
+        );
+
+        let key_only = serde_json::json!({ "OpenedEvent": { "dca_key": "my_pda" } });
+        let (_, _, payload) = resolve(key_only).unwrap().unwrap();
         assert_eq!(payload, EventPayload::None);
     }
 
@@ -528,6 +839,44 @@ mod tests {
         assert!(resolve(fields).is_none());
     }
 
+    #[test]
+    fn resolve_fill_event_without_variant_wrapper() {
+        let ev = RawEventBuilder::new("FilledEvent")
+            .fields(serde_json::json!({
+                "dca_key": "order_pda",
+                "in_amount": 100_u64,
+                "out_amount": 40_u64
+            }))
+            .build();
+        let (event_type, correlation, payload) = DcaAdapter
+            .classify_and_resolve_event(&ev, &ResolveContext::default())
+            .unwrap()
+            .unwrap();
+        assert_eq!(event_type, EventType::FillCompleted);
+        assert_eq!(
+            correlation,
+            CorrelationOutcome::Correlated(vec!["order_pda".to_string()])
+        );
+        assert_eq!(
+            payload,
+            EventPayload::DcaFill {
+                in_amount: 100,
+                out_amount: 40,
+                saturated: false,
+                cycle: None,
+            }
+        );
+
+        let unknown = RawEventBuilder::new("MysteryEvent")
+            .fields(serde_json::json!({ "dca_key": "order_pda" }))
+            .build();
+        assert!(
+            DcaAdapter
+                .classify_and_resolve_event(&unknown, &ResolveContext::default())
+                .is_none()
+        );
+    }
+
     #[test]
     fn malformed_known_event_returns_error() {
         let fields = serde_json::json!({
@@ -541,6 +890,13 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn empty_dca_key_is_a_parse_error() {
+        let fields = serde_json::json!({ "DepositEvent": { "dca_key": "  " } });
+        let err = resolve(fields).unwrap().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::MalformedPayload);
+    }
+
     #[test]
     fn resolve_fill_event_rejects_amount_overflow() {
         let fields = serde_json::json!({
@@ -554,6 +910,39 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn resolve_fill_event_saturates_overflow_when_enabled() {
+        let overflowing = (i64::MAX as u64) + 1;
+        let fields = serde_json::json!({
+            "FilledEvent": { "dca_key": "pda", "in_amount": overflowing, "out_amount": 7_u64 }
+        });
+        let checked = DcaAdapter::resolve_fields(&fields, &ResolveContext::default()).unwrap();
+        assert_eq!(checked.unwrap_err().code(), ErrorCode::AmountOverflow);
+
+        let ctx = ResolveContext {
+            saturate_amounts: true,
+            ..ResolveContext::default()
+        };
+        let (_, _, payload) = DcaAdapter::resolve_fields(&fields, &ctx).unwrap().unwrap();
+        assert_eq!(
+            payload,
+            EventPayload::DcaFill {
+                in_amount: i64::MAX,
+                out_amount: 7,
+                saturated: true,
+                cycle: None,
+            }
+        );
+        assert_eq!(
+            ProtocolHelpers::saturating_u64_to_i64(overflowing),
+            i64::MAX
+        );
+        assert_eq!(
+            ProtocolHelpers::saturating_u64_to_i64(i64::MAX as u64),
+            i64::MAX
+        );
+    }
+
     #[test]
     fn parse_create_args_rejects_overflow_amounts() {
         let args = serde_json::json!({
@@ -612,6 +1001,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_create_args_rejects_implausible_cycle_frequency() {
+        let args = serde_json::json!({
+            "in_amount": 1_000_u64,
+            "in_amount_per_cycle": 100_u64,
+            "cycle_frequency": i64::MIN
+        });
+        let err = DcaAdapter::parse_create_args(&args).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::MalformedPayload);
+        assert!(err.to_string().contains("cycle_frequency"));
+    }
+
     #[test]
     fn parse_create_args_rejects_malformed_payload() {
         let args = serde_json::json!({
@@ -651,7 +1052,7 @@ mod tests {
     #[test]
     fn extract_order_pda_rejects_unknown_instruction() {
         let err = DcaAdapter::extract_order_pda(&[account("a", None)], "Unknown").unwrap_err();
-        let Error::Protocol { reason } = err else {
+        let Error::Protocol { reason, .. } = err else {
             panic!("expected protocol error");
         };
         assert_eq!(reason, "unknown DCA instruction: Unknown");
@@ -660,12 +1061,99 @@ mod tests {
     #[test]
     fn extract_order_pda_rejects_out_of_bounds_fallback() {
         let err = DcaAdapter::extract_order_pda(&[account("only0", None)], "CloseDca").unwrap_err();
-        let Error::Protocol { reason } = err else {
+        let Error::Protocol { reason, .. } = err else {
             panic!("expected protocol error");
         };
         assert_eq!(reason, "DCA account index 1 out of bounds for CloseDca");
     }
 
+    #[test]
+    fn parse_dlmm_fill_args_reads_repay_and_out_amounts() {
+        let accounts = vec![account("keeper", None), account("order_pda", None)];
+        let fill = DcaAdapter::parse_dlmm_fill_args(
+            &serde_json::json!({ "repay_amount": 1_000_u64, "out_amount": 37_u64 }),
+            &accounts,
+        )
+        .unwrap();
+        assert_eq!(fill.order_pda, "order_pda");
+        assert_eq!(fill.in_amount, 1_000);
+        assert_eq!(fill.out_amount, 37);
+
+        let repay_only = DcaAdapter::parse_dlmm_fill_args(
+            &serde_json::json!({ "repay_amount": 5_u64 }),
+            &accounts,
+        )
+        .unwrap();
+        assert_eq!(repay_only.in_amount, 5);
+        assert_eq!(repay_only.out_amount, 0);
+    }
+
+    #[test]
+    fn parse_dlmm_fill_args_rejects_overflow() {
+        let accounts = vec![account("keeper", None), account("order_pda", None)];
+        let err = DcaAdapter::parse_dlmm_fill_args(
+            &serde_json::json!({ "repay_amount": (i64::MAX as u64) + 1 }),
+            &accounts,
+        )
+        .unwrap_err();
+        let Error::AmountOverflow { field, value } = err else {
+            panic!("expected amount overflow error");
+        };
+        assert_eq!(field, "repay_amount");
+        assert_eq!(value, (i64::MAX as u64) + 1);
+    }
+
+    #[test]
+    fn dlmm_fill_instructions_classify_as_fills() {
+        let fulfill = RawInstructionBuilder::new("FulfillDlmmFill").build();
+        assert_eq!(
+            DcaAdapter.classify_instruction(&fulfill),
+            Some(EventType::FillCompleted)
+        );
+        let initiate = RawInstructionBuilder::new("InitiateDlmmFill").build();
+        assert_eq!(
+            DcaAdapter.classify_instruction(&initiate),
+            Some(EventType::FillInitiated)
+        );
+    }
+
+    #[test]
+    fn resolve_instruction_yields_pda_and_create_args() {
+        let ix = RawInstructionBuilder::new("OpenDcaV2")
+            .accounts(serde_json::json!([
+                { "pubkey": "order_pda", "name": "dca" },
+                { "pubkey": "user", "is_signer": true, "name": "user" }
+            ]))
+            .args(serde_json::json!({
+                "in_amount": 1_000_u64,
+                "in_amount_per_cycle": 100_u64,
+                "cycle_frequency": 60_i64,
+                "min_out_amount": null,
+                "max_out_amount": null,
+                "start_at": null
+            }))
+            .build();
+
+        let outcome = DcaAdapter.resolve_instruction(&ix).unwrap().unwrap();
+        assert_eq!(outcome.event_type, EventType::Created);
+        assert_eq!(outcome.order_pda.as_deref(), Some("order_pda"));
+        let InstructionArgs::DcaCreate(args) = outcome.args else {
+            panic!("expected DCA create args");
+        };
+        assert_eq!(args.in_amount, 1_000);
+        assert_eq!(args.in_amount_per_cycle, 100);
+        assert_eq!(args.cycle_frequency, 60);
+
+        let fill = RawInstructionBuilder::new("FulfillFlashFill").build();
+        let outcome = DcaAdapter.resolve_instruction(&fill).unwrap().unwrap();
+        assert_eq!(outcome.event_type, EventType::FillCompleted);
+        assert_eq!(outcome.order_pda, None);
+        assert_eq!(outcome.args, InstructionArgs::None);
+
+        let unknown = RawInstructionBuilder::new("WithdrawFees").build();
+        assert!(DcaAdapter.resolve_instruction(&unknown).is_none());
+    }
+
     #[test]
     fn extract_create_mints_prefers_named_accounts() {
         let accounts = vec![
@@ -708,7 +1196,7 @@ mod tests {
         let err = DcaAdapter::extract_create_mints(&[], "CloseDca")
             .err()
             .expect("expected error");
-        let Error::Protocol { reason } = err else {
+        let Error::Protocol { reason, .. } = err else {
             panic!("expected protocol error");
         };
         assert_eq!(reason, "not a DCA create instruction: CloseDca");
@@ -719,7 +1207,7 @@ mod tests {
         let err = DcaAdapter::extract_create_mints(&[], "OpenDca")
             .err()
             .expect("expected error");
-        let Error::Protocol { reason } = err else {
+        let Error::Protocol { reason, .. } = err else {
             panic!("expected protocol error");
         };
         assert_eq!(reason, "DCA input_mint index 2 out of bounds");
@@ -731,7 +1219,7 @@ mod tests {
         let err = DcaAdapter::extract_create_mints(&accounts, "OpenDca")
             .err()
             .expect("expected error");
-        let Error::Protocol { reason } = err else {
+        let Error::Protocol { reason, .. } = err else {
             panic!("expected protocol error");
         };
         assert_eq!(reason, "DCA output_mint index 3 out of bounds");
@@ -759,18 +1247,7 @@ mod tests {
     #[test]
     fn instruction_constants_match_classify() {
         for (name, expected) in INSTRUCTION_EVENT_TYPES {
-            let ix = RawInstruction {
-                id: 1,
-                signature: "sig".to_string(),
-                instruction_index: 0,
-                instruction_path: None,
-                program_id: "p".to_string(),
-                inner_program_id: "p".to_string(),
-                instruction_name: name.to_string(),
-                accounts: None,
-                args: None,
-                slot: 1,
-            };
+            let ix = RawInstructionBuilder::new(*name).build();
             assert_eq!(
                 DcaAdapter.classify_instruction(&ix).as_ref(),
                 Some(expected),
@@ -0,0 +1,353 @@
+//! Property test: the JSON name-lookup path and the typed Carbon path must classify every
+//! decoder variant identically.
+//!
+//! Variants are enumerated from the decoder enums' own `Deserialize` impls, and each one is
+//! instantiated with zero values, so a variant added upstream is checked without editing
+//! this file.
+
+use serde::de::value::{Error as DeError, StrDeserializer};
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, Deserializer, EnumAccess, IntoDeserializer, MapAccess,
+    SeqAccess, VariantAccess, Visitor,
+};
+
+use crate::lifecycle::adapters::adapter_for;
+use crate::protocols::dca::{self, DcaAdapter};
+use crate::protocols::kamino::{self, KaminoAdapter};
+use crate::protocols::limit_v1::{self, LimitV1Adapter};
+use crate::protocols::limit_v2::{self, LimitV2Adapter};
+use crate::protocols::{EventType, Protocol, ProtocolHelpers, is_known_variant};
+use crate::test_util::RawInstructionBuilder;
+
+/// Captures the variant list the derived `Deserialize` passes to `deserialize_enum`.
+struct VariantNames<'a>(&'a mut &'static [&'static str]);
+
+impl<'de> Deserializer<'de> for VariantNames<'_> {
+    type Error = DeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, DeError> {
+        Err(de::Error::custom("expected an enum"))
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        variants: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, DeError> {
+        *self.0 = variants;
+        Err(de::Error::custom("variant names captured"))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        option unit unit_struct newtype_struct seq tuple tuple_struct map struct identifier
+        ignored_any
+    }
+}
+
+fn variant_names<T: DeserializeOwned>() -> &'static [&'static str] {
+    let mut names: &'static [&'static str] = &[];
+    let _ = T::deserialize(VariantNames(&mut names));
+    names
+}
+
+/// Produces the zero value of any type: `0`, `false`, `""`, `None`, empty collections, and
+/// `variant` (or the first variant) for enums.
+struct Zero {
+    variant: Option<&'static str>,
+}
+
+impl Zero {
+    const NESTED: Self = Self { variant: None };
+}
+
+struct ZeroSeq(usize);
+
+impl<'de> SeqAccess<'de> for ZeroSeq {
+    type Error = DeError;
+
+    fn next_element_seed<S: DeserializeSeed<'de>>(
+        &mut self,
+        seed: S,
+    ) -> Result<Option<S::Value>, DeError> {
+        if self.0 == 0 {
+            return Ok(None);
+        }
+        self.0 -= 1;
+        seed.deserialize(Zero::NESTED).map(Some)
+    }
+}
+
+struct EmptyMap;
+
+impl<'de> MapAccess<'de> for EmptyMap {
+    type Error = DeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        _seed: K,
+    ) -> Result<Option<K::Value>, DeError> {
+        Ok(None)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, _seed: V) -> Result<V::Value, DeError> {
+        Err(de::Error::custom("empty map has no values"))
+    }
+}
+
+struct ZeroEnum(&'static str);
+
+impl<'de> EnumAccess<'de> for ZeroEnum {
+    type Error = DeError;
+    type Variant = Self;
+
+    fn variant_seed<S: DeserializeSeed<'de>>(self, seed: S) -> Result<(S::Value, Self), DeError> {
+        let name: StrDeserializer<'_, DeError> = self.0.into_deserializer();
+        Ok((seed.deserialize(name)?, self))
+    }
+}
+
+impl<'de> VariantAccess<'de> for ZeroEnum {
+    type Error = DeError;
+
+    fn unit_variant(self) -> Result<(), DeError> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<S: DeserializeSeed<'de>>(self, seed: S) -> Result<S::Value, DeError> {
+        seed.deserialize(Zero::NESTED)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, DeError> {
+        visitor.visit_seq(ZeroSeq(len))
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, DeError> {
+        visitor.visit_seq(ZeroSeq(fields.len()))
+    }
+}
+
+impl<'de> Deserializer<'de> for Zero {
+    type Error = DeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        visitor.visit_u64(0)
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        visitor.visit_bool(false)
+    }
+
+    fn deserialize_i8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        visitor.visit_i8(0)
+    }
+
+    fn deserialize_i16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        visitor.visit_i16(0)
+    }
+
+    fn deserialize_i32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        visitor.visit_i32(0)
+    }
+
+    fn deserialize_i64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        visitor.visit_i64(0)
+    }
+
+    fn deserialize_i128<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        visitor.visit_i128(0)
+    }
+
+    fn deserialize_u8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        visitor.visit_u8(0)
+    }
+
+    fn deserialize_u16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        visitor.visit_u16(0)
+    }
+
+    fn deserialize_u32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        visitor.visit_u32(0)
+    }
+
+    fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        visitor.visit_u64(0)
+    }
+
+    fn deserialize_u128<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        visitor.visit_u128(0)
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        visitor.visit_f32(0.0)
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        visitor.visit_f64(0.0)
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        visitor.visit_char('\0')
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        visitor.visit_borrowed_str("")
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        visitor.visit_string(String::new())
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        visitor.visit_borrowed_bytes(&[])
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        visitor.visit_byte_buf(Vec::new())
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        visitor.visit_none()
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, DeError> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, DeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        visitor.visit_seq(ZeroSeq(0))
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, DeError> {
+        visitor.visit_seq(ZeroSeq(len))
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, DeError> {
+        visitor.visit_seq(ZeroSeq(len))
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        visitor.visit_map(EmptyMap)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, DeError> {
+        visitor.visit_seq(ZeroSeq(fields.len()))
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, DeError> {
+        let variant = self
+            .variant
+            .or_else(|| variants.first().copied())
+            .ok_or_else(|| de::Error::custom("enum has no variants"))?;
+        visitor.visit_enum(ZeroEnum(variant))
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        visitor.visit_borrowed_str("")
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        visitor.visit_unit()
+    }
+}
+
+/// For every variant of the decoder enum `T`, asserts that classifying its name through the
+/// JSON path agrees with `classify_decoded` on a zero-valued instance.
+///
+/// Names in the protocol's event envelope are looked up in `event_types`, the rest go through
+/// [`crate::ProtocolAdapter::classify_instruction`].
+fn assert_paths_agree<T: DeserializeOwned + serde::Serialize>(
+    protocol: Protocol,
+    event_types: &[(&'static str, EventType)],
+    classify_decoded: fn(&T) -> Option<EventType>,
+) {
+    let names = variant_names::<T>();
+    assert!(!names.is_empty(), "{protocol:?}: no decoder variants found");
+
+    for &name in names {
+        let decoded = T::deserialize(Zero {
+            variant: Some(name),
+        })
+        .unwrap_or_else(|e| panic!("{protocol:?}: cannot build zero {name}: {e}"));
+        let tagged = serde_json::to_value(&decoded).unwrap();
+        assert!(
+            tagged.get(name).is_some(),
+            "{protocol:?}: built {tagged} for {name}"
+        );
+
+        let json_path = if is_known_variant(protocol, name) {
+            ProtocolHelpers::lookup_event_type(name, event_types)
+        } else {
+            adapter_for(protocol).classify_instruction(&RawInstructionBuilder::new(name).build())
+        };
+        let typed_path = classify_decoded(&decoded);
+        assert_eq!(
+            json_path, typed_path,
+            "{protocol:?}: JSON and Carbon paths disagree on {name}"
+        );
+    }
+}
+
+#[test]
+fn json_and_carbon_classification_agree_for_every_decoder_variant() {
+    assert_paths_agree(
+        Protocol::Dca,
+        dca::EVENT_EVENT_TYPES,
+        DcaAdapter::classify_decoded,
+    );
+    assert_paths_agree(
+        Protocol::LimitV1,
+        limit_v1::EVENT_EVENT_TYPES,
+        LimitV1Adapter::classify_decoded,
+    );
+    assert_paths_agree(
+        Protocol::LimitV2,
+        limit_v2::EVENT_EVENT_TYPES,
+        LimitV2Adapter::classify_decoded,
+    );
+    assert_paths_agree(
+        Protocol::Kamino,
+        kamino::EVENT_EVENT_TYPES,
+        KaminoAdapter::classify_decoded,
+    );
+}
@@ -1,10 +1,16 @@
-use crate::error::Error;
+use std::borrow::Borrow;
+
+use crate::error::{Error, ErrorCode};
+use crate::lifecycle::TerminalStatus;
 use crate::lifecycle::adapters::{
-    CorrelationOutcome, EventPayload, ProtocolAdapter, kamino_display_terminal_status,
+    ContextRequirements, CorrelationOutcome, EventPayload, InstructionArgs, InstructionOutcome,
+    ProtocolAdapter, ResolvedEvent, correlate_via_resolution, kamino_display_terminal_status_with,
+};
+use crate::protocols::{
+    AccountInfo, AmountCaster, EventType, InstructionCategory, KAMINO_PROGRAM_ID, Protocol,
+    ProtocolHelpers,
 };
-use crate::protocols::{AccountInfo, EventType, Protocol, ProtocolHelpers};
 use crate::types::{RawEvent, RawInstruction, ResolveContext};
-use strum::VariantNames;
 
 /// Serde-tagged envelope for Kamino Limit Order event variants.
 #[derive(serde::Deserialize, strum_macros::VariantNames)]
@@ -14,7 +20,7 @@ pub enum KaminoEventEnvelope {
 }
 
 /// Serde-tagged envelope for Kamino Limit Order instruction variants.
-#[derive(serde::Deserialize)]
+#[derive(serde::Deserialize, strum_macros::VariantNames)]
 pub enum KaminoInstructionKind {
     CreateOrder(serde_json::Value),
     TakeOrder(serde_json::Value),
@@ -35,15 +41,55 @@ pub const INSTRUCTION_EVENT_TYPES: &[(&str, EventType)] = &[
     ("FlashTakeOrderStart", EventType::FillInitiated),
     ("FlashTakeOrderEnd", EventType::FillCompleted),
     ("CloseOrderAndClaimTip", EventType::Closed),
+    ("WithdrawHostTip", EventType::FeeCollected),
+    ("LogUserSwapBalances", EventType::Diagnostic),
+];
+
+/// Known instructions outside [`INSTRUCTION_EVENT_TYPES`], with their [`InstructionCategory`].
+pub const INSTRUCTION_CATEGORIES: &[(&str, InstructionCategory)] = &[
+    ("InitializeGlobalConfig", InstructionCategory::Config),
+    ("InitializeVault", InstructionCategory::Config),
+    ("UpdateGlobalConfig", InstructionCategory::Config),
+    ("UpdateGlobalConfigAdmin", InstructionCategory::Config),
 ];
 
 pub const EVENT_EVENT_TYPES: &[(&str, EventType)] = &[
     ("OrderDisplayEvent", EventType::FillCompleted),
-    ("UserSwapBalancesEvent", EventType::FillCompleted),
+    ("UserSwapBalancesEvent", EventType::Diagnostic),
+];
+
+/// [`crate::EventPayload`] variant each event in [`EVENT_EVENT_TYPES`] resolves to.
+/// An uncorrelated `OrderDisplayEvent` yields `None`.
+pub const EVENT_PAYLOAD_KINDS: &[(&str, &str)] = &[
+    ("OrderDisplayEvent", "KaminoDisplay"),
+    ("UserSwapBalancesEvent", "None"),
+];
+
+/// Event variants whose [`EventType`] depends on [`ResolveContext`], with the alternative type.
+///
+/// A Kamino `OrderDisplayEvent` takes its terminal status from its payload, not its type, so no
+/// event changes type with the context.
+pub const CONTEXT_EVENT_TYPES: &[(&str, EventType)] = &[];
+
+/// Every event type the adapter can produce: the union of [`INSTRUCTION_EVENT_TYPES`],
+/// [`EVENT_EVENT_TYPES`] and the context-derived [`CONTEXT_EVENT_TYPES`].
+pub const POSSIBLE_EVENT_TYPES: &[EventType] = &[
+    EventType::Created,
+    EventType::FillInitiated,
+    EventType::FillCompleted,
+    EventType::Closed,
+    EventType::FeeCollected,
+    EventType::Diagnostic,
 ];
 
 pub const CLOSED_VARIANTS: &[&str] = &["Completed", "Cancelled", "Expired"];
 
+/// [`KaminoEventEnvelope`] variant names as a compile-time set (see [`crate::protocols::is_known_variant`]).
+pub static EVENT_VARIANTS: phf::Set<&'static str> = phf::phf_set! {
+    "OrderDisplayEvent",
+    "UserSwapBalancesEvent",
+};
+
 /// Kamino Limit Order protocol adapter (zero-sized, stored as a static).
 #[derive(Debug)]
 pub struct KaminoAdapter;
@@ -53,14 +99,17 @@ pub struct KaminoAdapter;
 /// This event carries no order PDA. Callers should use path-aware prefetch when available and then pass the resolved PDA(s) through [`ResolveContext::pre_fetched_order_pdas`].
 #[derive(serde::Deserialize)]
 pub struct OrderDisplayEventFields {
-    #[serde(default)]
+    #[serde(default, deserialize_with = "crate::protocols::de_u64_flexible")]
     pub remaining_input_amount: u64,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "crate::protocols::de_u64_flexible")]
     pub filled_output_amount: u64,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "crate::protocols::de_u64_flexible")]
     pub number_of_fills: u64,
     #[serde(default)]
     pub status: u8,
+    /// Order PDA, present in newer payloads; makes the event self-correlating.
+    #[serde(default)]
+    pub order: Option<String>,
 }
 
 /// Extracted Kamino order display event with checked-cast amounts.
@@ -84,7 +133,20 @@ pub enum KaminoDisplayStatus {
     Expired,
 }
 
+impl From<KaminoDisplayStatus> for Option<TerminalStatus> {
+    /// `Open` → `None`; `Filled`/`Cancelled`/`Expired` → `Completed`/`Cancelled`/`Expired`.
+    fn from(status: KaminoDisplayStatus) -> Self {
+        match status {
+            KaminoDisplayStatus::Open => None,
+            KaminoDisplayStatus::Filled => Some(TerminalStatus::Completed),
+            KaminoDisplayStatus::Cancelled => Some(TerminalStatus::Cancelled),
+            KaminoDisplayStatus::Expired => Some(TerminalStatus::Expired),
+        }
+    }
+}
+
 /// Parsed arguments from a Kamino `CreateOrder` instruction.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct KaminoCreateArgs {
     pub input_amount: i64,
     pub output_amount: i64,
@@ -97,9 +159,72 @@ pub struct KaminoCreateMints {
     pub output_mint: String,
 }
 
+/// A jump in Kamino's `number_of_fills` counter between consecutive display snapshots.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FillGap {
+    /// Count the next snapshot should have carried (previous + 1).
+    pub expected: i64,
+    /// Count the snapshot actually carried.
+    pub actual: i64,
+}
+
+impl FillGap {
+    /// Number of fill events that were skipped.
+    pub fn missed(&self) -> i64 {
+        self.actual.saturating_sub(self.expected)
+    }
+}
+
+/// Tracks the `number_of_fills` counter across one order's `OrderDisplayEvent` snapshots.
+///
+/// The first snapshot only sets the baseline; stale or repeated counts never move it backwards.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct KaminoSnapshotAccumulator {
+    last_number_of_fills: Option<i64>,
+}
+
+impl KaminoSnapshotAccumulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Highest `number_of_fills` observed so far.
+    pub fn last_number_of_fills(&self) -> Option<i64> {
+        self.last_number_of_fills
+    }
+
+    /// Records `new_number_of_fills` and returns a [`FillGap`] when it jumped by more than one.
+    pub fn detect_gap(&mut self, new_number_of_fills: i64) -> Option<FillGap> {
+        let previous = self.last_number_of_fills;
+        self.last_number_of_fills =
+            Some(previous.map_or(new_number_of_fills, |prev| prev.max(new_number_of_fills)));
+        let expected = previous?.saturating_add(1);
+        (new_number_of_fills > expected).then_some(FillGap {
+            expected,
+            actual: new_number_of_fills,
+        })
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct HostTipFields {
+    #[serde(deserialize_with = "crate::protocols::de_u64_flexible")]
+    amount: u64,
+}
+
+#[derive(serde::Deserialize)]
+struct SwapBalancesFields {
+    #[serde(deserialize_with = "crate::protocols::de_u64_flexible")]
+    pre: u64,
+    #[serde(deserialize_with = "crate::protocols::de_u64_flexible")]
+    post: u64,
+}
+
 #[derive(serde::Deserialize)]
 struct CreateOrderFields {
+    #[serde(deserialize_with = "crate::protocols::de_u64_flexible")]
     input_amount: u64,
+    #[serde(deserialize_with = "crate::protocols::de_u64_flexible")]
     output_amount: u64,
     #[serde(default)]
     order_type: u8,
@@ -111,88 +236,315 @@ impl ProtocolAdapter for KaminoAdapter {
     }
 
     fn classify_instruction(&self, ix: &RawInstruction) -> Option<EventType> {
-        ProtocolHelpers::lookup_event_type(&ix.instruction_name, INSTRUCTION_EVENT_TYPES)
+        self.classify_instruction_category(ix).event_type()
+    }
+
+    fn classify_instruction_category(&self, ix: &RawInstruction) -> InstructionCategory {
+        ProtocolHelpers::lookup_instruction_category(
+            &ix.instruction_name,
+            INSTRUCTION_EVENT_TYPES,
+            INSTRUCTION_CATEGORIES,
+        )
+    }
+
+    /// `CloseOrderAndClaimTip` has no args, so a close resolves with [`InstructionArgs::None`];
+    /// its terminal status comes from the paired `OrderDisplayEvent` (see
+    /// [`Self::parse_close_args`]).
+    fn resolve_instruction(
+        &self,
+        ix: &RawInstruction,
+    ) -> Option<Result<InstructionOutcome, Error>> {
+        let event_type = self.classify_instruction(ix)?;
+        if event_type == EventType::Diagnostic {
+            return Some(Ok(InstructionOutcome {
+                event_type,
+                order_pda: None,
+                args: InstructionArgs::None,
+            }));
+        }
+        if event_type == EventType::FeeCollected {
+            return Some(
+                Self::host_tip_order_pda(ix).map(|order_pda| InstructionOutcome {
+                    event_type,
+                    order_pda,
+                    args: InstructionArgs::None,
+                }),
+            );
+        }
+        Some(ProtocolHelpers::resolve_classified_instruction(
+            ix,
+            event_type,
+            Self::extract_order_pda,
+            |args| Self::parse_create_args(args).map(InstructionArgs::KaminoCreate),
+        ))
+    }
+
+    /// `OrderDisplayEvent` carries no order key; it correlates via
+    /// [`ResolveContext::pre_fetched_order_pdas`].
+    fn required_context(&self) -> ContextRequirements {
+        ContextRequirements { order_pdas: true }
+    }
+
+    fn collect_order_pdas(&self, instructions: &[RawInstruction]) -> Vec<String> {
+        Self::collect_order_pdas(instructions)
+    }
+
+    fn resolve_transaction_events(
+        &self,
+        events: &[&RawEvent],
+        ctx: &ResolveContext,
+    ) -> Vec<Option<Result<ResolvedEvent, Error>>> {
+        Self::resolve_transaction_events(events, ctx)
     }
 
     fn classify_and_resolve_event(
         &self,
         ev: &RawEvent,
         ctx: &ResolveContext,
-    ) -> Option<Result<(EventType, CorrelationOutcome, EventPayload), Error>> {
-        let fields = ev.fields.as_ref()?;
+    ) -> Option<Result<ResolvedEvent, Error>> {
+        let fields = ProtocolHelpers::wrapped_event_fields(ev, &EVENT_VARIANTS)?;
+        Self::resolve_fields_from(&fields, Some(ev), ctx)
+    }
+
+    /// `OrderDisplayEvent` correlation without parsing amounts or status; see
+    /// [`KaminoAdapter::resolve_transaction_events`] for multi-order transactions.
+    fn correlate(&self, ev: &RawEvent, ctx: &ResolveContext) -> CorrelationOutcome {
+        let fields = ProtocolHelpers::wrapped_event_fields(ev, &EVENT_VARIANTS);
+        let Some(display) = fields.as_deref().and_then(|f| f.get("OrderDisplayEvent")) else {
+            return correlate_via_resolution(self, ev, ctx);
+        };
+        Self::correlate_display(
+            display.get("order").and_then(serde_json::Value::as_str),
+            Some(ev),
+            ctx,
+        )
+    }
+}
+
+impl KaminoAdapter {
+    /// Classifies and resolves a bare event `fields` payload (see [`crate::resolve_event_value`]).
+    ///
+    /// Without the originating [`RawEvent`], uncorrelated reasons omit the signature/path.
+    pub fn resolve_fields(
+        fields: &serde_json::Value,
+        ctx: &ResolveContext,
+    ) -> Option<Result<ResolvedEvent, Error>> {
+        Self::resolve_fields_from(fields, None, ctx)
+    }
+
+    /// Like [`ProtocolAdapter::classify_and_resolve_event`], but rejects an `OrderDisplayEvent`
+    /// whose status is `Filled` while `remaining_input_amount` is still nonzero.
+    ///
+    /// Intended for diagnostics: the contradiction points at a decoder or data bug.
+    pub fn resolve_event_strict(
+        &self,
+        ev: &RawEvent,
+        ctx: &ResolveContext,
+    ) -> Option<Result<ResolvedEvent, Error>> {
+        let fields = ProtocolHelpers::wrapped_event_fields(ev, &EVENT_VARIANTS)?;
+        if let Some(Ok(display)) = fields
+            .get("OrderDisplayEvent")
+            .map(|d| serde_json::from_value::<OrderDisplayEventFields>(d.clone()))
+            && let Err(err) = Self::check_display_consistency(&display)
+        {
+            return Some(Err(err));
+        }
+        self.classify_and_resolve_event(ev, ctx)
+    }
+
+    fn check_display_consistency(display: &OrderDisplayEventFields) -> Result<(), Error> {
+        let filled = matches!(
+            Self::parse_display_status(i64::from(display.status)),
+            Ok(KaminoDisplayStatus::Filled)
+        );
+        if filled && display.remaining_input_amount > 0 {
+            return Err(Error::Protocol {
+                code: ErrorCode::MalformedPayload,
+                reason: format!(
+                    "Kamino OrderDisplayEvent is Filled but remaining_input_amount is {}",
+                    display.remaining_input_amount
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    /// Order PDAs of a transaction's Kamino instructions, in first-seen order without
+    /// duplicates; the input for [`ResolveContext::pre_fetched_order_pdas`].
+    ///
+    /// Instructions of other programs, and those whose PDA cannot be extracted, are skipped.
+    pub fn collect_order_pdas(instructions: &[RawInstruction]) -> Vec<String> {
+        let mut pdas: Vec<String> = Vec::new();
+        for ix in instructions {
+            if ix.inner_program_id != KAMINO_PROGRAM_ID {
+                continue;
+            }
+            if let Some(accounts_json) = ix.accounts.as_ref()
+                && let Ok(accounts) = ProtocolHelpers::parse_accounts(accounts_json)
+                && let Ok(pda) = Self::extract_order_pda(&accounts, &ix.instruction_name)
+                && !pdas.contains(&pda)
+            {
+                pdas.push(pda);
+            }
+        }
+        pdas
+    }
+
+    /// Resolves every event of one transaction, narrowing each `OrderDisplayEvent` to a single
+    /// pre-fetched PDA where possible. Results align with `events`.
+    ///
+    /// Heuristic: a display event's own `order` field wins and claims that PDA. The remaining
+    /// PDAs are taken to be in instruction order, and display events are emitted in the same
+    /// order, so when the counts match the n-th order-less display event by `event_index` gets
+    /// the n-th remaining PDA. On a count mismatch each event falls back to
+    /// [`ProtocolAdapter::classify_and_resolve_event`] with the full `ctx`.
+    ///
+    /// Accepts owned or borrowed events, so callers can pass a filtered `&[&RawEvent]`.
+    pub fn resolve_transaction_events<E: Borrow<RawEvent>>(
+        events: &[E],
+        ctx: &ResolveContext,
+    ) -> Vec<Option<Result<ResolvedEvent, Error>>> {
+        let mut claimed = Vec::new();
+        let mut orderless = Vec::new();
+        for (position, ev) in events.iter().map(Borrow::borrow).enumerate() {
+            let fields = ProtocolHelpers::wrapped_event_fields(ev, &EVENT_VARIANTS);
+            let Some(display) = fields.as_deref().and_then(|f| f.get("OrderDisplayEvent")) else {
+                continue;
+            };
+            match display.get("order").and_then(serde_json::Value::as_str) {
+                Some(order) => claimed.push(order.to_string()),
+                None => orderless.push(position),
+            }
+        }
+        orderless.sort_by_key(|&position| events[position].borrow().event_index);
+
+        let unclaimed: Vec<&String> = ctx
+            .pre_fetched_order_pdas
+            .iter()
+            .flatten()
+            .filter(|pda| !claimed.contains(pda))
+            .collect();
+        let mut narrowed: Vec<Option<ResolveContext>> = vec![None; events.len()];
+        if orderless.len() == unclaimed.len() {
+            for (&position, pda) in orderless.iter().zip(unclaimed) {
+                narrowed[position] = Some(ResolveContext {
+                    pre_fetched_order_pdas: Some(vec![pda.clone()]),
+                    ..ctx.clone()
+                });
+            }
+        }
+
+        events
+            .iter()
+            .zip(narrowed)
+            .map(|(ev, narrowed)| {
+                Self.classify_and_resolve_event(ev.borrow(), narrowed.as_ref().unwrap_or(ctx))
+            })
+            .collect()
+    }
+
+    fn resolve_fields_from(
+        fields: &serde_json::Value,
+        origin: Option<&RawEvent>,
+        ctx: &ResolveContext,
+    ) -> Option<Result<ResolvedEvent, Error>> {
         let envelope: KaminoEventEnvelope = match serde_json::from_value(fields.clone()) {
             Ok(e) => e,
             Err(err) => {
-                if !ProtocolHelpers::contains_known_variant(fields, KaminoEventEnvelope::VARIANTS) {
+                if !ProtocolHelpers::contains_known_variant(fields, &EVENT_VARIANTS) {
                     return None;
                 }
                 return Some(Err(Error::Protocol {
+                    code: ErrorCode::MalformedPayload,
                     reason: format!("failed to parse Kamino event payload: {err}"),
                 }));
             }
         };
 
-        Some(Self::resolve_event(envelope, ev, ctx))
+        Some(Self::resolve_event(envelope, origin, ctx))
     }
-}
 
-impl KaminoAdapter {
     fn resolve_event(
         envelope: KaminoEventEnvelope,
-        ev: &RawEvent,
+        origin: Option<&RawEvent>,
         ctx: &ResolveContext,
     ) -> Result<(EventType, CorrelationOutcome, EventPayload), Error> {
         match envelope {
             KaminoEventEnvelope::UserSwapBalancesEvent(_) => Ok((
-                EventType::FillCompleted,
+                EventType::Diagnostic,
                 CorrelationOutcome::NotRequired,
                 EventPayload::None,
             )),
             KaminoEventEnvelope::OrderDisplayEvent(display_fields) => {
-                let order_pdas = ctx.pre_fetched_order_pdas.clone().unwrap_or_default();
-
-                if order_pdas.is_empty() {
-                    let correlation_target = ev.parent_instruction_path().map_or_else(
-                        || format!("signature {}", ev.signature),
-                        |instruction_path| {
-                            format!(
-                                "signature {} and instruction_path {}",
-                                ev.signature, instruction_path
-                            )
-                        },
-                    );
-                    return Ok((
-                        EventType::FillCompleted,
-                        CorrelationOutcome::Uncorrelated {
-                            reason: format!(
-                                "cannot correlate Kamino OrderDisplayEvent for {correlation_target}"
-                            ),
-                        },
-                        EventPayload::None,
-                    ));
+                let correlation =
+                    Self::correlate_display(display_fields.order.as_deref(), origin, ctx);
+                if matches!(correlation, CorrelationOutcome::Uncorrelated { .. }) {
+                    return Ok((EventType::FillCompleted, correlation, EventPayload::None));
                 }
 
-                let terminal_status =
-                    kamino_display_terminal_status(i64::from(display_fields.status))?;
+                let terminal_status = kamino_display_terminal_status_with(
+                    i64::from(display_fields.status),
+                    display_fields.remaining_input_amount,
+                    ctx.filled_is_terminal(),
+                )?;
+                let mut amounts = AmountCaster::new(ctx);
                 Ok((
                     EventType::FillCompleted,
-                    CorrelationOutcome::Correlated(order_pdas),
+                    correlation,
                     EventPayload::KaminoDisplay {
-                        remaining_input_amount: ProtocolHelpers::checked_u64_to_i64(
+                        remaining_input_amount: amounts.cast(
                             display_fields.remaining_input_amount,
                             "remaining_input_amount",
                         )?,
-                        filled_output_amount: ProtocolHelpers::checked_u64_to_i64(
-                            display_fields.filled_output_amount,
-                            "filled_output_amount",
-                        )?,
+                        filled_output_amount: amounts
+                            .cast(display_fields.filled_output_amount, "filled_output_amount")?,
                         terminal_status,
+                        saturated: amounts.saturated(),
                     },
                 ))
             }
         }
     }
 
+    /// Correlates an `OrderDisplayEvent`: its own `order` field wins, otherwise the
+    /// pre-fetched instruction PDAs are used, up to [`ResolveContext::correlated_pda_cap`].
+    fn correlate_display(
+        order: Option<&str>,
+        origin: Option<&RawEvent>,
+        ctx: &ResolveContext,
+    ) -> CorrelationOutcome {
+        if let Some(order) = order {
+            return CorrelationOutcome::Correlated(vec![order.to_string()]);
+        }
+        // `None` means the caller never looked; `Some(empty)` means it looked and found none.
+        let cap = ctx.correlated_pda_cap();
+        let cause = match ctx.pre_fetched_order_pdas.as_deref() {
+            None => "pdas not looked up".to_string(),
+            Some([]) => "no order pdas in transaction".to_string(),
+            Some(pdas) if pdas.len() > cap => format!(
+                "ambiguous order pdas: {} exceed max_correlated_pdas {cap}",
+                pdas.len()
+            ),
+            Some(pdas) => return CorrelationOutcome::Correlated(pdas.to_vec()),
+        };
+        let correlation_target = origin.map_or_else(String::new, |ev| {
+            ev.parent_instruction_path().map_or_else(
+                || format!(" for signature {}", ev.signature),
+                |instruction_path| {
+                    format!(
+                        " for signature {} and instruction_path {}",
+                        ev.signature, instruction_path
+                    )
+                },
+            )
+        });
+        CorrelationOutcome::Uncorrelated {
+            reason: format!(
+                "cannot correlate Kamino OrderDisplayEvent{correlation_target}: {cause}"
+            ),
+        }
+    }
+
     /// Extracts the order PDA from instruction accounts.
     ///
     /// Prefers the named `"order"` account; falls back to positional index per instruction variant.
@@ -204,9 +556,24 @@ impl KaminoAdapter {
             return Ok(acc.pubkey.clone());
         }
 
+        let idx = Self::order_pda_index(instruction_name)?;
+
+        accounts
+            .get(idx)
+            .map(|a| a.pubkey.clone())
+            .ok_or_else(|| Error::Protocol {
+                code: ErrorCode::OutOfBoundsAccount,
+                reason: format!("Kamino account index {idx} out of bounds for {instruction_name}"),
+            })
+    }
+
+    /// Positional index of the `"order"` account in `instruction_name`, used when the account
+    /// is not named.
+    pub fn order_pda_index(instruction_name: &str) -> Result<usize, Error> {
         let wrapper = serde_json::json!({ instruction_name: serde_json::Value::Null });
         let kind: KaminoInstructionKind =
             serde_json::from_value(wrapper).map_err(|_| Error::Protocol {
+                code: ErrorCode::UnknownInstruction,
                 reason: format!("unknown Kamino instruction: {instruction_name}"),
             })?;
 
@@ -223,53 +590,31 @@ impl KaminoAdapter {
             | KaminoInstructionKind::WithdrawHostTip(_)
             | KaminoInstructionKind::LogUserSwapBalances(_) => {
                 return Err(Error::Protocol {
+                    code: ErrorCode::UnsupportedInstruction,
                     reason: format!("Kamino instruction {instruction_name} has no order PDA"),
                 });
             }
         };
-
-        accounts
-            .get(idx)
-            .map(|a| a.pubkey.clone())
-            .ok_or_else(|| Error::Protocol {
-                reason: format!("Kamino account index {idx} out of bounds for {instruction_name}"),
-            })
+        Ok(idx)
     }
 
     /// Extracts input/output mint addresses from a Kamino create instruction's accounts.
     ///
-    /// Prefers named accounts; falls back to positional indexes 4 (input) and 5 (output).
+    /// Prefers each named account independently; falls back to positional indexes 4 (input) and 5 (output).
     pub fn extract_create_mints(accounts: &[AccountInfo]) -> Result<KaminoCreateMints, Error> {
-        let by_name_input =
-            ProtocolHelpers::find_account_by_name(accounts, "input_mint").map(|a| a.pubkey.clone());
-        let by_name_output = ProtocolHelpers::find_account_by_name(accounts, "output_mint")
-            .map(|a| a.pubkey.clone());
-
-        if let (Some(input_mint), Some(output_mint)) = (by_name_input, by_name_output) {
-            return Ok(KaminoCreateMints {
-                input_mint,
-                output_mint,
-            });
-        }
-
-        let input_mint =
-            accounts
-                .get(4)
-                .map(|a| a.pubkey.clone())
-                .ok_or_else(|| Error::Protocol {
-                    reason: "Kamino input_mint index 4 out of bounds".into(),
-                })?;
-        let output_mint =
-            accounts
-                .get(5)
-                .map(|a| a.pubkey.clone())
-                .ok_or_else(|| Error::Protocol {
-                    reason: "Kamino output_mint index 5 out of bounds".into(),
-                })?;
-
         Ok(KaminoCreateMints {
-            input_mint,
-            output_mint,
+            input_mint: ProtocolHelpers::named_or_positional_account(
+                accounts,
+                "input_mint",
+                4,
+                "Kamino",
+            )?,
+            output_mint: ProtocolHelpers::named_or_positional_account(
+                accounts,
+                "output_mint",
+                5,
+                "Kamino",
+            )?,
         })
     }
 
@@ -279,9 +624,7 @@ impl KaminoAdapter {
             input_amount,
             output_amount,
             order_type,
-        } = serde_json::from_value(args.clone()).map_err(|e| Error::Protocol {
-            reason: format!("failed to parse Kamino create args: {e}"),
-        })?;
+        } = ProtocolHelpers::parse_create_fields(args, "Kamino")?;
 
         Ok(KaminoCreateArgs {
             input_amount: ProtocolHelpers::checked_u64_to_i64(input_amount, "input_amount")?,
@@ -290,6 +633,80 @@ impl KaminoAdapter {
         })
     }
 
+    /// [`TerminalStatus`] of a `CloseOrderAndClaimTip`, from its args.
+    ///
+    /// Always `None`: the on-chain instruction carries no args, so a Kamino close is not
+    /// terminal from instruction data alone and must take its status from the paired
+    /// `OrderDisplayEvent` (see [`kamino_display_terminal_status`]).
+    ///
+    /// [`kamino_display_terminal_status`]: crate::kamino_display_terminal_status
+    pub fn parse_close_args(_args: &serde_json::Value) -> Result<Option<TerminalStatus>, Error> {
+        Ok(None)
+    }
+
+    /// Resolves a `WithdrawHostTip` instruction into a [`EventPayload::HostTip`] fee signal.
+    ///
+    /// The on-chain instruction carries no args, so `amount` must be supplied by the caller's
+    /// decoder (e.g. from the transferred lamports). Correlates to the named `"order"` account
+    /// when present; host tips are otherwise config-level and need no correlation.
+    pub fn resolve_host_tip(ix: &RawInstruction) -> Result<ResolvedEvent, Error> {
+        let args = ix.args.as_ref().ok_or_else(|| Error::Protocol {
+            code: ErrorCode::MalformedPayload,
+            reason: "Kamino WithdrawHostTip instruction has no args".to_string(),
+        })?;
+        let HostTipFields { amount } =
+            serde_json::from_value(args.clone()).map_err(|e| Error::Protocol {
+                code: ErrorCode::MalformedPayload,
+                reason: format!("failed to parse Kamino host tip args: {e}"),
+            })?;
+        let amount = ProtocolHelpers::checked_u64_to_i64(amount, "amount")?;
+
+        let correlation = match Self::host_tip_order_pda(ix)? {
+            Some(order_pda) => CorrelationOutcome::Correlated(vec![order_pda]),
+            None => CorrelationOutcome::NotRequired,
+        };
+        Ok((
+            EventType::FeeCollected,
+            correlation,
+            EventPayload::HostTip { amount },
+        ))
+    }
+
+    /// Resolves a `LogUserSwapBalances` instruction into an [`EventPayload::SwapBalances`]
+    /// diagnostic.
+    ///
+    /// Like [`Self::resolve_host_tip`], the on-chain instruction carries no args: the caller's
+    /// decoder supplies `pre`/`post` (e.g. from the surrounding `UserSwapBalancesEvent`s). The
+    /// log is user-level, so no order correlation is required.
+    pub fn resolve_swap_balances(ix: &RawInstruction) -> Result<ResolvedEvent, Error> {
+        let args = ix.args.as_ref().ok_or_else(|| Error::Protocol {
+            code: ErrorCode::MalformedPayload,
+            reason: "Kamino LogUserSwapBalances instruction has no args".to_string(),
+        })?;
+        let SwapBalancesFields { pre, post } =
+            serde_json::from_value(args.clone()).map_err(|e| Error::Protocol {
+                code: ErrorCode::MalformedPayload,
+                reason: format!("failed to parse Kamino swap balances args: {e}"),
+            })?;
+
+        Ok((
+            EventType::Diagnostic,
+            CorrelationOutcome::NotRequired,
+            EventPayload::SwapBalances {
+                pre: ProtocolHelpers::checked_u64_to_i64(pre, "pre")?,
+                post: ProtocolHelpers::checked_u64_to_i64(post, "post")?,
+            },
+        ))
+    }
+
+    fn host_tip_order_pda(ix: &RawInstruction) -> Result<Option<String>, Error> {
+        let Some(accounts_json) = ix.accounts.as_ref() else {
+            return Ok(None);
+        };
+        let accounts = ProtocolHelpers::parse_accounts(accounts_json)?;
+        Ok(ProtocolHelpers::find_account_by_name(&accounts, "order").map(|a| a.pubkey.clone()))
+    }
+
     /// Converts a numeric status code to a [`KaminoDisplayStatus`].
     pub fn parse_display_status(status: i64) -> Result<KaminoDisplayStatus, Error> {
         match status {
@@ -298,6 +715,7 @@ impl KaminoAdapter {
             2 => Ok(KaminoDisplayStatus::Cancelled),
             3 => Ok(KaminoDisplayStatus::Expired),
             _ => Err(Error::Protocol {
+                code: ErrorCode::UnknownStatusCode,
                 reason: format!("unknown Kamino display status code: {status}"),
             }),
         }
@@ -315,13 +733,13 @@ impl KaminoAdapter {
             KaminoLimitOrderInstruction::FlashTakeOrderEnd(_) => Some(EventType::FillCompleted),
             KaminoLimitOrderInstruction::CloseOrderAndClaimTip(_) => Some(EventType::Closed),
             KaminoLimitOrderInstruction::OrderDisplayEvent(_) => Some(EventType::FillCompleted),
+            KaminoLimitOrderInstruction::WithdrawHostTip(_) => Some(EventType::FeeCollected),
+            KaminoLimitOrderInstruction::LogUserSwapBalances(_)
+            | KaminoLimitOrderInstruction::UserSwapBalancesEvent(_) => Some(EventType::Diagnostic),
             KaminoLimitOrderInstruction::InitializeGlobalConfig(_)
             | KaminoLimitOrderInstruction::InitializeVault(_)
             | KaminoLimitOrderInstruction::UpdateGlobalConfig(_)
-            | KaminoLimitOrderInstruction::UpdateGlobalConfigAdmin(_)
-            | KaminoLimitOrderInstruction::WithdrawHostTip(_)
-            | KaminoLimitOrderInstruction::LogUserSwapBalances(_)
-            | KaminoLimitOrderInstruction::UserSwapBalancesEvent(_) => None,
+            | KaminoLimitOrderInstruction::UpdateGlobalConfigAdmin(_) => None,
         }
     }
 }
@@ -335,6 +753,10 @@ impl KaminoAdapter {
 )]
 mod tests {
     use super::*;
+    use crate::lifecycle::replay::{LifecycleStream, Row};
+    use crate::lifecycle::{LifecycleTransition, MetadataReason, mapping};
+    use crate::test_util::{RawEventBuilder, RawInstructionBuilder};
+    use crate::types::DEFAULT_MAX_CORRELATED_PDAS;
 
     fn account(pubkey: &str, name: Option<&str>) -> AccountInfo {
         AccountInfo {
@@ -346,17 +768,10 @@ mod tests {
     }
 
     fn make_event_with_sig(fields: serde_json::Value, signature: &str) -> RawEvent {
-        RawEvent {
-            id: 1,
-            signature: signature.to_string(),
-            event_index: 0,
-            event_path: None,
-            program_id: "p".to_string(),
-            inner_program_id: "p".to_string(),
-            event_name: "test".to_string(),
-            fields: Some(fields),
-            slot: 1,
-        }
+        RawEventBuilder::default()
+            .signature(signature)
+            .fields(fields)
+            .build()
     }
 
     fn resolve(
@@ -368,6 +783,93 @@ mod tests {
         KaminoAdapter.classify_and_resolve_event(&ev, ctx)
     }
 
+    #[test]
+    fn resolve_event_strict_checks_filled_status_against_remaining_input() {
+        let display = |remaining: u64, status: u8| {
+            make_event_with_sig(
+                serde_json::json!({
+                    "OrderDisplayEvent": {
+                        "remaining_input_amount": remaining,
+                        "filled_output_amount": 100_u64,
+                        "number_of_fills": 1_u64,
+                        "status": status
+                    }
+                }),
+                "sig",
+            )
+        };
+        let ctx = ResolveContext {
+            pre_fetched_order_pdas: Some(vec!["pda1".to_string()]),
+            ..ResolveContext::default()
+        };
+
+        for (remaining, status) in [(0, 1), (5, 0), (5, 2)] {
+            let ev = display(remaining, status);
+            assert_eq!(
+                KaminoAdapter
+                    .resolve_event_strict(&ev, &ctx)
+                    .unwrap()
+                    .unwrap(),
+                KaminoAdapter
+                    .classify_and_resolve_event(&ev, &ctx)
+                    .unwrap()
+                    .unwrap(),
+                "remaining {remaining}, status {status}"
+            );
+        }
+
+        let contradictory = display(5, 1);
+        let err = KaminoAdapter
+            .resolve_event_strict(&contradictory, &ctx)
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(err.code(), crate::error::ErrorCode::MalformedPayload);
+        assert!(err.to_string().contains("remaining_input_amount is 5"));
+        assert!(
+            KaminoAdapter
+                .classify_and_resolve_event(&contradictory, &ctx)
+                .unwrap()
+                .is_ok(),
+            "lenient resolution is unchanged"
+        );
+    }
+
+    #[test]
+    fn correlate_display_event_with_and_without_context() {
+        let ev = make_event_with_sig(
+            serde_json::json!({
+                "OrderDisplayEvent": {
+                    "remaining_input_amount": 0_u64,
+                    "filled_output_amount": 100_u64,
+                    "number_of_fills": 1_u64,
+                    "status": 1_u8
+                }
+            }),
+            "sig",
+        );
+        let with_ctx = ResolveContext {
+            pre_fetched_order_pdas: Some(vec!["pda1".to_string()]),
+            ..ResolveContext::default()
+        };
+
+        assert_eq!(
+            KaminoAdapter.correlate(&ev, &with_ctx),
+            CorrelationOutcome::Correlated(vec!["pda1".to_string()])
+        );
+        let CorrelationOutcome::Uncorrelated { reason } =
+            KaminoAdapter.correlate(&ev, &ResolveContext::default())
+        else {
+            panic!("expected Uncorrelated without context");
+        };
+        assert!(reason.contains("pdas not looked up"), "{reason}");
+
+        let swap = make_event_with_sig(serde_json::json!({ "UserSwapBalancesEvent": {} }), "sig");
+        assert_eq!(
+            KaminoAdapter.correlate(&swap, &ResolveContext::default()),
+            CorrelationOutcome::NotRequired
+        );
+    }
+
     #[test]
     fn classify_known_instructions_via_envelope() {
         let cases = [
@@ -380,23 +882,12 @@ mod tests {
             ("InitializeVault", None),
             ("UpdateGlobalConfig", None),
             ("UpdateGlobalConfigAdmin", None),
-            ("WithdrawHostTip", None),
-            ("LogUserSwapBalances", None),
+            ("WithdrawHostTip", Some(EventType::FeeCollected)),
+            ("LogUserSwapBalances", Some(EventType::Diagnostic)),
             ("Unknown", None),
         ];
         for (name, expected) in cases {
-            let ix = RawInstruction {
-                id: 1,
-                signature: "sig".to_string(),
-                instruction_index: 0,
-                instruction_path: None,
-                program_id: "p".to_string(),
-                inner_program_id: "p".to_string(),
-                instruction_name: name.to_string(),
-                accounts: None,
-                args: None,
-                slot: 1,
-            };
+            let ix = RawInstructionBuilder::new(name).build();
             assert_eq!(
                 KaminoAdapter.classify_instruction(&ix),
                 expected,
@@ -417,6 +908,7 @@ mod tests {
         });
         let ctx = ResolveContext {
             pre_fetched_order_pdas: Some(vec!["pda1".to_string()]),
+            ..ResolveContext::default()
         };
         let (event_type, correlation, payload) = resolve(fields, "sig", &ctx).unwrap().unwrap();
         assert_eq!(event_type, EventType::FillCompleted);
@@ -428,15 +920,54 @@ mod tests {
             remaining_input_amount,
             filled_output_amount,
             terminal_status,
+            saturated,
         } = payload
         else {
             panic!("expected KaminoDisplay");
         };
+        assert!(!saturated);
         assert_eq!(remaining_input_amount, 0);
         assert_eq!(filled_output_amount, 11_744_711);
         assert!(terminal_status.is_some());
     }
 
+    #[test]
+    fn filled_with_remaining_follows_terminal_policy() {
+        let fields = serde_json::json!({
+            "OrderDisplayEvent": {
+                "remaining_input_amount": 500_u64,
+                "filled_output_amount": 1_000_u64,
+                "status": 1_u8
+            }
+        });
+        let terminal_status = |treat_filled_as_terminal: Option<bool>| {
+            let ctx = ResolveContext {
+                pre_fetched_order_pdas: Some(vec!["pda1".to_string()]),
+                treat_filled_as_terminal,
+                ..ResolveContext::default()
+            };
+            let (event_type, _, payload) = resolve(fields.clone(), "sig", &ctx).unwrap().unwrap();
+            assert_eq!(event_type, EventType::FillCompleted);
+            let EventPayload::KaminoDisplay {
+                terminal_status, ..
+            } = payload
+            else {
+                panic!("expected KaminoDisplay");
+            };
+            terminal_status
+        };
+
+        assert_eq!(terminal_status(None), Some(TerminalStatus::Completed));
+        assert_eq!(terminal_status(Some(true)), Some(TerminalStatus::Completed));
+        assert_eq!(terminal_status(Some(false)), None);
+
+        // A drained Filled snapshot is terminal under either policy.
+        assert_eq!(
+            kamino_display_terminal_status_with(1, 0, false).unwrap(),
+            Some(TerminalStatus::Completed)
+        );
+    }
+
     #[test]
     fn resolve_display_event_without_pdas() {
         let fields = serde_json::json!({
@@ -447,9 +978,7 @@ mod tests {
                 "status": 1_u8
             }
         });
-        let ctx = ResolveContext {
-            pre_fetched_order_pdas: None,
-        };
+        let ctx = ResolveContext::default();
         let (_, correlation, payload) = resolve(fields, "sig", &ctx).unwrap().unwrap();
         assert!(matches!(
             correlation,
@@ -458,36 +987,189 @@ mod tests {
         assert_eq!(payload, EventPayload::None);
     }
 
+    #[test]
+    fn missing_and_empty_pdas_report_distinct_reasons() {
+        let fields = serde_json::json!({
+            "OrderDisplayEvent": {
+                "remaining_input_amount": 0_u64,
+                "filled_output_amount": 100_u64,
+                "number_of_fills": 1_u64,
+                "status": 1_u8
+            }
+        });
+        let not_looked_up = ResolveContext::default();
+        let none_found = ResolveContext {
+            pre_fetched_order_pdas: Some(vec![]),
+            ..ResolveContext::default()
+        };
+
+        let (_, correlation, _) = resolve(fields.clone(), "sig", &not_looked_up)
+            .unwrap()
+            .unwrap();
+        let CorrelationOutcome::Uncorrelated { reason } = correlation else {
+            panic!("expected uncorrelated outcome");
+        };
+        assert!(reason.ends_with(": pdas not looked up"), "{reason}");
+
+        let (_, correlation, _) = resolve(fields, "sig", &none_found).unwrap().unwrap();
+        let CorrelationOutcome::Uncorrelated { reason } = correlation else {
+            panic!("expected uncorrelated outcome");
+        };
+        assert!(
+            reason.ends_with(": no order pdas in transaction"),
+            "{reason}"
+        );
+    }
+
+    #[test]
+    fn display_event_over_pda_cap_is_uncorrelated() {
+        let fields = serde_json::json!({
+            "OrderDisplayEvent": {
+                "remaining_input_amount": 0_u64,
+                "filled_output_amount": 100_u64,
+                "number_of_fills": 1_u64,
+                "status": 1_u8
+            }
+        });
+        let pdas = |count: usize| ResolveContext {
+            pre_fetched_order_pdas: Some((0..count).map(|i| format!("pda{i}")).collect()),
+            ..ResolveContext::default()
+        };
+
+        let (_, correlation, _) =
+            resolve(fields.clone(), "sig", &pdas(DEFAULT_MAX_CORRELATED_PDAS))
+                .unwrap()
+                .unwrap();
+        let CorrelationOutcome::Correlated(correlated) = correlation else {
+            panic!("expected correlated outcome at the cap");
+        };
+        assert_eq!(correlated.len(), DEFAULT_MAX_CORRELATED_PDAS);
+
+        let (_, correlation, payload) = resolve(
+            fields.clone(),
+            "sig",
+            &pdas(DEFAULT_MAX_CORRELATED_PDAS + 1),
+        )
+        .unwrap()
+        .unwrap();
+        let CorrelationOutcome::Uncorrelated { reason } = correlation else {
+            panic!("expected uncorrelated outcome over the cap");
+        };
+        assert!(
+            reason.ends_with(": ambiguous order pdas: 9 exceed max_correlated_pdas 8"),
+            "{reason}"
+        );
+        assert_eq!(payload, EventPayload::None);
+
+        let raised = ResolveContext {
+            max_correlated_pdas: Some(16),
+            ..pdas(DEFAULT_MAX_CORRELATED_PDAS + 1)
+        };
+        let (_, correlation, _) = resolve(fields, "sig", &raised).unwrap().unwrap();
+        assert!(matches!(correlation, CorrelationOutcome::Correlated(_)));
+    }
+
+    #[test]
+    fn log_user_swap_balances_resolves_to_diagnostic_balances() {
+        let ix = RawInstructionBuilder::new("LogUserSwapBalances")
+            .accounts(serde_json::json!([{ "pubkey": "maker", "is_signer": true }]))
+            .args(serde_json::json!({ "pre": 1_000_u64, "post": "1250" }))
+            .build();
+        let outcome = KaminoAdapter.resolve_instruction(&ix).unwrap().unwrap();
+        assert_eq!(outcome.event_type, EventType::Diagnostic);
+        assert_eq!(outcome.order_pda, None);
+        assert_eq!(
+            crate::lifecycle::mapping::event_type_to_transition(&outcome.event_type, None),
+            crate::lifecycle::LifecycleTransition::MetadataOnly {
+                reason: crate::lifecycle::MetadataReason::Diagnostic,
+            }
+        );
+
+        let (event_type, correlation, payload) = KaminoAdapter::resolve_swap_balances(&ix).unwrap();
+        assert_eq!(event_type, EventType::Diagnostic);
+        assert_eq!(correlation, CorrelationOutcome::NotRequired);
+        assert_eq!(
+            payload,
+            EventPayload::SwapBalances {
+                pre: 1_000,
+                post: 1_250,
+            }
+        );
+
+        let missing = RawInstructionBuilder::new("LogUserSwapBalances").build();
+        assert!(KaminoAdapter::resolve_swap_balances(&missing).is_err());
+        let partial = RawInstructionBuilder::new("LogUserSwapBalances")
+            .args(serde_json::json!({ "pre": 1_u64 }))
+            .build();
+        assert!(KaminoAdapter::resolve_swap_balances(&partial).is_err());
+    }
+
+    #[test]
+    fn resolve_host_tip_parses_amount_and_optional_order() {
+        let ix = RawInstructionBuilder::new("WithdrawHostTip")
+            .args(serde_json::json!({ "amount": 5_000_u64 }))
+            .build();
+        assert_eq!(
+            KaminoAdapter.classify_instruction(&ix),
+            Some(EventType::FeeCollected)
+        );
+        let (event_type, correlation, payload) = KaminoAdapter::resolve_host_tip(&ix).unwrap();
+        assert_eq!(event_type, EventType::FeeCollected);
+        assert_eq!(correlation, CorrelationOutcome::NotRequired);
+        assert_eq!(payload, EventPayload::HostTip { amount: 5_000 });
+
+        let with_order = RawInstructionBuilder::new("WithdrawHostTip")
+            .accounts(serde_json::json!([
+                { "pubkey": "admin", "is_signer": true, "name": "admin_authority" },
+                { "pubkey": "order_pda", "name": "order" }
+            ]))
+            .args(serde_json::json!({ "amount": 1_u64 }))
+            .build();
+        let (_, correlation, _) = KaminoAdapter::resolve_host_tip(&with_order).unwrap();
+        assert_eq!(
+            correlation,
+            CorrelationOutcome::Correlated(vec!["order_pda".to_string()])
+        );
+        let outcome = KaminoAdapter
+            .resolve_instruction(&with_order)
+            .unwrap()
+            .unwrap();
+        assert_eq!(outcome.order_pda.as_deref(), Some("order_pda"));
+    }
+
+    #[test]
+    fn resolve_host_tip_rejects_overflow_and_missing_args() {
+        let overflow = RawInstructionBuilder::new("WithdrawHostTip")
+            .args(serde_json::json!({ "amount": (i64::MAX as u64) + 1 }))
+            .build();
+        let Err(Error::AmountOverflow { field, value }) =
+            KaminoAdapter::resolve_host_tip(&overflow)
+        else {
+            panic!("expected overflow error");
+        };
+        assert_eq!(field, "amount");
+        assert_eq!(value, (i64::MAX as u64) + 1);
+
+        let bare = RawInstructionBuilder::new("WithdrawHostTip").build();
+        assert!(KaminoAdapter::resolve_host_tip(&bare).is_err());
+    }
+
     #[test]
     fn flash_take_instructions_are_classified() {
         assert_eq!(
-            KaminoAdapter.classify_instruction(&RawInstruction {
-                id: 1,
-                signature: "s".to_string(),
-                instruction_index: 0,
-                instruction_path: None,
-                program_id: "p".to_string(),
-                inner_program_id: "p".to_string(),
-                instruction_name: "FlashTakeOrderStart".to_string(),
-                accounts: None,
-                args: None,
-                slot: 1,
-            }),
+            KaminoAdapter.classify_instruction(
+                &RawInstructionBuilder::new("FlashTakeOrderStart")
+                    .signature("s")
+                    .build()
+            ),
             Some(EventType::FillInitiated)
         );
         assert_eq!(
-            KaminoAdapter.classify_instruction(&RawInstruction {
-                id: 1,
-                signature: "s".to_string(),
-                instruction_index: 0,
-                instruction_path: None,
-                program_id: "p".to_string(),
-                inner_program_id: "p".to_string(),
-                instruction_name: "FlashTakeOrderEnd".to_string(),
-                accounts: None,
-                args: None,
-                slot: 1,
-            }),
+            KaminoAdapter.classify_instruction(
+                &RawInstructionBuilder::new("FlashTakeOrderEnd")
+                    .signature("s")
+                    .build()
+            ),
             Some(EventType::FillCompleted)
         );
     }
@@ -575,10 +1257,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn instruction_categories_separate_config_from_unknown() {
+        let category = |name| {
+            KaminoAdapter.classify_instruction_category(&RawInstructionBuilder::new(name).build())
+        };
+        assert_eq!(category("InitializeVault"), InstructionCategory::Config);
+        assert_eq!(
+            category("CreateOrder"),
+            InstructionCategory::OrderLifecycle(EventType::Created)
+        );
+        assert_eq!(category("Unknown"), InstructionCategory::Unknown);
+        assert_eq!(
+            KaminoAdapter
+                .classify_instruction(&RawInstructionBuilder::new("InitializeVault").build()),
+            None
+        );
+    }
+
     #[test]
     fn extract_order_pda_rejects_unknown_instruction() {
         let err = KaminoAdapter::extract_order_pda(&[], "Unknown").unwrap_err();
-        let Error::Protocol { reason } = err else {
+        let Error::Protocol { reason, .. } = err else {
             panic!("expected protocol error");
         };
         assert_eq!(reason, "unknown Kamino instruction: Unknown");
@@ -587,7 +1287,7 @@ mod tests {
     #[test]
     fn extract_order_pda_rejects_out_of_bounds_index() {
         let err = KaminoAdapter::extract_order_pda(&[], "TakeOrder").unwrap_err();
-        let Error::Protocol { reason } = err else {
+        let Error::Protocol { reason, .. } = err else {
             panic!("expected protocol error");
         };
         assert_eq!(reason, "Kamino account index 4 out of bounds for TakeOrder");
@@ -626,7 +1326,7 @@ mod tests {
         let err = KaminoAdapter::extract_create_mints(&[])
             .err()
             .expect("expected error");
-        let Error::Protocol { reason } = err else {
+        let Error::Protocol { reason, .. } = err else {
             panic!("expected protocol error");
         };
         assert_eq!(reason, "Kamino input_mint index 4 out of bounds");
@@ -644,7 +1344,7 @@ mod tests {
         let err = KaminoAdapter::extract_create_mints(&accounts)
             .err()
             .expect("expected error");
-        let Error::Protocol { reason } = err else {
+        let Error::Protocol { reason, .. } = err else {
             panic!("expected protocol error");
         };
         assert_eq!(reason, "Kamino output_mint index 5 out of bounds");
@@ -670,6 +1370,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn display_status_converts_to_terminal_status() {
+        let cases = [
+            (KaminoDisplayStatus::Open, None),
+            (KaminoDisplayStatus::Filled, Some(TerminalStatus::Completed)),
+            (
+                KaminoDisplayStatus::Cancelled,
+                Some(TerminalStatus::Cancelled),
+            ),
+            (KaminoDisplayStatus::Expired, Some(TerminalStatus::Expired)),
+        ];
+        for (status, expected) in cases {
+            assert_eq!(
+                Option::<TerminalStatus>::from(status),
+                expected,
+                "{status:?}"
+            );
+        }
+    }
+
     #[test]
     fn rejects_unknown_display_status_codes() {
         assert!(KaminoAdapter::parse_display_status(99).is_err());
@@ -678,9 +1398,7 @@ mod tests {
     #[test]
     fn unknown_event_returns_none() {
         let fields = serde_json::json!({"UnknownEvent": {"some_field": 1}});
-        let ctx = ResolveContext {
-            pre_fetched_order_pdas: None,
-        };
+        let ctx = ResolveContext::default();
         assert!(resolve(fields, "sig", &ctx).is_none());
     }
 
@@ -696,6 +1414,7 @@ mod tests {
         });
         let ctx = ResolveContext {
             pre_fetched_order_pdas: Some(vec!["pda1".to_string()]),
+            ..ResolveContext::default()
         };
         let result = resolve(fields, "sig", &ctx).unwrap();
         assert!(result.is_err());
@@ -713,6 +1432,7 @@ mod tests {
         });
         let ctx = ResolveContext {
             pre_fetched_order_pdas: Some(vec!["pda1".to_string()]),
+            ..ResolveContext::default()
         };
         let result = resolve(fields, "sig", &ctx).unwrap();
         assert!(result.is_err());
@@ -725,11 +1445,9 @@ mod tests {
                 "some_field": 42
             }
         });
-        let ctx = ResolveContext {
-            pre_fetched_order_pdas: None,
-        };
+        let ctx = ResolveContext::default();
         let (event_type, correlation, payload) = resolve(fields, "sig", &ctx).unwrap().unwrap();
-        assert_eq!(event_type, EventType::FillCompleted);
+        assert_eq!(event_type, EventType::Diagnostic);
         assert_eq!(correlation, CorrelationOutcome::NotRequired);
         assert_eq!(payload, EventPayload::None);
     }
@@ -766,22 +1484,113 @@ mod tests {
         assert!(KaminoAdapter::parse_create_args(&args).is_err());
     }
 
+    fn close_instruction() -> RawInstruction {
+        RawInstructionBuilder::new("CloseOrderAndClaimTip")
+            .signature("close_sig")
+            .accounts(serde_json::json!([{ "pubkey": "order_pda", "name": "order" }]))
+            .slot(10)
+            .build()
+    }
+
+    #[test]
+    fn close_status_is_not_derivable_from_instruction_alone() {
+        assert_eq!(
+            KaminoAdapter::parse_close_args(&serde_json::json!({})).unwrap(),
+            None
+        );
+
+        let outcome = KaminoAdapter
+            .resolve_instruction(&close_instruction())
+            .unwrap()
+            .unwrap();
+        assert_eq!(outcome.event_type, EventType::Closed);
+        assert_eq!(outcome.order_pda.as_deref(), Some("order_pda"));
+        assert_eq!(outcome.args, InstructionArgs::None);
+
+        let rows = [Row::Instruction(close_instruction())];
+        let mut stream = LifecycleStream::new(Protocol::Kamino, rows, ResolveContext::default());
+        let (_, transition, _) = stream.next().unwrap().unwrap();
+        assert_eq!(
+            transition,
+            LifecycleTransition::MetadataOnly {
+                reason: MetadataReason::CloseWithoutStatus
+            }
+        );
+        assert_eq!(stream.order().terminal(), None);
+    }
+
+    #[test]
+    fn close_status_is_derived_from_paired_display_event() {
+        let display = RawEventBuilder::new("OrderDisplayEvent")
+            .signature("close_sig")
+            .fields(serde_json::json!({
+                "OrderDisplayEvent": {
+                    "remaining_input_amount": 400_u64,
+                    "filled_output_amount": 0_u64,
+                    "number_of_fills": 0_u64,
+                    "status": 2_u8,
+                    "order": "order_pda"
+                }
+            }))
+            .slot(10)
+            .build();
+        let (_, _, payload) = KaminoAdapter
+            .classify_and_resolve_event(&display, &ResolveContext::default())
+            .unwrap()
+            .unwrap();
+        let EventPayload::KaminoDisplay {
+            terminal_status, ..
+        } = payload
+        else {
+            panic!("expected KaminoDisplay, got {payload:?}");
+        };
+        assert_eq!(terminal_status, Some(TerminalStatus::Cancelled));
+
+        let outcome = KaminoAdapter
+            .resolve_instruction(&close_instruction())
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            mapping::event_type_to_transition(&outcome.event_type, terminal_status),
+            LifecycleTransition::Close {
+                status: TerminalStatus::Cancelled
+            }
+        );
+    }
+
+    #[test]
+    fn snapshot_accumulator_accepts_contiguous_fills() {
+        let mut acc = KaminoSnapshotAccumulator::new();
+        for count in [0, 1, 2, 2, 3] {
+            assert_eq!(acc.detect_gap(count), None);
+        }
+        assert_eq!(acc.last_number_of_fills(), Some(3));
+    }
+
+    #[test]
+    fn snapshot_accumulator_reports_gap() {
+        let mut acc = KaminoSnapshotAccumulator::new();
+        assert_eq!(acc.detect_gap(4), None);
+        let gap = acc.detect_gap(7).unwrap();
+        assert_eq!(
+            gap,
+            FillGap {
+                expected: 5,
+                actual: 7
+            }
+        );
+        assert_eq!(gap.missed(), 2);
+
+        // A stale snapshot neither alerts nor rewinds the baseline.
+        assert_eq!(acc.detect_gap(6), None);
+        assert_eq!(acc.detect_gap(8), None);
+    }
+
     #[cfg(feature = "wasm")]
     #[test]
     fn instruction_constants_match_classify() {
         for (name, expected) in INSTRUCTION_EVENT_TYPES {
-            let ix = RawInstruction {
-                id: 1,
-                signature: "sig".to_string(),
-                instruction_index: 0,
-                instruction_path: None,
-                program_id: "p".to_string(),
-                inner_program_id: "p".to_string(),
-                instruction_name: name.to_string(),
-                accounts: None,
-                args: None,
-                slot: 1,
-            };
+            let ix = RawInstructionBuilder::new(*name).build();
             assert_eq!(
                 KaminoAdapter.classify_instruction(&ix).as_ref(),
                 Some(expected),
@@ -795,6 +1604,7 @@ mod tests {
     fn event_constants_match_resolve() {
         let ctx = ResolveContext {
             pre_fetched_order_pdas: Some(vec!["pda".to_string()]),
+            ..ResolveContext::default()
         };
         for (name, expected) in EVENT_EVENT_TYPES {
             let fields = serde_json::json!({(*name): {}});
@@ -1,8 +1,12 @@
-use crate::error::Error;
-use crate::lifecycle::adapters::{CorrelationOutcome, EventPayload, ProtocolAdapter};
-use crate::protocols::{AccountInfo, EventType, Protocol, ProtocolHelpers};
+use crate::error::{Error, ErrorCode};
+use crate::lifecycle::adapters::{
+    CorrelationOutcome, EventPayload, InstructionArgs, InstructionOutcome, ProtocolAdapter,
+    ResolvedEvent, UNKNOWN_COUNTERPARTY,
+};
+use crate::protocols::{
+    AccountInfo, AmountCaster, EventType, InstructionCategory, Protocol, ProtocolHelpers,
+};
 use crate::types::{RawEvent, RawInstruction, ResolveContext};
-use strum::VariantNames;
 
 /// Serde-tagged envelope for Jupiter Limit Order v1 event variants.
 #[derive(serde::Deserialize, strum_macros::VariantNames)]
@@ -13,7 +17,7 @@ pub enum LimitV1EventEnvelope {
 }
 
 /// Serde-tagged envelope for Jupiter Limit Order v1 instruction variants.
-#[derive(serde::Deserialize)]
+#[derive(serde::Deserialize, strum_macros::VariantNames)]
 pub enum LimitV1InstructionKind {
     InitializeOrder(serde_json::Value),
     PreFlashFillOrder(serde_json::Value),
@@ -35,14 +39,59 @@ pub const INSTRUCTION_EVENT_TYPES: &[(&str, EventType)] = &[
     ("CancelExpiredOrder", EventType::Expired),
 ];
 
+/// Known instructions outside [`INSTRUCTION_EVENT_TYPES`], with their [`InstructionCategory`].
+pub const INSTRUCTION_CATEGORIES: &[(&str, InstructionCategory)] = &[
+    ("WithdrawFee", InstructionCategory::Fee),
+    ("InitFee", InstructionCategory::Config),
+    ("UpdateFee", InstructionCategory::Config),
+];
+
 pub const EVENT_EVENT_TYPES: &[(&str, EventType)] = &[
     ("CreateOrderEvent", EventType::Created),
     ("CancelOrderEvent", EventType::Cancelled),
     ("TradeEvent", EventType::FillCompleted),
 ];
 
+/// [`crate::EventPayload`] variant each event in [`EVENT_EVENT_TYPES`] resolves to.
+pub const EVENT_PAYLOAD_KINDS: &[(&str, &str)] = &[
+    ("CreateOrderEvent", "None"),
+    ("CancelOrderEvent", "None"),
+    ("TradeEvent", "LimitFill"),
+];
+
+/// Event variants whose [`EventType`] depends on [`ResolveContext`], with the alternative type.
+///
+/// Limit v1 expiry comes from the `CancelExpiredOrder` instruction, so no event changes type
+/// with the context.
+pub const CONTEXT_EVENT_TYPES: &[(&str, EventType)] = &[];
+
+/// Every event type the adapter can produce: the union of [`INSTRUCTION_EVENT_TYPES`],
+/// [`EVENT_EVENT_TYPES`] and the context-derived [`CONTEXT_EVENT_TYPES`].
+pub const POSSIBLE_EVENT_TYPES: &[EventType] = &[
+    EventType::Created,
+    EventType::FillInitiated,
+    EventType::FillCompleted,
+    EventType::Cancelled,
+    EventType::Expired,
+];
+
 pub const CLOSED_VARIANTS: &[&str] = &[];
 
+/// [`LimitV1EventEnvelope`] variant names as a compile-time set (see [`crate::protocols::is_known_variant`]).
+pub static EVENT_VARIANTS: phf::Set<&'static str> = phf::phf_set! {
+    "CreateOrderEvent",
+    "CancelOrderEvent",
+    "TradeEvent",
+};
+
+/// Limit v2 `TradeEvent` field names rejected by [`LimitV1Adapter::resolve_event_strict`].
+const V2_TRADE_FIELDS: &[&str] = &[
+    "making_amount",
+    "taking_amount",
+    "remaining_making_amount",
+    "remaining_taking_amount",
+];
+
 /// Jupiter Limit Order v1 protocol adapter (zero-sized, stored as a static).
 #[derive(Debug)]
 pub struct LimitV1Adapter;
@@ -50,25 +99,45 @@ pub struct LimitV1Adapter;
 /// Serde intermediate for events that only carry an `order_key`.
 #[derive(serde::Deserialize)]
 pub struct OrderKeyHolder {
+    #[serde(deserialize_with = "crate::protocols::de_nonempty_pubkey")]
     order_key: String,
 }
 
 /// Serde intermediate for `TradeEvent` payload fields.
 ///
 /// Uses `serde(alias)` to accept both V1 (`in_amount`) and V2 (`making_amount`) field names.
+/// [`LimitV1Adapter::resolve_event_strict`] rejects the V2 names instead.
 #[derive(serde::Deserialize)]
 pub struct TradeEventFields {
+    #[serde(deserialize_with = "crate::protocols::de_nonempty_pubkey")]
     order_key: String,
     #[serde(default = "LimitV1Adapter::default_unknown")]
     taker: String,
-    #[serde(alias = "making_amount", default)]
+    #[serde(alias = "owner", default)]
+    maker: Option<String>,
+    #[serde(
+        alias = "making_amount",
+        default,
+        deserialize_with = "crate::protocols::de_u64_flexible"
+    )]
     in_amount: u64,
-    #[serde(alias = "taking_amount", default)]
+    #[serde(
+        alias = "taking_amount",
+        default,
+        deserialize_with = "crate::protocols::de_u64_flexible"
+    )]
     out_amount: u64,
-    #[serde(alias = "remaining_making_amount", default)]
+    #[serde(
+        alias = "remaining_making_amount",
+        default,
+        deserialize_with = "crate::protocols::de_u64_flexible"
+    )]
     remaining_in_amount: u64,
-    #[expect(dead_code, reason = "consumed by serde for completeness")]
-    #[serde(alias = "remaining_taking_amount", default)]
+    #[serde(
+        alias = "remaining_taking_amount",
+        default,
+        deserialize_with = "crate::protocols::de_u64_flexible"
+    )]
     remaining_out_amount: u64,
 }
 
@@ -83,21 +152,57 @@ pub struct LimitTradeEvent {
 }
 
 /// Parsed arguments from an `InitializeOrder` instruction (Limit v1).
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct LimitV1CreateArgs {
     pub making_amount: i64,
     pub taking_amount: i64,
     pub expired_at: Option<i64>,
 }
 
+/// Final fill settled by a `CancelExpiredOrder` instruction (Limit v1).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LimitV1ExpirySettlement {
+    pub in_amount: i64,
+    pub out_amount: i64,
+}
+
+impl LimitV1ExpirySettlement {
+    /// The settlement as a [`EventPayload::LimitFill`], to apply as a fill before the expiry close.
+    ///
+    /// The order is expired, so `remaining_in_amount` is `0`; the taker is unknown.
+    pub fn fill_payload(&self) -> EventPayload {
+        EventPayload::LimitFill {
+            in_amount: self.in_amount,
+            out_amount: self.out_amount,
+            remaining_in_amount: 0,
+            counterparty: UNKNOWN_COUNTERPARTY.to_string(),
+            maker: None,
+            saturated: false,
+            remaining_out_amount: 0,
+            remaining_anomaly: false,
+        }
+    }
+}
+
 /// Input and output mint addresses extracted from a Limit v1 create instruction.
 pub struct LimitV1CreateMints {
     pub input_mint: String,
     pub output_mint: String,
 }
 
+#[derive(serde::Deserialize)]
+struct CancelExpiredOrderFields {
+    #[serde(default, deserialize_with = "crate::protocols::de_opt_u64_flexible")]
+    in_amount: Option<u64>,
+    #[serde(default, deserialize_with = "crate::protocols::de_opt_u64_flexible")]
+    out_amount: Option<u64>,
+}
+
 #[derive(serde::Deserialize)]
 struct InitializeOrderFields {
+    #[serde(deserialize_with = "crate::protocols::de_u64_flexible")]
     making_amount: u64,
+    #[serde(deserialize_with = "crate::protocols::de_u64_flexible")]
     taking_amount: u64,
     expired_at: Option<i64>,
 }
@@ -108,40 +213,82 @@ impl ProtocolAdapter for LimitV1Adapter {
     }
 
     fn classify_instruction(&self, ix: &RawInstruction) -> Option<EventType> {
-        ProtocolHelpers::lookup_event_type(&ix.instruction_name, INSTRUCTION_EVENT_TYPES)
+        self.classify_instruction_category(ix).event_type()
+    }
+
+    fn classify_instruction_category(&self, ix: &RawInstruction) -> InstructionCategory {
+        ProtocolHelpers::lookup_instruction_category(
+            &ix.instruction_name,
+            INSTRUCTION_EVENT_TYPES,
+            INSTRUCTION_CATEGORIES,
+        )
+    }
+
+    fn resolve_instruction(
+        &self,
+        ix: &RawInstruction,
+    ) -> Option<Result<InstructionOutcome, Error>> {
+        let event_type = self.classify_instruction(ix)?;
+        let outcome = ProtocolHelpers::resolve_classified_instruction(
+            ix,
+            event_type,
+            Self::extract_order_pda,
+            |args| Self::parse_create_args(args).map(InstructionArgs::LimitV1Create),
+        );
+        if event_type != EventType::Expired {
+            return Some(outcome);
+        }
+        Some(outcome.and_then(|mut outcome| {
+            if let Some(args) = ix.args.as_ref()
+                && let Some(settlement) = Self::parse_expiry_settlement(args)?
+            {
+                outcome.args = InstructionArgs::LimitV1ExpirySettlement(settlement);
+            }
+            Ok(outcome)
+        }))
     }
 
     fn classify_and_resolve_event(
         &self,
         ev: &RawEvent,
-        _ctx: &ResolveContext,
-    ) -> Option<Result<(EventType, CorrelationOutcome, EventPayload), Error>> {
-        let fields = ev.fields.as_ref()?;
+        ctx: &ResolveContext,
+    ) -> Option<Result<ResolvedEvent, Error>> {
+        let fields = ProtocolHelpers::wrapped_event_fields(ev, &EVENT_VARIANTS)?;
+        Self::resolve_fields(&fields, ctx)
+    }
+}
+
+impl LimitV1Adapter {
+    /// Classifies and resolves a bare event `fields` payload (see [`crate::resolve_event_value`]).
+    pub fn resolve_fields(
+        fields: &serde_json::Value,
+        ctx: &ResolveContext,
+    ) -> Option<Result<ResolvedEvent, Error>> {
         let envelope: LimitV1EventEnvelope = match serde_json::from_value(fields.clone()) {
             Ok(e) => e,
             Err(err) => {
-                if !ProtocolHelpers::contains_known_variant(fields, LimitV1EventEnvelope::VARIANTS)
-                {
+                if !ProtocolHelpers::contains_known_variant(fields, &EVENT_VARIANTS) {
                     return None;
                 }
                 return Some(Err(Error::Protocol {
+                    code: ErrorCode::MalformedPayload,
                     reason: format!("failed to parse Limit v1 event payload: {err}"),
                 }));
             }
         };
 
-        Some(Self::resolve_event(envelope))
+        Some(Self::resolve_event(envelope, ctx))
     }
-}
 
-impl LimitV1Adapter {
     fn default_unknown() -> String {
-        "unknown".to_string()
+        UNKNOWN_COUNTERPARTY.to_string()
     }
 
     fn resolve_event(
         envelope: LimitV1EventEnvelope,
+        ctx: &ResolveContext,
     ) -> Result<(EventType, CorrelationOutcome, EventPayload), Error> {
+        let mut amounts = AmountCaster::new(ctx);
         match envelope {
             LimitV1EventEnvelope::CreateOrderEvent(OrderKeyHolder { order_key }) => Ok((
                 EventType::Created,
@@ -156,24 +303,55 @@ impl LimitV1Adapter {
             LimitV1EventEnvelope::TradeEvent(TradeEventFields {
                 order_key,
                 taker,
+                maker,
                 in_amount,
                 out_amount,
                 remaining_in_amount,
-                ..
-            }) => Ok((
-                EventType::FillCompleted,
-                CorrelationOutcome::Correlated(vec![order_key]),
-                EventPayload::LimitFill {
-                    in_amount: ProtocolHelpers::checked_u64_to_i64(in_amount, "in_amount")?,
-                    out_amount: ProtocolHelpers::checked_u64_to_i64(out_amount, "out_amount")?,
-                    remaining_in_amount: ProtocolHelpers::checked_u64_to_i64(
-                        remaining_in_amount,
-                        "remaining_in_amount",
-                    )?,
-                    counterparty: taker,
-                },
-            )),
+                remaining_out_amount,
+            }) => {
+                let remaining_anomaly = ctx.remaining_exceeds_original(remaining_in_amount);
+                Ok((
+                    EventType::FillCompleted,
+                    CorrelationOutcome::Correlated(vec![order_key]),
+                    EventPayload::LimitFill {
+                        in_amount: amounts.cast(in_amount, "in_amount")?,
+                        out_amount: amounts.cast(out_amount, "out_amount")?,
+                        remaining_in_amount: if remaining_anomaly {
+                            0
+                        } else {
+                            amounts.cast(remaining_in_amount, "remaining_in_amount")?
+                        },
+                        counterparty: taker,
+                        maker: maker.or_else(|| ctx.owner.clone()),
+                        remaining_out_amount: amounts
+                            .cast(remaining_out_amount, "remaining_out_amount")?,
+                        saturated: amounts.saturated(),
+                        remaining_anomaly,
+                    },
+                ))
+            }
+        }
+    }
+
+    /// Like [`ProtocolAdapter::classify_and_resolve_event`], but rejects a `TradeEvent` that
+    /// carries Limit v2 field names instead of parsing it through the cross-version aliases.
+    ///
+    /// Intended for diagnostics: surfaces payloads routed to the wrong adapter.
+    pub fn resolve_event_strict(
+        &self,
+        ev: &RawEvent,
+        ctx: &ResolveContext,
+    ) -> Option<Result<(EventType, CorrelationOutcome, EventPayload), Error>> {
+        let fields = ProtocolHelpers::wrapped_event_fields(ev, &EVENT_VARIANTS)?;
+        if let Some(field) =
+            ProtocolHelpers::find_present_field(&fields, "TradeEvent", V2_TRADE_FIELDS)
+        {
+            return Some(Err(Error::Protocol {
+                code: ErrorCode::MalformedPayload,
+                reason: format!("Limit v1 TradeEvent carries Limit v2 field `{field}`"),
+            }));
         }
+        self.classify_and_resolve_event(ev, ctx)
     }
 
     /// Extracts the order PDA from instruction accounts.
@@ -187,9 +365,26 @@ impl LimitV1Adapter {
             return Ok(acc.pubkey.clone());
         }
 
+        let idx = Self::order_pda_index(instruction_name)?;
+
+        accounts
+            .get(idx)
+            .map(|a| a.pubkey.clone())
+            .ok_or_else(|| Error::Protocol {
+                code: ErrorCode::OutOfBoundsAccount,
+                reason: format!(
+                    "Limit v1 account index {idx} out of bounds for {instruction_name}"
+                ),
+            })
+    }
+
+    /// Positional index of the `"order"` account in `instruction_name`, used when the account
+    /// is not named.
+    pub fn order_pda_index(instruction_name: &str) -> Result<usize, Error> {
         let wrapper = serde_json::json!({ instruction_name: serde_json::Value::Null });
         let kind: LimitV1InstructionKind =
             serde_json::from_value(wrapper).map_err(|_| Error::Protocol {
+                code: ErrorCode::UnknownInstruction,
                 reason: format!("unknown Limit v1 instruction: {instruction_name}"),
             })?;
 
@@ -204,55 +399,31 @@ impl LimitV1Adapter {
             | LimitV1InstructionKind::InitFee(_)
             | LimitV1InstructionKind::UpdateFee(_) => {
                 return Err(Error::Protocol {
+                    code: ErrorCode::UnsupportedInstruction,
                     reason: format!("Limit v1 instruction {instruction_name} has no order PDA"),
                 });
             }
         };
-
-        accounts
-            .get(idx)
-            .map(|a| a.pubkey.clone())
-            .ok_or_else(|| Error::Protocol {
-                reason: format!(
-                    "Limit v1 account index {idx} out of bounds for {instruction_name}"
-                ),
-            })
+        Ok(idx)
     }
 
     /// Extracts input/output mint addresses from a Limit v1 create instruction's accounts.
     ///
-    /// Prefers named accounts; falls back to positional indexes 5 (input) and 8 (output).
+    /// Prefers each named account independently; falls back to positional indexes 5 (input) and 8 (output).
     pub fn extract_create_mints(accounts: &[AccountInfo]) -> Result<LimitV1CreateMints, Error> {
-        let by_name_input =
-            ProtocolHelpers::find_account_by_name(accounts, "input_mint").map(|a| a.pubkey.clone());
-        let by_name_output = ProtocolHelpers::find_account_by_name(accounts, "output_mint")
-            .map(|a| a.pubkey.clone());
-
-        if let (Some(input_mint), Some(output_mint)) = (by_name_input, by_name_output) {
-            return Ok(LimitV1CreateMints {
-                input_mint,
-                output_mint,
-            });
-        }
-
-        let input_mint =
-            accounts
-                .get(5)
-                .map(|a| a.pubkey.clone())
-                .ok_or_else(|| Error::Protocol {
-                    reason: "Limit v1 input_mint index 5 out of bounds".into(),
-                })?;
-        let output_mint =
-            accounts
-                .get(8)
-                .map(|a| a.pubkey.clone())
-                .ok_or_else(|| Error::Protocol {
-                    reason: "Limit v1 output_mint index 8 out of bounds".into(),
-                })?;
-
         Ok(LimitV1CreateMints {
-            input_mint,
-            output_mint,
+            input_mint: ProtocolHelpers::named_or_positional_account(
+                accounts,
+                "input_mint",
+                5,
+                "Limit v1",
+            )?,
+            output_mint: ProtocolHelpers::named_or_positional_account(
+                accounts,
+                "output_mint",
+                8,
+                "Limit v1",
+            )?,
         })
     }
 
@@ -262,17 +433,49 @@ impl LimitV1Adapter {
             making_amount,
             taking_amount,
             expired_at,
-        } = serde_json::from_value(args.clone()).map_err(|e| Error::Protocol {
-            reason: format!("failed to parse Limit v1 create args: {e}"),
-        })?;
+        } = ProtocolHelpers::parse_create_fields(args, "Limit v1")?;
 
         Ok(LimitV1CreateArgs {
             making_amount: ProtocolHelpers::checked_u64_to_i64(making_amount, "making_amount")?,
             taking_amount: ProtocolHelpers::checked_u64_to_i64(taking_amount, "taking_amount")?,
-            expired_at,
+            expired_at: expired_at
+                .map(|ts| ProtocolHelpers::checked_timestamp(ts, "expired_at"))
+                .transpose()?,
         })
     }
 
+    /// Parses the fill settled at expiry from enriched `CancelExpiredOrder` args.
+    ///
+    /// The on-chain instruction has no args, so `in_amount`/`out_amount` must be supplied by an
+    /// enriched decoder (e.g. from the order's token balance changes). `None` when either is
+    /// missing, when `args` is not an object, or when both are zero; present but unparsable
+    /// amounts are still `MalformedPayload`.
+    pub fn parse_expiry_settlement(
+        args: &serde_json::Value,
+    ) -> Result<Option<LimitV1ExpirySettlement>, Error> {
+        if !args.is_object() {
+            return Ok(None);
+        }
+        let CancelExpiredOrderFields {
+            in_amount,
+            out_amount,
+        } = serde_json::from_value(args.clone()).map_err(|e| Error::Protocol {
+            code: ErrorCode::MalformedPayload,
+            reason: format!("failed to parse Limit v1 CancelExpiredOrder args: {e}"),
+        })?;
+        let (Some(in_amount), Some(out_amount)) = (in_amount, out_amount) else {
+            return Ok(None);
+        };
+        if in_amount == 0 && out_amount == 0 {
+            return Ok(None);
+        }
+
+        Ok(Some(LimitV1ExpirySettlement {
+            in_amount: ProtocolHelpers::checked_u64_to_i64(in_amount, "in_amount")?,
+            out_amount: ProtocolHelpers::checked_u64_to_i64(out_amount, "out_amount")?,
+        }))
+    }
+
     #[cfg(all(test, feature = "native"))]
     pub fn classify_decoded(
         decoded: &carbon_jupiter_limit_order_decoder::instructions::JupiterLimitOrderInstruction,
@@ -304,6 +507,7 @@ impl LimitV1Adapter {
 )]
 mod tests {
     use super::*;
+    use crate::test_util::{RawEventBuilder, RawInstructionBuilder};
 
     fn account(pubkey: &str, name: Option<&str>) -> AccountInfo {
         AccountInfo {
@@ -315,26 +519,14 @@ mod tests {
     }
 
     fn make_event(fields: serde_json::Value) -> RawEvent {
-        RawEvent {
-            id: 1,
-            signature: "sig".to_string(),
-            event_index: 0,
-            event_path: None,
-            program_id: "p".to_string(),
-            inner_program_id: "p".to_string(),
-            event_name: "test".to_string(),
-            fields: Some(fields),
-            slot: 1,
-        }
+        RawEventBuilder::default().fields(fields).build()
     }
 
     fn resolve(
         fields: serde_json::Value,
     ) -> Option<Result<(EventType, CorrelationOutcome, EventPayload), crate::error::Error>> {
         let ev = make_event(fields);
-        let ctx = ResolveContext {
-            pre_fetched_order_pdas: None,
-        };
+        let ctx = ResolveContext::default();
         LimitV1Adapter.classify_and_resolve_event(&ev, &ctx)
     }
 
@@ -353,18 +545,7 @@ mod tests {
             ("Unknown", None),
         ];
         for (name, expected) in cases {
-            let ix = RawInstruction {
-                id: 1,
-                signature: "sig".to_string(),
-                instruction_index: 0,
-                instruction_path: None,
-                program_id: "p".to_string(),
-                inner_program_id: "p".to_string(),
-                instruction_name: name.to_string(),
-                accounts: None,
-                args: None,
-                slot: 1,
-            };
+            let ix = RawInstructionBuilder::new(name).build();
             assert_eq!(
                 LimitV1Adapter.classify_instruction(&ix),
                 expected,
@@ -396,13 +577,21 @@ mod tests {
             out_amount,
             remaining_in_amount,
             counterparty,
+            maker,
+            saturated,
+            remaining_out_amount,
+            remaining_anomaly,
         } = payload
         else {
             panic!("expected LimitFill");
         };
+        assert!(!saturated);
+        assert!(!remaining_anomaly);
+        assert_eq!(maker, None);
         assert_eq!(in_amount, 724_773_829);
         assert_eq!(out_amount, 51_821_329);
         assert_eq!(remaining_in_amount, 89_147_181_051);
+        assert_eq!(remaining_out_amount, 6_374_023_074);
         assert_eq!(counterparty, "j1oeQoPeuEDmjvyMwBmCWexzCQup77kbKKxV59CnYbd");
     }
 
@@ -476,6 +665,131 @@ mod tests {
         assert_eq!(counterparty, "unknown");
     }
 
+    #[test]
+    fn resolve_trade_event_reads_owner_as_maker() {
+        let trade = |taker: &str| {
+            serde_json::json!({
+                "TradeEvent": {
+                    "order_key": "order",
+                    "taker": taker,
+                    "owner": "owner_wallet",
+                    "in_amount": 10_u64,
+                    "out_amount": 5_u64,
+                    "remaining_in_amount": 0_u64,
+                    "remaining_out_amount": 0_u64
+                }
+            })
+        };
+
+        let (_, _, self_fill) = resolve(trade("owner_wallet")).unwrap().unwrap();
+        assert_eq!(self_fill.is_self_fill(), Some(true));
+        let (_, _, normal_fill) = resolve(trade("taker_wallet")).unwrap().unwrap();
+        assert_eq!(normal_fill.is_self_fill(), Some(false));
+
+        let ctx = ResolveContext {
+            owner: Some("taker_wallet".to_string()),
+            ..ResolveContext::default()
+        };
+        let (_, _, event_owner_wins) = LimitV1Adapter
+            .classify_and_resolve_event(&make_event(trade("taker_wallet")), &ctx)
+            .unwrap()
+            .unwrap();
+        assert_eq!(event_owner_wins.is_self_fill(), Some(false));
+    }
+
+    #[test]
+    fn resolve_event_strict_rejects_v2_field_names() {
+        let v2_fields = serde_json::json!({
+            "TradeEvent": {
+                "order_key": "order",
+                "making_amount": 10_u64,
+                "taking_amount": 5_u64,
+                "remaining_making_amount": 1_u64,
+                "remaining_taking_amount": 0_u64
+            }
+        });
+        let ctx = ResolveContext::default();
+
+        assert!(resolve(v2_fields.clone()).unwrap().is_ok());
+        let err = LimitV1Adapter
+            .resolve_event_strict(&make_event(v2_fields), &ctx)
+            .unwrap()
+            .unwrap_err();
+        let Error::Protocol { reason, .. } = err else {
+            panic!("expected protocol error");
+        };
+        assert_eq!(
+            reason,
+            "Limit v1 TradeEvent carries Limit v2 field `making_amount`"
+        );
+
+        let v1_fields = serde_json::json!({
+            "TradeEvent": {
+                "order_key": "order",
+                "in_amount": 10_u64,
+                "out_amount": 5_u64,
+                "remaining_in_amount": 1_u64,
+                "remaining_out_amount": 0_u64
+            }
+        });
+        assert!(
+            LimitV1Adapter
+                .resolve_event_strict(&make_event(v1_fields), &ctx)
+                .unwrap()
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn cancel_expired_order_carries_optional_settlement() {
+        let resolve = |args: serde_json::Value| {
+            let ix = RawInstructionBuilder::new("CancelExpiredOrder")
+                .args(args)
+                .build();
+            LimitV1Adapter.resolve_instruction(&ix).unwrap().unwrap()
+        };
+
+        let settled = resolve(serde_json::json!({ "in_amount": "400", "out_amount": 380_u64 }));
+        assert_eq!(settled.event_type, EventType::Expired);
+        let InstructionArgs::LimitV1ExpirySettlement(settlement) = settled.args else {
+            panic!("expected settlement args");
+        };
+        assert_eq!(
+            settlement,
+            LimitV1ExpirySettlement {
+                in_amount: 400,
+                out_amount: 380,
+            }
+        );
+        let mut order = crate::lifecycle::order::OrderLifecycle::new();
+        order
+            .apply(
+                crate::lifecycle::LifecycleTransition::FillDelta,
+                &settlement.fill_payload(),
+                1,
+            )
+            .unwrap();
+        assert_eq!(order.fills().total_in, 400);
+
+        for unsettled in [
+            serde_json::json!({}),
+            serde_json::json!(null),
+            serde_json::json!([]),
+            serde_json::json!({ "in_amount": 400_u64 }),
+            serde_json::json!({ "in_amount": 0_u64, "out_amount": 0_u64 }),
+        ] {
+            let outcome = resolve(unsettled);
+            assert_eq!(outcome.event_type, EventType::Expired);
+            assert_eq!(outcome.args, InstructionArgs::None);
+        }
+
+        let err = LimitV1Adapter::parse_expiry_settlement(
+            &serde_json::json!({ "in_amount": "x", "out_amount": 1_u64 }),
+        )
+        .unwrap_err();
+        assert_eq!(err.code(), ErrorCode::MalformedPayload);
+    }
+
     #[test]
     fn parse_create_args_rejects_amount_overflow() {
         let args = serde_json::json!({
@@ -498,6 +812,17 @@ mod tests {
         assert_eq!(parsed.expired_at, Some(1_700_000_000));
     }
 
+    #[test]
+    fn parse_create_args_rejects_implausible_expired_at() {
+        let args = serde_json::json!({
+            "making_amount": 5_000_u64,
+            "taking_amount": 4_500_u64,
+            "expired_at": i64::MIN
+        });
+        let err = LimitV1Adapter::parse_create_args(&args).unwrap_err();
+        assert!(err.to_string().contains("expired_at"));
+    }
+
     #[test]
     fn parse_create_args_rejects_malformed_payload() {
         let args = serde_json::json!({
@@ -540,7 +865,7 @@ mod tests {
     #[test]
     fn extract_order_pda_rejects_unknown_instruction() {
         let err = LimitV1Adapter::extract_order_pda(&[], "Unknown").unwrap_err();
-        let Error::Protocol { reason } = err else {
+        let Error::Protocol { reason, .. } = err else {
             panic!("expected protocol error");
         };
         assert_eq!(reason, "unknown Limit v1 instruction: Unknown");
@@ -549,7 +874,7 @@ mod tests {
     #[test]
     fn extract_order_pda_rejects_out_of_bounds_index() {
         let err = LimitV1Adapter::extract_order_pda(&[], "InitializeOrder").unwrap_err();
-        let Error::Protocol { reason } = err else {
+        let Error::Protocol { reason, .. } = err else {
             panic!("expected protocol error");
         };
         assert_eq!(
@@ -594,7 +919,7 @@ mod tests {
         let err = LimitV1Adapter::extract_create_mints(&[])
             .err()
             .expect("expected error");
-        let Error::Protocol { reason } = err else {
+        let Error::Protocol { reason, .. } = err else {
             panic!("expected protocol error");
         };
         assert_eq!(reason, "Limit v1 input_mint index 5 out of bounds");
@@ -613,7 +938,7 @@ mod tests {
         let err = LimitV1Adapter::extract_create_mints(&accounts)
             .err()
             .expect("expected error");
-        let Error::Protocol { reason } = err else {
+        let Error::Protocol { reason, .. } = err else {
             panic!("expected protocol error");
         };
         assert_eq!(reason, "Limit v1 output_mint index 8 out of bounds");
@@ -623,18 +948,7 @@ mod tests {
     #[test]
     fn instruction_constants_match_classify() {
         for (name, expected) in INSTRUCTION_EVENT_TYPES {
-            let ix = RawInstruction {
-                id: 1,
-                signature: "sig".to_string(),
-                instruction_index: 0,
-                instruction_path: None,
-                program_id: "p".to_string(),
-                inner_program_id: "p".to_string(),
-                instruction_name: name.to_string(),
-                accounts: None,
-                args: None,
-                slot: 1,
-            };
+            let ix = RawInstructionBuilder::new(*name).build();
             assert_eq!(
                 LimitV1Adapter.classify_instruction(&ix).as_ref(),
                 Some(expected),
@@ -1,8 +1,13 @@
-use crate::error::Error;
-use crate::lifecycle::adapters::{CorrelationOutcome, EventPayload, ProtocolAdapter};
-use crate::protocols::{AccountInfo, EventType, Protocol, ProtocolHelpers};
+use crate::error::{Error, ErrorCode};
+use crate::lifecycle::TerminalStatus;
+use crate::lifecycle::adapters::{
+    CorrelationOutcome, EventPayload, InstructionArgs, InstructionOutcome, ProtocolAdapter,
+    ResolvedEvent, UNKNOWN_COUNTERPARTY,
+};
+use crate::protocols::{
+    AccountInfo, AmountCaster, EventType, InstructionCategory, Protocol, ProtocolHelpers,
+};
 use crate::types::{RawEvent, RawInstruction, ResolveContext};
-use strum::VariantNames;
 
 /// Serde-tagged envelope for Jupiter Limit Order v2 event variants.
 #[derive(serde::Deserialize, strum_macros::VariantNames)]
@@ -13,7 +18,7 @@ pub enum LimitV2EventEnvelope {
 }
 
 /// Serde-tagged envelope for Jupiter Limit Order v2 instruction variants.
-#[derive(serde::Deserialize)]
+#[derive(serde::Deserialize, strum_macros::VariantNames)]
 pub enum LimitV2InstructionKind {
     InitializeOrder(serde_json::Value),
     PreFlashFillOrder(serde_json::Value),
@@ -30,13 +35,65 @@ pub const INSTRUCTION_EVENT_TYPES: &[(&str, EventType)] = &[
     ("CancelOrder", EventType::Cancelled),
 ];
 
+/// Known instructions outside [`INSTRUCTION_EVENT_TYPES`], with their [`InstructionCategory`].
+pub const INSTRUCTION_CATEGORIES: &[(&str, InstructionCategory)] = &[
+    ("UpdateFee", InstructionCategory::Config),
+    ("WithdrawFee", InstructionCategory::Fee),
+];
+
 pub const EVENT_EVENT_TYPES: &[(&str, EventType)] = &[
     ("CreateOrderEvent", EventType::Created),
     ("CancelOrderEvent", EventType::Cancelled),
     ("TradeEvent", EventType::FillCompleted),
 ];
 
-pub const CLOSED_VARIANTS: &[&str] = &[];
+/// [`crate::EventPayload`] variant each event in [`EVENT_EVENT_TYPES`] resolves to.
+/// A `CancelOrderEvent` on a fully filled order yields `Closed` instead (see
+/// [`crate::ResolveContext::order_fully_filled`]).
+pub const EVENT_PAYLOAD_KINDS: &[(&str, &str)] = &[
+    ("CreateOrderEvent", "None"),
+    ("CancelOrderEvent", "None"),
+    ("TradeEvent", "LimitFill"),
+];
+
+/// Event variants whose [`EventType`] depends on [`ResolveContext`], with the alternative type.
+///
+/// `CancelOrderEvent` is `Closed` (Completed) when [`ResolveContext::order_fully_filled`], else
+/// `Expired` when [`ResolveContext::order_has_expired`].
+pub const CONTEXT_EVENT_TYPES: &[(&str, EventType)] = &[
+    ("CancelOrderEvent", EventType::Closed),
+    ("CancelOrderEvent", EventType::Expired),
+];
+
+/// Every event type the adapter can produce: the union of [`INSTRUCTION_EVENT_TYPES`],
+/// [`EVENT_EVENT_TYPES`] and the context-derived [`CONTEXT_EVENT_TYPES`].
+pub const POSSIBLE_EVENT_TYPES: &[EventType] = &[
+    EventType::Created,
+    EventType::FillInitiated,
+    EventType::FillCompleted,
+    EventType::Cancelled,
+    EventType::Expired,
+    EventType::Closed,
+];
+
+/// Terminal statuses a `Closed` event can carry: only a cancel of a fully filled order closes,
+/// as [`EventPayload::Closed`] with `Completed`.
+pub const CLOSED_VARIANTS: &[&str] = &["Completed"];
+
+/// [`LimitV2EventEnvelope`] variant names as a compile-time set (see [`crate::protocols::is_known_variant`]).
+pub static EVENT_VARIANTS: phf::Set<&'static str> = phf::phf_set! {
+    "CreateOrderEvent",
+    "CancelOrderEvent",
+    "TradeEvent",
+};
+
+/// Limit v1 `TradeEvent` field names rejected by [`LimitV2Adapter::resolve_event_strict`].
+const V1_TRADE_FIELDS: &[&str] = &[
+    "in_amount",
+    "out_amount",
+    "remaining_in_amount",
+    "remaining_out_amount",
+];
 
 /// Jupiter Limit Order v2 protocol adapter (zero-sized, stored as a static).
 #[derive(Debug)]
@@ -45,19 +102,26 @@ pub struct LimitV2Adapter;
 /// Serde intermediate for events that only carry an `order_key`.
 #[derive(serde::Deserialize)]
 pub struct OrderKeyHolder {
+    #[serde(deserialize_with = "crate::protocols::de_nonempty_pubkey")]
     order_key: String,
 }
 
 /// Serde intermediate for `TradeEvent` payload fields (v2 field names).
 #[derive(serde::Deserialize)]
 pub struct TradeEventFields {
+    #[serde(deserialize_with = "crate::protocols::de_nonempty_pubkey")]
     order_key: String,
     #[serde(default = "LimitV2Adapter::default_unknown")]
     taker: String,
+    #[serde(alias = "owner", default)]
+    maker: Option<String>,
+    #[serde(deserialize_with = "crate::protocols::de_u64_flexible")]
     making_amount: u64,
+    #[serde(deserialize_with = "crate::protocols::de_u64_flexible")]
     taking_amount: u64,
+    #[serde(deserialize_with = "crate::protocols::de_u64_flexible")]
     remaining_making_amount: u64,
-    #[expect(dead_code, reason = "consumed by serde for completeness")]
+    #[serde(deserialize_with = "crate::protocols::de_u64_flexible")]
     remaining_taking_amount: u64,
 }
 
@@ -74,6 +138,7 @@ pub struct LimitV2TradeEvent {
 /// Parsed arguments from an `InitializeOrder` instruction (Limit v2).
 ///
 /// `fee_bps` and `unique_id` are v2-specific fields not present in v1.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct LimitV2CreateArgs {
     pub unique_id: Option<i64>,
     pub making_amount: i64,
@@ -92,7 +157,9 @@ pub struct LimitV2CreateMints {
 struct InitializeOrderParamsFields {
     #[serde(default)]
     unique_id: Option<u64>,
+    #[serde(deserialize_with = "crate::protocols::de_u64_flexible")]
     making_amount: u64,
+    #[serde(deserialize_with = "crate::protocols::de_u64_flexible")]
     taking_amount: u64,
     expired_at: Option<i64>,
     #[serde(default)]
@@ -110,77 +177,154 @@ impl ProtocolAdapter for LimitV2Adapter {
     }
 
     fn classify_instruction(&self, ix: &RawInstruction) -> Option<EventType> {
-        ProtocolHelpers::lookup_event_type(&ix.instruction_name, INSTRUCTION_EVENT_TYPES)
+        self.classify_instruction_category(ix).event_type()
+    }
+
+    fn classify_instruction_category(&self, ix: &RawInstruction) -> InstructionCategory {
+        ProtocolHelpers::lookup_instruction_category(
+            &ix.instruction_name,
+            INSTRUCTION_EVENT_TYPES,
+            INSTRUCTION_CATEGORIES,
+        )
+    }
+
+    fn resolve_instruction(
+        &self,
+        ix: &RawInstruction,
+    ) -> Option<Result<InstructionOutcome, Error>> {
+        let event_type = self.classify_instruction(ix)?;
+        Some(ProtocolHelpers::resolve_classified_instruction(
+            ix,
+            event_type,
+            Self::extract_order_pda,
+            |args| Self::parse_create_args(args).map(InstructionArgs::LimitV2Create),
+        ))
     }
 
     fn classify_and_resolve_event(
         &self,
         ev: &RawEvent,
-        _ctx: &ResolveContext,
-    ) -> Option<Result<(EventType, CorrelationOutcome, EventPayload), Error>> {
-        let fields = ev.fields.as_ref()?;
+        ctx: &ResolveContext,
+    ) -> Option<Result<ResolvedEvent, Error>> {
+        let fields = ProtocolHelpers::wrapped_event_fields(ev, &EVENT_VARIANTS)?;
+        Self::resolve_fields(&fields, ctx)
+    }
+}
+
+impl LimitV2Adapter {
+    /// Classifies and resolves a bare event `fields` payload (see [`crate::resolve_event_value`]).
+    pub fn resolve_fields(
+        fields: &serde_json::Value,
+        ctx: &ResolveContext,
+    ) -> Option<Result<ResolvedEvent, Error>> {
         let envelope: LimitV2EventEnvelope = match serde_json::from_value(fields.clone()) {
             Ok(e) => e,
             Err(err) => {
-                if !ProtocolHelpers::contains_known_variant(fields, LimitV2EventEnvelope::VARIANTS)
-                {
+                if !ProtocolHelpers::contains_known_variant(fields, &EVENT_VARIANTS) {
                     return None;
                 }
                 return Some(Err(Error::Protocol {
+                    code: ErrorCode::MalformedPayload,
                     reason: format!("failed to parse Limit v2 event payload: {err}"),
                 }));
             }
         };
 
-        Some(Self::resolve_event(envelope))
+        Some(Self::resolve_event(envelope, ctx))
     }
-}
 
-impl LimitV2Adapter {
     fn default_unknown() -> String {
-        "unknown".to_string()
+        UNKNOWN_COUNTERPARTY.to_string()
     }
 
+    /// A `CancelOrderEvent` on an order whose remaining making amount is already zero closes a
+    /// fully filled order, so it is reported as `Closed` with a `Completed` status when
+    /// [`ResolveContext::order_fully_filled`]. Limit v2 has no `CancelExpiredOrder`, so otherwise
+    /// it is `Expired` when [`ResolveContext::order_has_expired`], else `Cancelled`.
     fn resolve_event(
         envelope: LimitV2EventEnvelope,
+        ctx: &ResolveContext,
     ) -> Result<(EventType, CorrelationOutcome, EventPayload), Error> {
+        let mut amounts = AmountCaster::new(ctx);
         match envelope {
             LimitV2EventEnvelope::CreateOrderEvent(OrderKeyHolder { order_key }) => Ok((
                 EventType::Created,
                 CorrelationOutcome::Correlated(vec![order_key]),
                 EventPayload::None,
             )),
-            LimitV2EventEnvelope::CancelOrderEvent(OrderKeyHolder { order_key }) => Ok((
-                EventType::Cancelled,
-                CorrelationOutcome::Correlated(vec![order_key]),
-                EventPayload::None,
-            )),
+            LimitV2EventEnvelope::CancelOrderEvent(OrderKeyHolder { order_key }) => {
+                let (event_type, payload) = if ctx.order_fully_filled() {
+                    (
+                        EventType::Closed,
+                        EventPayload::Closed {
+                            status: TerminalStatus::Completed,
+                        },
+                    )
+                } else if ctx.order_has_expired() {
+                    (EventType::Expired, EventPayload::None)
+                } else {
+                    (EventType::Cancelled, EventPayload::None)
+                };
+                Ok((
+                    event_type,
+                    CorrelationOutcome::Correlated(vec![order_key]),
+                    payload,
+                ))
+            }
             LimitV2EventEnvelope::TradeEvent(TradeEventFields {
                 order_key,
                 taker,
+                maker,
                 making_amount,
                 taking_amount,
                 remaining_making_amount,
-                ..
-            }) => Ok((
-                EventType::FillCompleted,
-                CorrelationOutcome::Correlated(vec![order_key]),
-                EventPayload::LimitFill {
-                    in_amount: ProtocolHelpers::checked_u64_to_i64(making_amount, "making_amount")?,
-                    out_amount: ProtocolHelpers::checked_u64_to_i64(
-                        taking_amount,
-                        "taking_amount",
-                    )?,
-                    remaining_in_amount: ProtocolHelpers::checked_u64_to_i64(
-                        remaining_making_amount,
-                        "remaining_making_amount",
-                    )?,
-                    counterparty: taker,
-                },
-            )),
+                remaining_taking_amount,
+            }) => {
+                let remaining_anomaly = ctx.remaining_exceeds_original(remaining_making_amount);
+                Ok((
+                    EventType::FillCompleted,
+                    CorrelationOutcome::Correlated(vec![order_key]),
+                    EventPayload::LimitFill {
+                        in_amount: amounts.cast(making_amount, "making_amount")?,
+                        out_amount: amounts.cast(taking_amount, "taking_amount")?,
+                        remaining_in_amount: if remaining_anomaly {
+                            0
+                        } else {
+                            amounts.cast(remaining_making_amount, "remaining_making_amount")?
+                        },
+                        counterparty: taker,
+                        maker: maker.or_else(|| ctx.owner.clone()),
+                        remaining_out_amount: amounts
+                            .cast(remaining_taking_amount, "remaining_taking_amount")?,
+                        saturated: amounts.saturated(),
+                        remaining_anomaly,
+                    },
+                ))
+            }
         }
     }
 
+    /// Like [`ProtocolAdapter::classify_and_resolve_event`], but rejects a `TradeEvent` that
+    /// carries Limit v1 field names instead of parsing it through the cross-version aliases.
+    ///
+    /// Intended for diagnostics: surfaces payloads routed to the wrong adapter.
+    pub fn resolve_event_strict(
+        &self,
+        ev: &RawEvent,
+        ctx: &ResolveContext,
+    ) -> Option<Result<(EventType, CorrelationOutcome, EventPayload), Error>> {
+        let fields = ProtocolHelpers::wrapped_event_fields(ev, &EVENT_VARIANTS)?;
+        if let Some(field) =
+            ProtocolHelpers::find_present_field(&fields, "TradeEvent", V1_TRADE_FIELDS)
+        {
+            return Some(Err(Error::Protocol {
+                code: ErrorCode::MalformedPayload,
+                reason: format!("Limit v2 TradeEvent carries Limit v1 field `{field}`"),
+            }));
+        }
+        self.classify_and_resolve_event(ev, ctx)
+    }
+
     /// Extracts the order PDA from instruction accounts.
     ///
     /// Prefers the named `"order"` account; falls back to positional index per instruction variant.
@@ -192,9 +336,26 @@ impl LimitV2Adapter {
             return Ok(acc.pubkey.clone());
         }
 
+        let idx = Self::order_pda_index(instruction_name)?;
+
+        accounts
+            .get(idx)
+            .map(|a| a.pubkey.clone())
+            .ok_or_else(|| Error::Protocol {
+                code: ErrorCode::OutOfBoundsAccount,
+                reason: format!(
+                    "Limit v2 account index {idx} out of bounds for {instruction_name}"
+                ),
+            })
+    }
+
+    /// Positional index of the `"order"` account in `instruction_name`, used when the account
+    /// is not named.
+    pub fn order_pda_index(instruction_name: &str) -> Result<usize, Error> {
         let wrapper = serde_json::json!({ instruction_name: serde_json::Value::Null });
         let kind: LimitV2InstructionKind =
             serde_json::from_value(wrapper).map_err(|_| Error::Protocol {
+                code: ErrorCode::UnknownInstruction,
                 reason: format!("unknown Limit v2 instruction: {instruction_name}"),
             })?;
 
@@ -204,72 +365,49 @@ impl LimitV2Adapter {
             LimitV2InstructionKind::PreFlashFillOrder(_) => 1,
             LimitV2InstructionKind::UpdateFee(_) | LimitV2InstructionKind::WithdrawFee(_) => {
                 return Err(Error::Protocol {
+                    code: ErrorCode::UnsupportedInstruction,
                     reason: format!("Limit v2 instruction {instruction_name} has no order PDA"),
                 });
             }
         };
-
-        accounts
-            .get(idx)
-            .map(|a| a.pubkey.clone())
-            .ok_or_else(|| Error::Protocol {
-                reason: format!(
-                    "Limit v2 account index {idx} out of bounds for {instruction_name}"
-                ),
-            })
+        Ok(idx)
     }
 
     /// Extracts input/output mint addresses from a Limit v2 create instruction's accounts.
     ///
-    /// Prefers named accounts; falls back to positional indexes 7 (input) and 8 (output).
+    /// Prefers each named account independently; falls back to positional indexes 7 (input) and 8 (output).
     pub fn extract_create_mints(accounts: &[AccountInfo]) -> Result<LimitV2CreateMints, Error> {
-        let by_name_input =
-            ProtocolHelpers::find_account_by_name(accounts, "input_mint").map(|a| a.pubkey.clone());
-        let by_name_output = ProtocolHelpers::find_account_by_name(accounts, "output_mint")
-            .map(|a| a.pubkey.clone());
-
-        if let (Some(input_mint), Some(output_mint)) = (by_name_input, by_name_output) {
-            return Ok(LimitV2CreateMints {
-                input_mint,
-                output_mint,
-            });
-        }
-
-        let input_mint =
-            accounts
-                .get(7)
-                .map(|a| a.pubkey.clone())
-                .ok_or_else(|| Error::Protocol {
-                    reason: "Limit v2 input_mint index 7 out of bounds".into(),
-                })?;
-        let output_mint =
-            accounts
-                .get(8)
-                .map(|a| a.pubkey.clone())
-                .ok_or_else(|| Error::Protocol {
-                    reason: "Limit v2 output_mint index 8 out of bounds".into(),
-                })?;
-
         Ok(LimitV2CreateMints {
-            input_mint,
-            output_mint,
+            input_mint: ProtocolHelpers::named_or_positional_account(
+                accounts,
+                "input_mint",
+                7,
+                "Limit v2",
+            )?,
+            output_mint: ProtocolHelpers::named_or_positional_account(
+                accounts,
+                "output_mint",
+                8,
+                "Limit v2",
+            )?,
         })
     }
 
     /// Parses `InitializeOrder` instruction args into checked [`LimitV2CreateArgs`].
     ///
-    /// Handles both `{"params": {...}}` wrapper format and flat format.
+    /// Handles the `{"params": {...}}` wrapper and flat formats, either of which may be nested
+    /// under a `data` key.
     pub fn parse_create_args(args: &serde_json::Value) -> Result<LimitV2CreateArgs, Error> {
-        let params =
-            if let Ok(wrapper) = serde_json::from_value::<InitializeOrderWrapper>(args.clone()) {
-                wrapper.params
-            } else {
-                serde_json::from_value::<InitializeOrderParamsFields>(args.clone()).map_err(
-                    |e| Error::Protocol {
-                        reason: format!("failed to parse Limit v2 create args: {e}"),
-                    },
-                )?
-            };
+        let wrapped = |value: &serde_json::Value| {
+            serde_json::from_value::<InitializeOrderWrapper>(value.clone()).ok()
+        };
+        let params = if let Some(wrapper) =
+            wrapped(args).or_else(|| args.get("data").and_then(wrapped))
+        {
+            wrapper.params
+        } else {
+            ProtocolHelpers::parse_create_fields::<InitializeOrderParamsFields>(args, "Limit v2")?
+        };
 
         let InitializeOrderParamsFields {
             unique_id,
@@ -283,7 +421,9 @@ impl LimitV2Adapter {
             unique_id: unique_id.and_then(ProtocolHelpers::optional_u64_to_i64),
             making_amount: ProtocolHelpers::checked_u64_to_i64(making_amount, "making_amount")?,
             taking_amount: ProtocolHelpers::checked_u64_to_i64(taking_amount, "taking_amount")?,
-            expired_at,
+            expired_at: expired_at
+                .map(|ts| ProtocolHelpers::checked_timestamp(ts, "expired_at"))
+                .transpose()?,
             fee_bps: fee_bps
                 .map(|v| ProtocolHelpers::checked_u16_to_i16(v, "fee_bps"))
                 .transpose()?,
@@ -318,6 +458,7 @@ impl LimitV2Adapter {
 )]
 mod tests {
     use super::*;
+    use crate::test_util::{RawEventBuilder, RawInstructionBuilder};
 
     fn account(pubkey: &str, name: Option<&str>) -> AccountInfo {
         AccountInfo {
@@ -329,29 +470,103 @@ mod tests {
     }
 
     fn make_event(fields: serde_json::Value) -> RawEvent {
-        RawEvent {
-            id: 1,
-            signature: "sig".to_string(),
-            event_index: 0,
-            event_path: None,
-            program_id: "p".to_string(),
-            inner_program_id: "p".to_string(),
-            event_name: "test".to_string(),
-            fields: Some(fields),
-            slot: 1,
-        }
+        RawEventBuilder::default().fields(fields).build()
     }
 
     fn resolve(
         fields: serde_json::Value,
     ) -> Option<Result<(EventType, CorrelationOutcome, EventPayload), crate::error::Error>> {
         let ev = make_event(fields);
-        let ctx = ResolveContext {
-            pre_fetched_order_pdas: None,
-        };
+        let ctx = ResolveContext::default();
         LimitV2Adapter.classify_and_resolve_event(&ev, &ctx)
     }
 
+    #[test]
+    fn cancel_event_after_expiry_resolves_as_expired() {
+        let ev = make_event(serde_json::json!({
+            "CancelOrderEvent": { "order_key": "order_pda" }
+        }));
+        let ctx_at = |block_time| ResolveContext {
+            block_time: Some(block_time),
+            order_expired_at: Some(1_700_000_000),
+            ..ResolveContext::default()
+        };
+
+        let (pre_expiry, correlation, _) = LimitV2Adapter
+            .classify_and_resolve_event(&ev, &ctx_at(1_699_999_999))
+            .unwrap()
+            .unwrap();
+        assert_eq!(pre_expiry, EventType::Cancelled);
+        assert_eq!(
+            correlation,
+            CorrelationOutcome::Correlated(vec!["order_pda".to_string()])
+        );
+
+        let (post_expiry, _, _) = LimitV2Adapter
+            .classify_and_resolve_event(&ev, &ctx_at(1_700_000_000))
+            .unwrap()
+            .unwrap();
+        assert_eq!(post_expiry, EventType::Expired);
+
+        let without_expiry = ResolveContext {
+            block_time: Some(1_800_000_000),
+            ..ResolveContext::default()
+        };
+        let (unknown_expiry, _, _) = LimitV2Adapter
+            .classify_and_resolve_event(&ev, &without_expiry)
+            .unwrap()
+            .unwrap();
+        assert_eq!(unknown_expiry, EventType::Cancelled);
+    }
+
+    #[test]
+    fn cancel_event_after_full_fill_resolves_as_completed_close() {
+        let ev = make_event(serde_json::json!({
+            "CancelOrderEvent": { "order_key": "order_pda" }
+        }));
+        let ctx_with_remaining = |remaining| ResolveContext {
+            order_remaining_making_amount: Some(remaining),
+            block_time: Some(1_800_000_000),
+            order_expired_at: Some(1_700_000_000),
+            ..ResolveContext::default()
+        };
+
+        let (event_type, correlation, payload) = LimitV2Adapter
+            .classify_and_resolve_event(&ev, &ctx_with_remaining(0))
+            .unwrap()
+            .unwrap();
+        assert_eq!(event_type, EventType::Closed);
+        assert_eq!(
+            correlation,
+            CorrelationOutcome::Correlated(vec!["order_pda".to_string()])
+        );
+        assert_eq!(
+            payload,
+            EventPayload::Closed {
+                status: TerminalStatus::Completed,
+            }
+        );
+
+        let (event_type, _, payload) = LimitV2Adapter
+            .classify_and_resolve_event(&ev, &ctx_with_remaining(500))
+            .unwrap()
+            .unwrap();
+        assert_eq!(event_type, EventType::Expired);
+        assert_eq!(payload, EventPayload::None);
+
+        let (event_type, _, _) = LimitV2Adapter
+            .classify_and_resolve_event(
+                &ev,
+                &ResolveContext {
+                    order_remaining_making_amount: Some(500),
+                    ..ResolveContext::default()
+                },
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(event_type, EventType::Cancelled);
+    }
+
     #[test]
     fn classify_known_instructions_via_envelope() {
         let cases = [
@@ -364,18 +579,7 @@ mod tests {
             ("Unknown", None),
         ];
         for (name, expected) in cases {
-            let ix = RawInstruction {
-                id: 1,
-                signature: "sig".to_string(),
-                instruction_index: 0,
-                instruction_path: None,
-                program_id: "p".to_string(),
-                inner_program_id: "p".to_string(),
-                instruction_name: name.to_string(),
-                accounts: None,
-                args: None,
-                slot: 1,
-            };
+            let ix = RawInstructionBuilder::new(name).build();
             assert_eq!(
                 LimitV2Adapter.classify_instruction(&ix),
                 expected,
@@ -384,6 +588,70 @@ mod tests {
         }
     }
 
+    #[test]
+    fn resolve_trade_event_detects_self_fill() {
+        let trade = |taker: &str| {
+            serde_json::json!({
+                "TradeEvent": {
+                    "order_key": "order",
+                    "taker": taker,
+                    "maker": "maker_wallet",
+                    "making_amount": 10_u64,
+                    "taking_amount": 5_u64,
+                    "remaining_making_amount": 0_u64,
+                    "remaining_taking_amount": 0_u64
+                }
+            })
+        };
+
+        let (_, _, self_fill) = resolve(trade("maker_wallet")).unwrap().unwrap();
+        assert_eq!(self_fill.is_self_fill(), Some(true));
+
+        let (_, _, normal_fill) = resolve(trade("taker_wallet")).unwrap().unwrap();
+        assert_eq!(normal_fill.is_self_fill(), Some(false));
+        let EventPayload::LimitFill { maker, .. } = normal_fill else {
+            panic!("expected LimitFill");
+        };
+        assert_eq!(maker.as_deref(), Some("maker_wallet"));
+
+        let mut no_taker = trade("unused");
+        no_taker["TradeEvent"]
+            .as_object_mut()
+            .unwrap()
+            .remove("taker");
+        let (_, _, unknown_taker) = resolve(no_taker).unwrap().unwrap();
+        assert_eq!(unknown_taker.is_self_fill(), None);
+    }
+
+    #[test]
+    fn context_owner_enables_self_fill_without_event_maker() {
+        let ev = make_event(serde_json::json!({
+            "TradeEvent": {
+                "order_key": "order",
+                "taker": "owner_wallet",
+                "making_amount": 10_u64,
+                "taking_amount": 5_u64,
+                "remaining_making_amount": 0_u64,
+                "remaining_taking_amount": 0_u64
+            }
+        }));
+        let self_fill = |owner: Option<&str>| {
+            let ctx = ResolveContext {
+                owner: owner.map(str::to_string),
+                ..ResolveContext::default()
+            };
+            let (_, _, payload) = LimitV2Adapter
+                .classify_and_resolve_event(&ev, &ctx)
+                .unwrap()
+                .unwrap();
+            payload.is_self_fill()
+        };
+
+        assert_eq!(self_fill(None), None);
+        assert_eq!(self_fill(Some("owner_wallet")), Some(true));
+        assert_eq!(self_fill(Some("other_wallet")), Some(false));
+    }
+
     #[test]
     fn resolve_trade_event_from_envelope() {
         let fields = serde_json::json!({
@@ -407,13 +675,21 @@ mod tests {
             out_amount,
             remaining_in_amount,
             counterparty,
+            maker,
+            saturated,
+            remaining_out_amount,
+            remaining_anomaly,
         } = payload
         else {
             panic!("expected LimitFill");
         };
+        assert!(!saturated);
+        assert!(!remaining_anomaly);
+        assert_eq!(maker, None);
         assert_eq!(in_amount, 724_773_829);
         assert_eq!(out_amount, 51_821_329);
         assert_eq!(remaining_in_amount, 89_147_181_051);
+        assert_eq!(remaining_out_amount, 6_374_023_074);
         assert_eq!(counterparty, "j1oeQoPeuEDmjvyMwBmCWexzCQup77kbKKxV59CnYbd");
     }
 
@@ -432,6 +708,26 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn empty_order_key_is_a_parse_error() {
+        for fields in [
+            serde_json::json!({ "CancelOrderEvent": { "order_key": "" } }),
+            serde_json::json!({
+                "TradeEvent": {
+                    "order_key": "",
+                    "making_amount": 1_u64,
+                    "taking_amount": 1_u64,
+                    "remaining_making_amount": 0_u64,
+                    "remaining_taking_amount": 0_u64
+                }
+            }),
+        ] {
+            let err = resolve(fields).unwrap().unwrap_err();
+            assert_eq!(err.code(), ErrorCode::MalformedPayload);
+            assert!(err.to_string().contains("a non-empty pubkey"), "{err}");
+        }
+    }
+
     #[test]
     fn resolve_trade_event_rejects_amount_overflow() {
         let fields = serde_json::json!({
@@ -447,6 +743,115 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn resolve_trade_event_saturation_is_opt_in() {
+        let fields = serde_json::json!({
+            "TradeEvent": {
+                "order_key": "order",
+                "making_amount": u64::MAX,
+                "taking_amount": 1_u64,
+                "remaining_making_amount": 0_u64,
+                "remaining_taking_amount": 0_u64
+            }
+        });
+        assert!(resolve(fields.clone()).unwrap().is_err());
+
+        let ctx = ResolveContext {
+            saturate_amounts: true,
+            ..ResolveContext::default()
+        };
+        let (_, _, payload) = LimitV2Adapter::resolve_fields(&fields, &ctx)
+            .unwrap()
+            .unwrap();
+        let EventPayload::LimitFill {
+            in_amount,
+            out_amount,
+            saturated,
+            ..
+        } = payload
+        else {
+            panic!("expected LimitFill");
+        };
+        assert_eq!((in_amount, out_amount), (i64::MAX, 1));
+        assert!(saturated);
+
+        let in_range = serde_json::json!({
+            "TradeEvent": {
+                "order_key": "order",
+                "making_amount": 5_u64,
+                "taking_amount": 1_u64,
+                "remaining_making_amount": 0_u64,
+                "remaining_taking_amount": 0_u64
+            }
+        });
+        let (_, _, payload) = LimitV2Adapter::resolve_fields(&in_range, &ctx)
+            .unwrap()
+            .unwrap();
+        assert!(matches!(
+            payload,
+            EventPayload::LimitFill {
+                saturated: false,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn resolve_trade_event_clamps_wrapped_remaining_to_zero() {
+        let fields = serde_json::json!({
+            "TradeEvent": {
+                "order_key": "order",
+                "making_amount": 400_u64,
+                "taking_amount": 200_u64,
+                "remaining_making_amount": u64::MAX,
+                "remaining_taking_amount": 0_u64
+            }
+        });
+        assert!(resolve(fields.clone()).unwrap().is_err());
+
+        let ctx = ResolveContext {
+            order_original_making_amount: Some(1_000),
+            ..ResolveContext::default()
+        };
+        let (_, _, payload) = LimitV2Adapter::resolve_fields(&fields, &ctx)
+            .unwrap()
+            .unwrap();
+        let EventPayload::LimitFill {
+            in_amount,
+            remaining_in_amount,
+            remaining_anomaly,
+            saturated,
+            ..
+        } = payload
+        else {
+            panic!("expected LimitFill");
+        };
+        assert_eq!((in_amount, remaining_in_amount), (400, 0));
+        assert!(remaining_anomaly);
+        assert!(!saturated);
+
+        let plausible = serde_json::json!({
+            "TradeEvent": {
+                "order_key": "order",
+                "making_amount": 400_u64,
+                "taking_amount": 200_u64,
+                "remaining_making_amount": 600_u64,
+                "remaining_taking_amount": 300_u64
+            }
+        });
+        let (_, _, payload) = LimitV2Adapter::resolve_fields(&plausible, &ctx)
+            .unwrap()
+            .unwrap();
+        assert!(matches!(
+            payload,
+            EventPayload::LimitFill {
+                remaining_in_amount: 600,
+                remaining_anomaly: false,
+                ..
+            }
+        ));
+    }
+
     #[test]
     fn resolve_trade_event_defaults_missing_taker() {
         let fields = serde_json::json!({
@@ -465,6 +870,47 @@ mod tests {
         assert_eq!(counterparty, "unknown");
     }
 
+    #[test]
+    fn resolve_event_strict_rejects_v1_field_names() {
+        let ctx = ResolveContext::default();
+        let v1_fields = serde_json::json!({
+            "TradeEvent": {
+                "order_key": "order",
+                "in_amount": 10_u64,
+                "out_amount": 5_u64,
+                "remaining_in_amount": 1_u64,
+                "remaining_out_amount": 0_u64
+            }
+        });
+        let err = LimitV2Adapter
+            .resolve_event_strict(&make_event(v1_fields), &ctx)
+            .unwrap()
+            .unwrap_err();
+        let Error::Protocol { reason, .. } = err else {
+            panic!("expected protocol error");
+        };
+        assert_eq!(
+            reason,
+            "Limit v2 TradeEvent carries Limit v1 field `in_amount`"
+        );
+
+        let v2_fields = serde_json::json!({
+            "TradeEvent": {
+                "order_key": "order",
+                "making_amount": 10_u64,
+                "taking_amount": 5_u64,
+                "remaining_making_amount": 1_u64,
+                "remaining_taking_amount": 0_u64
+            }
+        });
+        assert!(
+            LimitV2Adapter
+                .resolve_event_strict(&make_event(v2_fields), &ctx)
+                .unwrap()
+                .is_ok()
+        );
+    }
+
     #[test]
     fn unknown_event_returns_none() {
         let fields = serde_json::json!({"UnknownEvent": {"some_field": 1}});
@@ -533,6 +979,18 @@ mod tests {
         assert!(LimitV2Adapter::parse_create_args(&args).is_err());
     }
 
+    #[test]
+    fn parse_create_args_unwraps_params_wrapper_inside_data_envelope() {
+        let wrapped = serde_json::json!({
+            "data": {
+                "params": { "making_amount": 3000_u64, "taking_amount": 1500_u64, "fee_bps": 10_u16 }
+            }
+        });
+        let parsed = LimitV2Adapter::parse_create_args(&wrapped).unwrap();
+        assert_eq!(parsed.making_amount, 3000);
+        assert_eq!(parsed.fee_bps, Some(10));
+    }
+
     #[test]
     fn extract_order_pda_prefers_named_account() {
         let accounts = vec![
@@ -566,7 +1024,7 @@ mod tests {
     #[test]
     fn extract_order_pda_rejects_unknown_instruction() {
         let err = LimitV2Adapter::extract_order_pda(&[], "Unknown").unwrap_err();
-        let Error::Protocol { reason } = err else {
+        let Error::Protocol { reason, .. } = err else {
             panic!("expected protocol error");
         };
         assert_eq!(reason, "unknown Limit v2 instruction: Unknown");
@@ -575,7 +1033,7 @@ mod tests {
     #[test]
     fn extract_order_pda_rejects_out_of_bounds_index() {
         let err = LimitV2Adapter::extract_order_pda(&[], "CancelOrder").unwrap_err();
-        let Error::Protocol { reason } = err else {
+        let Error::Protocol { reason, .. } = err else {
             panic!("expected protocol error");
         };
         assert_eq!(
@@ -620,7 +1078,7 @@ mod tests {
         let err = LimitV2Adapter::extract_create_mints(&[])
             .err()
             .expect("expected error");
-        let Error::Protocol { reason } = err else {
+        let Error::Protocol { reason, .. } = err else {
             panic!("expected protocol error");
         };
         assert_eq!(reason, "Limit v2 input_mint index 7 out of bounds");
@@ -641,7 +1099,7 @@ mod tests {
         let err = LimitV2Adapter::extract_create_mints(&accounts)
             .err()
             .expect("expected error");
-        let Error::Protocol { reason } = err else {
+        let Error::Protocol { reason, .. } = err else {
             panic!("expected protocol error");
         };
         assert_eq!(reason, "Limit v2 output_mint index 8 out of bounds");
@@ -651,18 +1109,7 @@ mod tests {
     #[test]
     fn instruction_constants_match_classify() {
         for (name, expected) in INSTRUCTION_EVENT_TYPES {
-            let ix = RawInstruction {
-                id: 1,
-                signature: "sig".to_string(),
-                instruction_index: 0,
-                instruction_path: None,
-                program_id: "p".to_string(),
-                inner_program_id: "p".to_string(),
-                instruction_name: name.to_string(),
-                accounts: None,
-                args: None,
-                slot: 1,
-            };
+            let ix = RawInstructionBuilder::new(*name).build();
             assert_eq!(
                 LimitV2Adapter.classify_instruction(&ix).as_ref(),
                 Some(expected),
@@ -2,25 +2,51 @@ pub mod dca;
 pub mod kamino;
 pub mod limit_v1;
 pub mod limit_v2;
+pub mod noop;
+pub mod va;
+
+#[cfg(all(test, feature = "native"))]
+mod decoder_parity;
+
+use std::borrow::Cow;
+use std::collections::HashSet;
 
 use serde::{Deserialize, Serialize};
+use strum::VariantNames;
 
-use crate::error::Error;
+use crate::error::{Error, ErrorCode};
+use crate::lifecycle::adapters::{InstructionArgs, InstructionOutcome};
+use crate::types::{RawEvent, RawInstruction, ResolveContext};
 
-#[cfg(feature = "wasm")]
 pub const DCA_PROGRAM_ID: &str = "DCA265Vj8a9CEuX1eb1LWRnDT7uK6q1xMipnNyatn23M";
-#[cfg(feature = "wasm")]
 pub const LIMIT_V1_PROGRAM_ID: &str = "jupoNjAxXgZ4rjzxzPMP4oxduvQsQtZzyknqvzYNrNu";
-#[cfg(feature = "wasm")]
 pub const LIMIT_V2_PROGRAM_ID: &str = "j1o2qRpjcyUwEvwtcfhEQefh773ZgjxcVRry7LDqg5X";
-#[cfg(feature = "wasm")]
 pub const KAMINO_PROGRAM_ID: &str = "LiMoM9rMhrdYrfzUCxQppvxCSG1FcrUK9G8uLq4A1GF";
+pub const VA_PROGRAM_ID: &str = "VALaaymxQh2mNy2trH9jUqHT1mTow76KQWBtLHxDPkG";
+
+/// Largest timestamp (or duration, in seconds) [`ProtocolHelpers::checked_timestamp`] accepts:
+/// 9999-12-31T23:59:59Z.
+pub const MAX_PLAUSIBLE_TIMESTAMP: i64 = 253_402_300_799;
 
 /// Supported DeFi protocols.
+///
+/// Parses from and displays as its snake_case id (`"dca"`, `"limit_v1"`, ...); `"unknown"` does
+/// not parse. Ordering follows declaration order.
 #[derive(
-    Debug, Clone, Copy, PartialEq, Eq, Serialize, strum_macros::Display, strum_macros::AsRefStr,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Serialize,
+    strum_macros::Display,
+    strum_macros::AsRefStr,
 )]
 #[strum(serialize_all = "snake_case")]
+#[non_exhaustive]
 pub enum Protocol {
     /// Jupiter Dollar-Cost Averaging.
     Dca,
@@ -30,9 +56,193 @@ pub enum Protocol {
     LimitV2,
     /// Kamino Limit Order.
     Kamino,
+    /// Jupiter Value Averaging.
+    ValueAveraging,
+    /// Sentinel for unsupported programs, served by [`noop::NoOpAdapter`].
+    ///
+    /// Not part of [`Protocol::ALL`], never returned by [`Protocol::from_program_id`] or parsed
+    /// from a string, and has no program id.
+    Unknown,
+}
+
+impl std::str::FromStr for Protocol {
+    type Err = strum::ParseError;
+
+    /// Parses the snake_case id of a protocol in [`Protocol::ALL`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|protocol| protocol.as_ref() == s)
+            .ok_or(strum::ParseError::VariantNotFound)
+    }
+}
+
+/// Fails compilation when a Carbon decoder's `PROGRAM_ID` drifts from the string constants that
+/// builds without the `native` feature rely on.
+#[cfg(feature = "native")]
+const _: () = {
+    const fn matches(carbon: solana_pubkey::Pubkey, hardcoded: &str) -> bool {
+        let carbon = carbon.to_bytes();
+        let hardcoded = solana_pubkey::Pubkey::from_str_const(hardcoded).to_bytes();
+        let mut i = 0;
+        while i < carbon.len() {
+            if carbon[i] != hardcoded[i] {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+    assert!(
+        matches(carbon_jupiter_dca_decoder::PROGRAM_ID, DCA_PROGRAM_ID),
+        "DCA_PROGRAM_ID differs from carbon_jupiter_dca_decoder::PROGRAM_ID"
+    );
+    assert!(
+        matches(
+            carbon_jupiter_limit_order_decoder::PROGRAM_ID,
+            LIMIT_V1_PROGRAM_ID
+        ),
+        "LIMIT_V1_PROGRAM_ID differs from carbon_jupiter_limit_order_decoder::PROGRAM_ID"
+    );
+    assert!(
+        matches(
+            carbon_jupiter_limit_order_2_decoder::PROGRAM_ID,
+            LIMIT_V2_PROGRAM_ID
+        ),
+        "LIMIT_V2_PROGRAM_ID differs from carbon_jupiter_limit_order_2_decoder::PROGRAM_ID"
+    );
+    assert!(
+        matches(
+            carbon_kamino_limit_order_decoder::PROGRAM_ID,
+            KAMINO_PROGRAM_ID
+        ),
+        "KAMINO_PROGRAM_ID differs from carbon_kamino_limit_order_decoder::PROGRAM_ID"
+    );
+};
+
+/// No Carbon decoder exists for Value Averaging, so its pubkey is parsed from the string constant.
+#[cfg(feature = "native")]
+const VA_PROGRAM_PUBKEY: solana_pubkey::Pubkey =
+    solana_pubkey::Pubkey::from_str_const(VA_PROGRAM_ID);
+
+/// On-chain program id of a built-in [`Protocol`].
+///
+/// Exposes the base58 string on every build and the [`solana_pubkey::Pubkey`] on native builds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProgramId {
+    base58: &'static str,
+    #[cfg(feature = "native")]
+    pubkey: solana_pubkey::Pubkey,
+}
+
+impl ProgramId {
+    /// Program id of `protocol`, or `None` for [`Protocol::Unknown`].
+    pub fn for_protocol(protocol: Protocol) -> Option<Self> {
+        let base58 = protocol.program_id_str()?;
+        #[cfg(feature = "native")]
+        let pubkey = match protocol {
+            Protocol::Dca => carbon_jupiter_dca_decoder::PROGRAM_ID,
+            Protocol::LimitV1 => carbon_jupiter_limit_order_decoder::PROGRAM_ID,
+            Protocol::LimitV2 => carbon_jupiter_limit_order_2_decoder::PROGRAM_ID,
+            Protocol::Kamino => carbon_kamino_limit_order_decoder::PROGRAM_ID,
+            Protocol::ValueAveraging => VA_PROGRAM_PUBKEY,
+            Protocol::Unknown => return None,
+        };
+        Some(Self {
+            base58,
+            #[cfg(feature = "native")]
+            pubkey,
+        })
+    }
+
+    /// Base58-encoded program id.
+    pub fn as_str(&self) -> &'static str {
+        self.base58
+    }
+
+    /// Program id as a [`solana_pubkey::Pubkey`].
+    #[cfg(feature = "native")]
+    pub fn as_pubkey(&self) -> solana_pubkey::Pubkey {
+        self.pubkey
+    }
+}
+
+impl std::fmt::Display for ProgramId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Set of supported program ids for pre-routing rows before classification.
+///
+/// Matches base58 strings on every build (the native Carbon ids are asserted equal to the
+/// string constants at compile time), so wasm consumers can filter the same way.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProgramIdFilter {
+    ids: HashSet<&'static str>,
+}
+
+impl ProgramIdFilter {
+    /// Filter accepting the program id of every protocol in [`Protocol::ALL`].
+    pub fn new() -> Self {
+        Self {
+            ids: Protocol::ALL
+                .iter()
+                .filter_map(Protocol::program_id_str)
+                .collect(),
+        }
+    }
+
+    /// Whether `program_id` (base58) belongs to a supported protocol.
+    pub fn contains(&self, program_id: &str) -> bool {
+        self.ids.contains(program_id)
+    }
+}
+
+impl Default for ProgramIdFilter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Which on-chain data a protocol's lifecycle is best reconstructed from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum_macros::Display, strum_macros::AsRefStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum DataSource {
+    /// Program events carry the full lifecycle, including order PDAs.
+    Events,
+    /// Instructions are authoritative; events lack correlation data on their own.
+    Instructions,
+    /// Events cover most of the lifecycle, but some transitions are instruction-only.
+    Both,
 }
 
 impl Protocol {
+    /// Every built-in protocol in declaration order.
+    pub const ALL: &'static [Protocol] = &[
+        Self::Dca,
+        Self::LimitV1,
+        Self::LimitV2,
+        Self::Kamino,
+        Self::ValueAveraging,
+    ];
+
+    /// Preferred data source for reconstructing this protocol's order lifecycle.
+    ///
+    /// - DCA / Value Averaging: every lifecycle step emits an event keyed by the order account.
+    /// - Limit v1: events cover create/fill/cancel, but expiry is only visible via `CancelExpiredOrder`.
+    /// - Limit v2: events cover create/fill/cancel.
+    /// - Kamino: `OrderDisplayEvent` has no order PDA, so instructions must be parsed first
+    ///   to populate [`ResolveContext::pre_fetched_order_pdas`](crate::types::ResolveContext).
+    pub fn primary_source(&self) -> DataSource {
+        match self {
+            Self::Dca | Self::LimitV2 | Self::ValueAveraging | Self::Unknown => DataSource::Events,
+            Self::LimitV1 => DataSource::Both,
+            Self::Kamino => DataSource::Instructions,
+        }
+    }
+
     /// Resolves a base58 program id string to its [`Protocol`], or `None` if unrecognised.
     #[cfg(feature = "native")]
     pub fn from_program_id(program_id: &str) -> Option<Self> {
@@ -42,6 +252,7 @@ impl Protocol {
             carbon_jupiter_limit_order_decoder::PROGRAM_ID => Some(Self::LimitV1),
             carbon_jupiter_limit_order_2_decoder::PROGRAM_ID => Some(Self::LimitV2),
             carbon_kamino_limit_order_decoder::PROGRAM_ID => Some(Self::Kamino),
+            VA_PROGRAM_PUBKEY => Some(Self::ValueAveraging),
             _ => None,
         }
     }
@@ -53,35 +264,211 @@ impl Protocol {
             LIMIT_V1_PROGRAM_ID => Some(Self::LimitV1),
             LIMIT_V2_PROGRAM_ID => Some(Self::LimitV2),
             KAMINO_PROGRAM_ID => Some(Self::Kamino),
+            VA_PROGRAM_ID => Some(Self::ValueAveraging),
             _ => None,
         }
     }
 
     /// Returns the on-chain program id for every supported protocol.
     #[cfg(feature = "native")]
-    pub fn all_program_ids() -> [solana_pubkey::Pubkey; 4] {
+    pub fn all_program_ids() -> [solana_pubkey::Pubkey; 5] {
         [
             carbon_jupiter_dca_decoder::PROGRAM_ID,
             carbon_jupiter_limit_order_decoder::PROGRAM_ID,
             carbon_jupiter_limit_order_2_decoder::PROGRAM_ID,
             carbon_kamino_limit_order_decoder::PROGRAM_ID,
+            VA_PROGRAM_PUBKEY,
         ]
     }
 
-    #[cfg(feature = "wasm")]
-    pub fn program_id_str(&self) -> &'static str {
+    /// Every [`EventType`] this protocol's adapter can produce, including types an event only
+    /// resolves to under some [`ResolveContext`], in declaration order.
+    pub fn possible_event_types(&self) -> &'static [EventType] {
         match self {
-            Self::Dca => DCA_PROGRAM_ID,
-            Self::LimitV1 => LIMIT_V1_PROGRAM_ID,
-            Self::LimitV2 => LIMIT_V2_PROGRAM_ID,
-            Self::Kamino => KAMINO_PROGRAM_ID,
+            Self::Dca => dca::POSSIBLE_EVENT_TYPES,
+            Self::LimitV1 => limit_v1::POSSIBLE_EVENT_TYPES,
+            Self::LimitV2 => limit_v2::POSSIBLE_EVENT_TYPES,
+            Self::Kamino => kamino::POSSIBLE_EVENT_TYPES,
+            Self::ValueAveraging => va::POSSIBLE_EVENT_TYPES,
+            Self::Unknown => &[],
+        }
+    }
+
+    /// Base58-encoded on-chain program id, or `None` for [`Protocol::Unknown`].
+    pub fn program_id_str(&self) -> Option<&'static str> {
+        match self {
+            Self::Dca => Some(DCA_PROGRAM_ID),
+            Self::LimitV1 => Some(LIMIT_V1_PROGRAM_ID),
+            Self::LimitV2 => Some(LIMIT_V2_PROGRAM_ID),
+            Self::Kamino => Some(KAMINO_PROGRAM_ID),
+            Self::ValueAveraging => Some(VA_PROGRAM_ID),
+            Self::Unknown => None,
+        }
+    }
+
+    /// On-chain program id as raw 32 bytes, or `None` for [`Protocol::Unknown`].
+    #[cfg(feature = "native")]
+    pub fn program_id_bytes(&self) -> Option<[u8; 32]> {
+        ProgramId::for_protocol(*self).map(|id| id.as_pubkey().to_bytes())
+    }
+
+    /// On-chain program id as raw 32 bytes, decoded from base58 at compile time, or `None` for
+    /// [`Protocol::Unknown`].
+    #[cfg(not(feature = "native"))]
+    pub fn program_id_bytes(&self) -> Option<[u8; 32]> {
+        match self {
+            Self::Dca => Some(const { decode_base58_32(DCA_PROGRAM_ID) }),
+            Self::LimitV1 => Some(const { decode_base58_32(LIMIT_V1_PROGRAM_ID) }),
+            Self::LimitV2 => Some(const { decode_base58_32(LIMIT_V2_PROGRAM_ID) }),
+            Self::Kamino => Some(const { decode_base58_32(KAMINO_PROGRAM_ID) }),
+            Self::ValueAveraging => Some(const { decode_base58_32(VA_PROGRAM_ID) }),
+            Self::Unknown => None,
         }
     }
 }
 
+/// Decodes a base58 string into a big-endian 32-byte array.
+///
+/// Only called in `const` contexts, so an invalid character or an oversized value fails the
+/// build instead of panicking at runtime.
+#[cfg(any(test, not(feature = "native")))]
+const fn decode_base58_32(encoded: &str) -> [u8; 32] {
+    const BASE58_ALPHABET: &[u8; 58] =
+        b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+    let input = encoded.as_bytes();
+    let mut out = [0_u8; 32];
+    let mut i = 0;
+    while i < input.len() {
+        let mut digit = 0;
+        while digit < BASE58_ALPHABET.len() && BASE58_ALPHABET[digit] != input[i] {
+            digit += 1;
+        }
+        assert!(digit < BASE58_ALPHABET.len(), "invalid base58 character");
+        let mut carry = digit as u32;
+        let mut j = out.len();
+        while j > 0 {
+            j -= 1;
+            carry += out[j] as u32 * 58;
+            out[j] = (carry & 0xff) as u8;
+            carry >>= 8;
+        }
+        assert!(carry == 0, "base58 value exceeds 32 bytes");
+        i += 1;
+    }
+    out
+}
+
+/// Whether `name` is an event envelope variant recognised by `protocol`'s adapter.
+///
+/// Backed by per-protocol compile-time `phf` sets, so the lookup is constant-time.
+pub fn is_known_variant(protocol: Protocol, name: &str) -> bool {
+    let variants = match protocol {
+        Protocol::Dca => &dca::EVENT_VARIANTS,
+        Protocol::LimitV1 => &limit_v1::EVENT_VARIANTS,
+        Protocol::LimitV2 => &limit_v2::EVENT_VARIANTS,
+        Protocol::Kamino => &kamino::EVENT_VARIANTS,
+        Protocol::ValueAveraging => &va::EVENT_VARIANTS,
+        Protocol::Unknown => &noop::EVENT_VARIANTS,
+    };
+    variants.contains(name)
+}
+
+/// Every known instruction of every built-in protocol, with the [`EventType`] it classifies as.
+///
+/// Instructions that classification ignores (config, fee, and balance operations) map to `None`.
+pub fn instruction_mappings() -> Vec<(Protocol, &'static str, Option<EventType>)> {
+    Protocol::ALL
+        .iter()
+        .flat_map(|&protocol| {
+            let (names, table): (&[&'static str], _) = match protocol {
+                Protocol::Dca => (
+                    dca::DcaInstructionKind::VARIANTS,
+                    dca::INSTRUCTION_EVENT_TYPES,
+                ),
+                Protocol::LimitV1 => (
+                    limit_v1::LimitV1InstructionKind::VARIANTS,
+                    limit_v1::INSTRUCTION_EVENT_TYPES,
+                ),
+                Protocol::LimitV2 => (
+                    limit_v2::LimitV2InstructionKind::VARIANTS,
+                    limit_v2::INSTRUCTION_EVENT_TYPES,
+                ),
+                Protocol::Kamino => (
+                    kamino::KaminoInstructionKind::VARIANTS,
+                    kamino::INSTRUCTION_EVENT_TYPES,
+                ),
+                Protocol::ValueAveraging => {
+                    (va::VaInstructionKind::VARIANTS, va::INSTRUCTION_EVENT_TYPES)
+                }
+                Protocol::Unknown => (&[], &[]),
+            };
+            names.iter().map(move |&name| {
+                (
+                    protocol,
+                    name,
+                    ProtocolHelpers::lookup_event_type(name, table),
+                )
+            })
+        })
+        .collect()
+}
+
+/// Name of the [`crate::EventPayload`] variant `event_name` resolves to under `protocol`, or
+/// `None` if the protocol has no such event.
+///
+/// Complements the event → [`EventType`] tables for schema docs. The kind is that of a complete,
+/// correlated payload; see each protocol's `EVENT_PAYLOAD_KINDS` for the fallbacks.
+pub fn expected_payload_kind(protocol: Protocol, event_name: &str) -> Option<&'static str> {
+    let table: &[(&str, &'static str)] = match protocol {
+        Protocol::Dca => dca::EVENT_PAYLOAD_KINDS,
+        Protocol::LimitV1 => limit_v1::EVENT_PAYLOAD_KINDS,
+        Protocol::LimitV2 => limit_v2::EVENT_PAYLOAD_KINDS,
+        Protocol::Kamino => kamino::EVENT_PAYLOAD_KINDS,
+        Protocol::ValueAveraging => va::EVENT_PAYLOAD_KINDS,
+        Protocol::Unknown => &[],
+    };
+    table
+        .iter()
+        .find_map(|(name, kind)| (*name == event_name).then_some(*kind))
+}
+
+/// Instruction names that classify as `event_type` under `protocol`, in table order; the
+/// reverse of [`crate::ProtocolAdapter::classify_instruction`].
+pub fn instruction_names_for(protocol: Protocol, event_type: EventType) -> Vec<&'static str> {
+    let table: &[(&'static str, EventType)] = match protocol {
+        Protocol::Dca => dca::INSTRUCTION_EVENT_TYPES,
+        Protocol::LimitV1 => limit_v1::INSTRUCTION_EVENT_TYPES,
+        Protocol::LimitV2 => limit_v2::INSTRUCTION_EVENT_TYPES,
+        Protocol::Kamino => kamino::INSTRUCTION_EVENT_TYPES,
+        Protocol::ValueAveraging => va::INSTRUCTION_EVENT_TYPES,
+        Protocol::Unknown => &[],
+    };
+    table
+        .iter()
+        .filter_map(|&(name, mapped)| (mapped == event_type).then_some(name))
+        .collect()
+}
+
 /// Canonical event classification shared across all protocols.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, strum_macros::Display, strum_macros::AsRefStr)]
+///
+/// Ordering follows declaration order, which is also the `binary` encoding: append, never reorder.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    strum_macros::Display,
+    strum_macros::AsRefStr,
+)]
+#[serde(rename_all = "snake_case")]
 #[strum(serialize_all = "snake_case")]
+#[non_exhaustive]
 pub enum EventType {
     /// Order was created on-chain.
     Created,
@@ -101,27 +488,145 @@ pub enum EventType {
     Withdrawn,
     /// Funds were deposited into the order.
     Deposited,
+    /// Informational signal with no effect on order state (e.g. Kamino swap balance logs).
+    Diagnostic,
+}
+
+impl EventType {
+    /// Every variant in declaration order.
+    ///
+    /// `EventType` is `#[non_exhaustive]`, so downstream code cannot match it exhaustively;
+    /// iterate this instead when every variant must be covered.
+    pub const ALL: &'static [EventType] = &[
+        Self::Created,
+        Self::FillInitiated,
+        Self::FillCompleted,
+        Self::Cancelled,
+        Self::Expired,
+        Self::Closed,
+        Self::FeeCollected,
+        Self::Withdrawn,
+        Self::Deposited,
+        Self::Diagnostic,
+    ];
+}
+
+/// Broad category of an instruction, distinguishing deliberate non-lifecycle operations from
+/// instructions the adapter does not know.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum InstructionCategory {
+    /// Affects an order's lifecycle; carries the [`EventType`] it classifies as.
+    OrderLifecycle(EventType),
+    /// Program or vault configuration (e.g. Kamino `InitializeVault`, Limit `UpdateFee`).
+    Config,
+    /// Protocol fee withdrawal not tied to an order's lifecycle.
+    Fee,
+    /// Deposit into or withdrawal from an open order's balance.
+    Balance,
+    /// Not an instruction of this protocol.
+    Unknown,
+}
+
+impl InstructionCategory {
+    /// The [`EventType`] of an [`Self::OrderLifecycle`] instruction, else `None`.
+    pub fn event_type(&self) -> Option<EventType> {
+        match self {
+            Self::OrderLifecycle(event_type) => Some(*event_type),
+            Self::Config | Self::Fee | Self::Balance | Self::Unknown => None,
+        }
+    }
 }
 
 /// A single account entry from a decoded instruction's account list.
+///
+/// Deserializes from an object, or from a bare pubkey string (no signer/writable/name metadata).
 #[derive(Debug, Deserialize)]
+#[serde(from = "AccountEntry")]
 pub struct AccountInfo {
     /// Base58-encoded account public key.
     pub pubkey: String,
     /// Whether this account signed the transaction.
-    #[serde(default)]
     pub is_signer: bool,
     /// Whether this account was marked writable.
-    #[serde(default)]
     pub is_writable: bool,
     /// Optional IDL-derived account name (e.g. `"dca"`, `"order"`).
     pub name: Option<String>,
 }
 
+/// Serde intermediate accepting either account-list form.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AccountEntry {
+    Pubkey(String),
+    Object {
+        pubkey: String,
+        #[serde(default)]
+        is_signer: bool,
+        #[serde(default)]
+        is_writable: bool,
+        name: Option<String>,
+    },
+}
+
+impl From<AccountEntry> for AccountInfo {
+    fn from(entry: AccountEntry) -> Self {
+        match entry {
+            AccountEntry::Pubkey(pubkey) => Self {
+                pubkey,
+                is_signer: false,
+                is_writable: false,
+                name: None,
+            },
+            AccountEntry::Object {
+                pubkey,
+                is_signer,
+                is_writable,
+                name,
+            } => Self {
+                pubkey,
+                is_signer,
+                is_writable,
+                name,
+            },
+        }
+    }
+}
+
 /// Shared stateless helpers used across all protocol adapters.
 pub struct ProtocolHelpers;
 
 impl ProtocolHelpers {
+    /// Builds an [`InstructionOutcome`] for an already-classified instruction.
+    ///
+    /// The order PDA is extracted only when accounts are present; `parse_create` runs only
+    /// for [`EventType::Created`] instructions that carry args.
+    pub fn resolve_classified_instruction(
+        ix: &RawInstruction,
+        event_type: EventType,
+        extract_order_pda: fn(&[AccountInfo], &str) -> Result<String, Error>,
+        parse_create: fn(&serde_json::Value) -> Result<InstructionArgs, Error>,
+    ) -> Result<InstructionOutcome, Error> {
+        let order_pda = match ix.accounts.as_ref() {
+            Some(accounts_json) => {
+                let accounts = Self::parse_accounts(accounts_json)?;
+                Some(extract_order_pda(&accounts, &ix.instruction_name)?)
+            }
+            None => None,
+        };
+
+        let args = match (event_type, ix.args.as_ref()) {
+            (EventType::Created, Some(args)) => parse_create(args)?,
+            _ => InstructionArgs::None,
+        };
+
+        Ok(InstructionOutcome {
+            event_type,
+            order_pda,
+            args,
+        })
+    }
+
     /// Looks up an [`EventType`] by name from a static mapping table.
     pub fn lookup_event_type(
         name: &str,
@@ -132,9 +637,29 @@ impl ProtocolHelpers {
             .find_map(|(candidate, event_type)| (*candidate == name).then_some(*event_type))
     }
 
-    /// Deserializes a JSON array of accounts into [`AccountInfo`] structs.
+    /// Categorizes an instruction name: `lifecycle` entries become
+    /// [`InstructionCategory::OrderLifecycle`], then `categories` is consulted, else
+    /// [`InstructionCategory::Unknown`].
+    pub fn lookup_instruction_category(
+        name: &str,
+        lifecycle: &[(&'static str, EventType)],
+        categories: &[(&'static str, InstructionCategory)],
+    ) -> InstructionCategory {
+        Self::lookup_event_type(name, lifecycle)
+            .map(InstructionCategory::OrderLifecycle)
+            .or_else(|| {
+                categories
+                    .iter()
+                    .find_map(|(candidate, category)| (*candidate == name).then_some(*category))
+            })
+            .unwrap_or(InstructionCategory::Unknown)
+    }
+
+    /// Deserializes a JSON array of accounts (objects or bare pubkey strings) into
+    /// [`AccountInfo`] structs.
     pub fn parse_accounts(accounts_json: &serde_json::Value) -> Result<Vec<AccountInfo>, Error> {
         serde_json::from_value(accounts_json.clone()).map_err(|e| Error::Protocol {
+            code: ErrorCode::MalformedPayload,
             reason: format!("failed to parse accounts: {e}"),
         })
     }
@@ -155,20 +680,124 @@ impl ProtocolHelpers {
         accounts.iter().find(|a| a.name.as_deref() == Some(name))
     }
 
+    /// Returns the pubkey of the account named `name`, else of the account at `fallback_index`.
+    ///
+    /// `label` prefixes the out-of-bounds error (e.g. `"DCA input_mint index 2 out of bounds"`).
+    pub fn named_or_positional_account(
+        accounts: &[AccountInfo],
+        name: &str,
+        fallback_index: usize,
+        label: &str,
+    ) -> Result<String, Error> {
+        Self::find_account_by_name(accounts, name)
+            .or_else(|| accounts.get(fallback_index))
+            .map(|a| a.pubkey.clone())
+            .ok_or_else(|| Error::Protocol {
+                code: ErrorCode::OutOfBoundsAccount,
+                reason: format!("{label} {name} index {fallback_index} out of bounds"),
+            })
+    }
+
+    /// Checks that the account named `name`, if any, is the one at `expected_index`.
+    ///
+    /// Pass an adapter's `order_pda_index` (e.g. [`dca::DcaAdapter::order_pda_index`]) to catch
+    /// IDL layout drift that would make positional fallbacks resolve the wrong order PDA. Meant
+    /// for `debug_assert!`s and staging self-tests, not the hot path.
+    pub fn verify_order_pda_consistency(
+        accounts: &[AccountInfo],
+        name: &str,
+        expected_index: usize,
+        label: &str,
+        instruction_name: &str,
+    ) -> Result<(), Error> {
+        let Some(named) = Self::find_account_by_name(accounts, name) else {
+            return Ok(());
+        };
+        match accounts.get(expected_index) {
+            Some(positional) if positional.pubkey == named.pubkey => Ok(()),
+            positional => Err(Error::Protocol {
+                code: ErrorCode::MalformedPayload,
+                reason: format!(
+                    "{label} {instruction_name} account `{name}` is {} but index {expected_index} holds {}",
+                    named.pubkey,
+                    positional.map_or("nothing", |a| a.pubkey.as_str())
+                ),
+            }),
+        }
+    }
+
     /// Returns `true` if the JSON object's keys contain any of the `known_names`.
-    pub fn contains_known_variant(fields: &serde_json::Value, known_names: &[&str]) -> bool {
+    pub fn contains_known_variant(
+        fields: &serde_json::Value,
+        known_names: &phf::Set<&'static str>,
+    ) -> bool {
         fields
             .as_object()
-            .is_some_and(|obj| obj.keys().any(|name| known_names.contains(&name.as_str())))
+            .is_some_and(|obj| obj.keys().any(|name| known_names.contains(name.as_str())))
+    }
+
+    /// Deserializes instruction create args, retrying under a nested `data` key when the flat
+    /// parse fails (some indexer versions emit `{"data": {...}}`).
+    ///
+    /// Errors report the flat-parse failure, prefixed with `label`.
+    pub fn parse_create_fields<T: serde::de::DeserializeOwned>(
+        args: &serde_json::Value,
+        label: &str,
+    ) -> Result<T, Error> {
+        serde_json::from_value(args.clone()).or_else(|e| {
+            args.get("data")
+                .and_then(|data| serde_json::from_value(data.clone()).ok())
+                .ok_or_else(|| Error::Protocol {
+                    code: ErrorCode::MalformedPayload,
+                    reason: format!("failed to parse {label} create args: {e}"),
+                })
+        })
+    }
+
+    /// Returns `ev.fields` in `{"Variant": {...}}` form, or `None` when fields are absent.
+    ///
+    /// Wrapper formats are first reshaped by [`RawEvent::normalize_fields`]. Some sources also
+    /// strip the variant key and rely on `event_name`: when no key of the fields is one of
+    /// `known_names` but `event_name` is, the inner object is re-wrapped under `event_name`.
+    pub fn wrapped_event_fields<'a>(
+        ev: &'a RawEvent,
+        known_names: &phf::Set<&'static str>,
+    ) -> Option<Cow<'a, serde_json::Value>> {
+        let fields = ev.normalized_fields()?;
+        if !fields.is_object()
+            || Self::contains_known_variant(&fields, known_names)
+            || !known_names.contains(ev.event_name.as_str())
+        {
+            return Some(fields);
+        }
+        let mut wrapped = serde_json::Map::new();
+        wrapped.insert(ev.event_name.clone(), fields.into_owned());
+        Some(Cow::Owned(serde_json::Value::Object(wrapped)))
+    }
+
+    /// Returns the first of `names` present as a key of `fields[variant]`, if any.
+    pub fn find_present_field<'a>(
+        fields: &serde_json::Value,
+        variant: &str,
+        names: &[&'a str],
+    ) -> Option<&'a str> {
+        let inner = fields.get(variant)?.as_object()?;
+        names.iter().copied().find(|name| inner.contains_key(*name))
     }
 
     /// Converts `u64` to `i64`, returning an error if the value exceeds `i64::MAX`.
     pub fn checked_u64_to_i64(value: u64, field: &str) -> Result<i64, Error> {
-        i64::try_from(value).map_err(|_| Error::Protocol {
-            reason: format!("{field} exceeds i64::MAX: {value}"),
+        i64::try_from(value).map_err(|_| Error::AmountOverflow {
+            field: field.to_string(),
+            value,
         })
     }
 
+    /// Converts `u64` to `i64`, clamping values above `i64::MAX`.
+    pub fn saturating_u64_to_i64(value: u64) -> i64 {
+        i64::try_from(value).unwrap_or(i64::MAX)
+    }
+
     /// Converts `u64` to `i64` for optional fields.
     /// Returns `None` if the value exceeds `i64::MAX` (e.g. `u64::MAX` sentinel for "no limit").
     pub fn optional_u64_to_i64(value: u64) -> Option<i64> {
@@ -178,9 +807,115 @@ impl ProtocolHelpers {
     /// Converts `u16` to `i16`, returning an error if the value exceeds `i16::MAX`.
     pub fn checked_u16_to_i16(value: u16, field: &str) -> Result<i16, Error> {
         i16::try_from(value).map_err(|_| Error::Protocol {
+            code: ErrorCode::AmountOverflow,
             reason: format!("{field} exceeds i16::MAX: {value}"),
         })
     }
+
+    /// Rejects an implausible unix timestamp or duration in seconds: negative, or beyond
+    /// [`MAX_PLAUSIBLE_TIMESTAMP`].
+    pub fn checked_timestamp(value: i64, field: &str) -> Result<i64, Error> {
+        if (0..=MAX_PLAUSIBLE_TIMESTAMP).contains(&value) {
+            return Ok(value);
+        }
+        Err(Error::Protocol {
+            code: ErrorCode::MalformedPayload,
+            reason: format!("{field} is not a plausible timestamp: {value}"),
+        })
+    }
+}
+
+/// Casts the amounts of one event payload, checked or saturating per
+/// [`ResolveContext::saturate_amounts`], and remembers whether any value was clamped.
+pub(crate) struct AmountCaster {
+    saturate: bool,
+    saturated: bool,
+}
+
+impl AmountCaster {
+    pub(crate) fn new(ctx: &ResolveContext) -> Self {
+        Self {
+            saturate: ctx.saturate_amounts,
+            saturated: false,
+        }
+    }
+
+    pub(crate) fn cast(&mut self, value: u64, field: &str) -> Result<i64, Error> {
+        if !self.saturate {
+            return ProtocolHelpers::checked_u64_to_i64(value, field);
+        }
+        self.saturated |= value > i64::MAX.unsigned_abs();
+        Ok(ProtocolHelpers::saturating_u64_to_i64(value))
+    }
+
+    /// Whether any [`Self::cast`] clamped its value.
+    pub(crate) fn saturated(&self) -> bool {
+        self.saturated
+    }
+}
+
+/// Deserializes an order-key pubkey string, rejecting empty values.
+///
+/// Guards correlation keys (`dca_key`, `order_key`, `value_average`) against decoder bugs that
+/// would otherwise correlate to `""`.
+pub(crate) fn de_nonempty_pubkey<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = <String as serde::Deserialize>::deserialize(deserializer)?;
+    if value.trim().is_empty() {
+        return Err(serde::de::Error::invalid_value(
+            serde::de::Unexpected::Str(&value),
+            &"a non-empty pubkey",
+        ));
+    }
+    Ok(value)
+}
+
+/// Like [`de_u64_flexible`], but `null` yields `None`; pair with `#[serde(default)]` for absent fields.
+pub(crate) fn de_opt_u64_flexible<'de, D>(deserializer: D) -> Result<Option<u64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct Flexible(#[serde(deserialize_with = "de_u64_flexible")] u64);
+
+    Option::<Flexible>::deserialize(deserializer).map(|value| value.map(|Flexible(v)| v))
+}
+
+/// Deserializes a `u64` from either a JSON number or a decimal string.
+///
+/// Some decoders emit `u64` amounts as strings to avoid JS precision loss.
+pub(crate) fn de_u64_flexible<'de, D>(deserializer: D) -> Result<u64, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    struct FlexibleU64;
+
+    impl serde::de::Visitor<'_> for FlexibleU64 {
+        type Value = u64;
+
+        fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("a u64 number or decimal string")
+        }
+
+        fn visit_u64<E: serde::de::Error>(self, value: u64) -> Result<u64, E> {
+            Ok(value)
+        }
+
+        fn visit_i64<E: serde::de::Error>(self, value: i64) -> Result<u64, E> {
+            u64::try_from(value)
+                .map_err(|_| E::invalid_value(serde::de::Unexpected::Signed(value), &self))
+        }
+
+        fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<u64, E> {
+            value
+                .parse()
+                .map_err(|_| E::invalid_value(serde::de::Unexpected::Str(value), &self))
+        }
+    }
+
+    deserializer.deserialize_any(FlexibleU64)
 }
 
 #[cfg(test)]
@@ -188,9 +923,55 @@ impl ProtocolHelpers {
 mod tests {
     use super::*;
     use crate::lifecycle::adapters::{ProtocolAdapter, adapter_for};
+    use crate::test_util::{RawEventBuilder, RawInstructionBuilder};
     use crate::types::{RawEvent, RawInstruction, ResolveContext};
     use std::collections::HashSet;
 
+    #[test]
+    fn program_id_filter_accepts_only_supported_programs() {
+        let filter = ProgramIdFilter::new();
+        for id in [
+            DCA_PROGRAM_ID,
+            LIMIT_V1_PROGRAM_ID,
+            LIMIT_V2_PROGRAM_ID,
+            KAMINO_PROGRAM_ID,
+            VA_PROGRAM_ID,
+        ] {
+            assert!(filter.contains(id), "{id}");
+        }
+        assert!(!filter.contains("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"));
+        assert!(!filter.contains("11111111111111111111111111111111"));
+        assert!(!filter.contains(""));
+    }
+
+    #[cfg(feature = "native")]
+    #[test]
+    fn program_id_bytes_round_trip_to_base58() {
+        for protocol in Protocol::ALL {
+            let bytes = protocol.program_id_bytes().unwrap();
+            let base58 = protocol.program_id_str().unwrap();
+            assert_eq!(
+                solana_pubkey::Pubkey::new_from_array(bytes).to_string(),
+                base58,
+                "{protocol}"
+            );
+            assert_eq!(decode_base58_32(base58), bytes, "{protocol}");
+        }
+        assert_eq!(Protocol::Unknown.program_id_bytes(), None);
+    }
+
+    #[test]
+    fn protocol_parses_builtins_but_not_unknown() {
+        for protocol in Protocol::ALL {
+            assert_eq!(protocol.to_string().parse::<Protocol>(), Ok(*protocol));
+        }
+        assert_eq!(Protocol::Unknown.to_string(), "unknown");
+        assert!("unknown".parse::<Protocol>().is_err());
+        assert!("Dca".parse::<Protocol>().is_err());
+        assert_eq!(Protocol::Unknown.program_id_str(), None);
+        assert_eq!(ProgramId::for_protocol(Protocol::Unknown), None);
+    }
+
     #[cfg(feature = "native")]
     #[test]
     fn protocol_program_id_mapping_and_string_names_are_stable() {
@@ -215,6 +996,11 @@ mod tests {
                 Protocol::Kamino,
                 "kamino",
             ),
+            (
+                &VA_PROGRAM_PUBKEY,
+                Protocol::ValueAveraging,
+                "value_averaging",
+            ),
         ];
         for (program_id, expected_protocol, expected_name) in cases {
             assert_eq!(
@@ -233,11 +1019,12 @@ mod tests {
                 carbon_jupiter_limit_order_decoder::PROGRAM_ID,
                 carbon_jupiter_limit_order_2_decoder::PROGRAM_ID,
                 carbon_kamino_limit_order_decoder::PROGRAM_ID,
+                VA_PROGRAM_PUBKEY,
             ]
         );
     }
 
-    #[cfg(all(feature = "native", feature = "wasm"))]
+    #[cfg(feature = "native")]
     #[test]
     fn hardcoded_program_ids_match_carbon_constants() {
         assert_eq!(
@@ -258,7 +1045,7 @@ mod tests {
         );
     }
 
-    #[cfg(feature = "wasm")]
+    #[cfg(any(feature = "native", feature = "wasm"))]
     #[test]
     fn protocol_program_id_str_roundtrips() {
         for protocol in [
@@ -266,14 +1053,33 @@ mod tests {
             Protocol::LimitV1,
             Protocol::LimitV2,
             Protocol::Kamino,
+            Protocol::ValueAveraging,
         ] {
             assert_eq!(
-                Protocol::from_program_id(protocol.program_id_str()),
+                Protocol::from_program_id(protocol.program_id_str().unwrap()),
                 Some(protocol)
             );
         }
     }
 
+    #[test]
+    fn adapters_report_expected_program_ids() {
+        let cases = [
+            (Protocol::Dca, DCA_PROGRAM_ID),
+            (Protocol::LimitV1, LIMIT_V1_PROGRAM_ID),
+            (Protocol::LimitV2, LIMIT_V2_PROGRAM_ID),
+            (Protocol::Kamino, KAMINO_PROGRAM_ID),
+            (Protocol::ValueAveraging, VA_PROGRAM_ID),
+        ];
+        for (protocol, expected) in cases {
+            let program_id = adapter_for(protocol).program_id().unwrap();
+            assert_eq!(program_id.as_str(), expected);
+            assert_eq!(program_id.to_string(), expected);
+            #[cfg(feature = "native")]
+            assert_eq!(program_id.as_pubkey().to_string(), expected);
+        }
+    }
+
     #[test]
     fn event_type_strings_match_expected_labels() {
         let cases = [
@@ -286,6 +1092,7 @@ mod tests {
             (EventType::FeeCollected, "fee_collected"),
             (EventType::Withdrawn, "withdrawn"),
             (EventType::Deposited, "deposited"),
+            (EventType::Diagnostic, "diagnostic"),
         ];
         for (event_type, expected_label) in cases {
             assert_eq!(event_type.as_ref(), expected_label);
@@ -326,21 +1133,187 @@ mod tests {
         assert!(ProtocolHelpers::find_account_by_name(&parsed, "missing").is_none());
     }
 
+    #[test]
+    fn checked_timestamp_rejects_negative_and_far_future_values() {
+        assert_eq!(
+            ProtocolHelpers::checked_timestamp(1_700_000_000, "expired_at").unwrap(),
+            1_700_000_000
+        );
+        assert_eq!(
+            ProtocolHelpers::checked_timestamp(0, "start_at").unwrap(),
+            0
+        );
+
+        let err = ProtocolHelpers::checked_timestamp(i64::MIN, "expired_at").unwrap_err();
+        assert_eq!(err.code(), ErrorCode::MalformedPayload);
+        assert!(err.to_string().contains("expired_at"));
+        assert!(ProtocolHelpers::checked_timestamp(-1, "cycle_frequency").is_err());
+        assert!(
+            ProtocolHelpers::checked_timestamp(MAX_PLAUSIBLE_TIMESTAMP + 1, "expired_at").is_err()
+        );
+    }
+
+    #[test]
+    fn error_codes_classify_overflow_and_out_of_bounds() {
+        let overflow = ProtocolHelpers::checked_u64_to_i64(u64::MAX, "amount").unwrap_err();
+        assert_eq!(overflow.code(), ErrorCode::AmountOverflow);
+        assert_eq!(
+            overflow.to_string(),
+            format!("protocol error: amount exceeds i64::MAX: {}", u64::MAX)
+        );
+        let Error::AmountOverflow { field, value } = &overflow else {
+            panic!("expected amount overflow error");
+        };
+        assert_eq!(field, "amount");
+        // A caller can choose to clamp rather than reject.
+        assert_eq!(i64::try_from(*value).unwrap_or(i64::MAX), i64::MAX);
+        assert_eq!(overflow.code().as_ref(), "amount_overflow");
+
+        let out_of_bounds = [
+            dca::DcaAdapter::extract_order_pda(&[], "CloseDca"),
+            limit_v1::LimitV1Adapter::extract_order_pda(&[], "CancelOrder"),
+            limit_v2::LimitV2Adapter::extract_order_pda(&[], "CancelOrder"),
+            kamino::KaminoAdapter::extract_order_pda(&[], "CloseOrderAndClaimTip"),
+        ];
+        for result in out_of_bounds {
+            assert_eq!(result.unwrap_err().code(), ErrorCode::OutOfBoundsAccount);
+        }
+
+        let unknown = dca::DcaAdapter::extract_order_pda(&[], "Unknown").unwrap_err();
+        assert_eq!(unknown.code(), ErrorCode::UnknownInstruction);
+        assert_eq!(
+            Error::UnknownEvent {
+                event_name: "x".into()
+            }
+            .code(),
+            ErrorCode::UnknownEvent
+        );
+    }
+
+    #[test]
+    fn parse_accounts_accepts_bare_pubkey_strings() {
+        let parsed =
+            ProtocolHelpers::parse_accounts(&serde_json::json!(["order_pda", "user"])).unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert!(!parsed[1].is_signer && !parsed[1].is_writable);
+        assert_eq!(parsed[1].name, None);
+        assert_eq!(ProtocolHelpers::find_signer(&parsed), None);
+        assert_eq!(
+            dca::DcaAdapter::extract_order_pda(&parsed, "OpenDcaV2").unwrap(),
+            "order_pda"
+        );
+
+        let mixed = ProtocolHelpers::parse_accounts(&serde_json::json!([
+            "order_pda",
+            { "pubkey": "user", "is_signer": true }
+        ]))
+        .unwrap();
+        assert_eq!(ProtocolHelpers::find_signer(&mixed), Some("user"));
+    }
+
     #[test]
     fn parse_accounts_rejects_non_array() {
         let err = ProtocolHelpers::parse_accounts(&serde_json::json!({"pubkey": "not-an-array"}))
             .unwrap_err();
-        let Error::Protocol { reason } = err else {
+        let Error::Protocol { reason, .. } = err else {
             panic!("expected protocol error");
         };
         assert!(reason.contains("failed to parse accounts"), "{reason}");
     }
 
+    #[test]
+    fn named_or_positional_account_resolves_by_name_else_index() {
+        let accounts = ProtocolHelpers::parse_accounts(&serde_json::json!([
+            "positional_input",
+            "positional_output",
+            {"pubkey": "named_output", "name": "output_mint"}
+        ]))
+        .unwrap();
+        let cases = [
+            ("input_mint", 0, Ok("positional_input")),
+            ("output_mint", 1, Ok("named_output")),
+            ("output_mint", 9, Ok("named_output")),
+            ("input_mint", 9, Err("DCA input_mint index 9 out of bounds")),
+        ];
+        for (name, index, expected) in cases {
+            let resolved =
+                ProtocolHelpers::named_or_positional_account(&accounts, name, index, "DCA");
+            match expected {
+                Ok(pubkey) => assert_eq!(resolved.unwrap(), pubkey, "{name} @ {index}"),
+                Err(reason) => {
+                    let err = resolved.unwrap_err();
+                    assert_eq!(err.code(), ErrorCode::OutOfBoundsAccount);
+                    assert_eq!(err.to_string(), format!("protocol error: {reason}"));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn parse_create_fields_accepts_flat_and_data_envelope() {
+        #[derive(Debug, PartialEq, serde::Deserialize)]
+        struct Fields {
+            amount: u64,
+        }
+        let cases = [
+            (serde_json::json!({ "amount": 5 }), Some(5)),
+            (serde_json::json!({ "data": { "amount": 7 } }), Some(7)),
+            (serde_json::json!({ "data": { "amount": "bad" } }), None),
+            (serde_json::json!({ "other": 1 }), None),
+        ];
+        for (args, expected) in cases {
+            let parsed = ProtocolHelpers::parse_create_fields::<Fields>(&args, "Test");
+            if let Some(amount) = expected {
+                assert_eq!(parsed.unwrap(), Fields { amount }, "{args}");
+            } else {
+                let err = parsed.unwrap_err();
+                assert_eq!(err.code(), ErrorCode::MalformedPayload);
+                assert!(
+                    err.to_string().contains("failed to parse Test create args"),
+                    "{err}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn verify_order_pda_consistency_flags_layout_drift() {
+        let idx = dca::DcaAdapter::order_pda_index("CloseDca").unwrap();
+        let check = |accounts: serde_json::Value| {
+            let accounts = ProtocolHelpers::parse_accounts(&accounts).unwrap();
+            ProtocolHelpers::verify_order_pda_consistency(&accounts, "dca", idx, "DCA", "CloseDca")
+        };
+
+        let ok_cases = [
+            serde_json::json!(["idx0", {"pubkey": "named", "name": "dca"}]),
+            serde_json::json!(["idx0", "idx1"]),
+        ];
+        for accounts in ok_cases {
+            assert!(check(accounts.clone()).is_ok(), "{accounts}");
+        }
+
+        let drift_cases = [
+            (
+                serde_json::json!(["idx0", "idx1", {"pubkey": "named", "name": "dca"}]),
+                "protocol error: DCA CloseDca account `dca` is named but index 1 holds idx1",
+            ),
+            (
+                serde_json::json!([{"pubkey": "named", "name": "dca"}]),
+                "protocol error: DCA CloseDca account `dca` is named but index 1 holds nothing",
+            ),
+        ];
+        for (accounts, expected) in drift_cases {
+            let err = check(accounts).unwrap_err();
+            assert_eq!(err.code(), ErrorCode::MalformedPayload);
+            assert_eq!(err.to_string(), expected);
+        }
+    }
+
     #[test]
     fn parse_accounts_rejects_missing_pubkey() {
         let err =
             ProtocolHelpers::parse_accounts(&serde_json::json!([{"is_signer": true}])).unwrap_err();
-        let Error::Protocol { reason } = err else {
+        let Error::Protocol { reason, .. } = err else {
             panic!("expected protocol error");
         };
         assert!(reason.contains("failed to parse accounts"), "{reason}");
@@ -359,18 +1332,7 @@ mod tests {
     }
 
     fn make_ix(name: &str) -> RawInstruction {
-        RawInstruction {
-            id: 1,
-            signature: "sig".to_string(),
-            instruction_index: 0,
-            instruction_path: None,
-            program_id: "p".to_string(),
-            inner_program_id: "p".to_string(),
-            instruction_name: name.to_string(),
-            accounts: None,
-            args: None,
-            slot: 1,
-        }
+        RawInstructionBuilder::new(name).build()
     }
 
     fn collect_instruction_event_types(
@@ -385,17 +1347,7 @@ mod tests {
     }
 
     fn make_event(fields: serde_json::Value) -> RawEvent {
-        RawEvent {
-            id: 1,
-            signature: "sig".to_string(),
-            event_index: 0,
-            event_path: None,
-            program_id: "p".to_string(),
-            inner_program_id: "p".to_string(),
-            event_name: "test".to_string(),
-            fields: Some(fields),
-            slot: 1,
-        }
+        RawEventBuilder::default().fields(fields).build()
     }
 
     fn resolve_event_type(
@@ -409,12 +1361,340 @@ mod tests {
             .map(|(et, _, _)| et.as_ref().to_string())
     }
 
+    /// In-crate exhaustive match: adding an `EventType` variant fails to compile here
+    /// until it is given an ordinal and appended to [`EventType::ALL`].
+    fn event_type_ordinal(event_type: EventType) -> usize {
+        match event_type {
+            EventType::Created => 0,
+            EventType::FillInitiated => 1,
+            EventType::FillCompleted => 2,
+            EventType::Cancelled => 3,
+            EventType::Expired => 4,
+            EventType::Closed => 5,
+            EventType::FeeCollected => 6,
+            EventType::Withdrawn => 7,
+            EventType::Deposited => 8,
+            EventType::Diagnostic => 9,
+        }
+    }
+
+    #[test]
+    fn event_type_all_lists_every_variant_in_order() {
+        for (idx, event_type) in EventType::ALL.iter().enumerate() {
+            assert_eq!(event_type_ordinal(*event_type), idx, "{event_type}");
+        }
+        assert_eq!(
+            EventType::ALL.len(),
+            event_type_ordinal(EventType::Diagnostic) + 1
+        );
+    }
+
+    #[test]
+    fn event_types_and_protocols_are_usable_as_map_keys() {
+        use std::collections::{BTreeSet, HashSet};
+
+        let event_types: HashSet<EventType> = EventType::ALL.iter().copied().collect();
+        assert_eq!(event_types.len(), EventType::ALL.len());
+
+        let protocols: HashSet<Protocol> = Protocol::ALL.iter().copied().collect();
+        assert_eq!(protocols.len(), Protocol::ALL.len());
+        let ordered: Vec<Protocol> = Protocol::ALL
+            .iter()
+            .copied()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        assert_eq!(ordered, Protocol::ALL);
+    }
+
+    #[test]
+    fn known_variant_sets_match_event_envelopes() {
+        use strum::VariantNames;
+
+        assert!(is_known_variant(Protocol::Dca, "FilledEvent"));
+        assert!(!is_known_variant(Protocol::Dca, "MysteryEvent"));
+        assert!(!is_known_variant(Protocol::Dca, "TradeEvent"));
+
+        let envelopes: [(Protocol, &[&str]); 5] = [
+            (Protocol::Dca, dca::DcaEventEnvelope::VARIANTS),
+            (Protocol::LimitV1, limit_v1::LimitV1EventEnvelope::VARIANTS),
+            (Protocol::LimitV2, limit_v2::LimitV2EventEnvelope::VARIANTS),
+            (Protocol::Kamino, kamino::KaminoEventEnvelope::VARIANTS),
+            (Protocol::ValueAveraging, va::VaEventEnvelope::VARIANTS),
+        ];
+        for (protocol, variants) in envelopes {
+            for name in variants {
+                assert!(is_known_variant(protocol, name), "{protocol}: {name}");
+            }
+            let set_len = match protocol {
+                Protocol::Dca => dca::EVENT_VARIANTS.len(),
+                Protocol::LimitV1 => limit_v1::EVENT_VARIANTS.len(),
+                Protocol::LimitV2 => limit_v2::EVENT_VARIANTS.len(),
+                Protocol::Kamino => kamino::EVENT_VARIANTS.len(),
+                Protocol::ValueAveraging => va::EVENT_VARIANTS.len(),
+                Protocol::Unknown => noop::EVENT_VARIANTS.len(),
+            };
+            assert_eq!(set_len, variants.len(), "{protocol}");
+        }
+    }
+
+    #[test]
+    fn instruction_mappings_cover_unclassified_instructions() {
+        let mappings = instruction_mappings();
+        assert!(mappings.contains(&(Protocol::Dca, "WithdrawFees", None)));
+        assert!(mappings.contains(&(Protocol::Kamino, "CreateOrder", Some(EventType::Created))));
+
+        for (protocol, name, event_type) in mappings {
+            let ix = RawInstructionBuilder::new(name).build();
+            assert_eq!(
+                adapter_for(protocol).classify_instruction(&ix),
+                event_type,
+                "{protocol} {name}"
+            );
+        }
+    }
+
+    #[test]
+    fn expected_payload_kind_matches_resolved_payloads() {
+        let ctx = ResolveContext::default();
+        for (name, fields) in [
+            (
+                "FilledEvent",
+                serde_json::json!({ "dca_key": "pda", "in_amount": 10, "out_amount": 4 }),
+            ),
+            (
+                "OpenedEvent",
+                serde_json::json!({
+                    "dca_key": "pda",
+                    "cycle_frequency": 60,
+                    "in_amount_per_cycle": 100,
+                    "in_deposited": 1_000
+                }),
+            ),
+        ] {
+            let ev = RawEventBuilder::new(name)
+                .fields(serde_json::json!({ name: fields }))
+                .build();
+            let (_, _, payload) = dca::DcaAdapter
+                .classify_and_resolve_event(&ev, &ctx)
+                .unwrap()
+                .unwrap();
+            assert_eq!(
+                expected_payload_kind(Protocol::Dca, name),
+                Some(payload.kind())
+            );
+        }
+        assert_eq!(
+            expected_payload_kind(Protocol::Dca, "FilledEvent"),
+            Some("DcaFill")
+        );
+        assert_eq!(
+            expected_payload_kind(Protocol::LimitV2, "CreateOrderEvent"),
+            Some("None")
+        );
+        assert_eq!(expected_payload_kind(Protocol::Dca, "TradeEvent"), None);
+    }
+
+    #[test]
+    fn instruction_names_for_reverses_classification() {
+        assert_eq!(
+            instruction_names_for(Protocol::Dca, EventType::Created),
+            ["OpenDca", "OpenDcaV2"]
+        );
+        assert_eq!(
+            instruction_names_for(Protocol::Dca, EventType::FillCompleted),
+            ["FulfillFlashFill", "FulfillDlmmFill"]
+        );
+        assert!(instruction_names_for(Protocol::Dca, EventType::Expired).is_empty());
+        assert!(instruction_names_for(Protocol::Unknown, EventType::Created).is_empty());
+
+        for (protocol, name, event_type) in instruction_mappings() {
+            if let Some(event_type) = event_type {
+                assert!(
+                    instruction_names_for(protocol, event_type).contains(&name),
+                    "{protocol} {name}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn payload_kind_tables_cover_every_event() {
+        for (kinds, event_types) in [
+            (dca::EVENT_PAYLOAD_KINDS, dca::EVENT_EVENT_TYPES),
+            (limit_v1::EVENT_PAYLOAD_KINDS, limit_v1::EVENT_EVENT_TYPES),
+            (limit_v2::EVENT_PAYLOAD_KINDS, limit_v2::EVENT_EVENT_TYPES),
+            (kamino::EVENT_PAYLOAD_KINDS, kamino::EVENT_EVENT_TYPES),
+            (va::EVENT_PAYLOAD_KINDS, va::EVENT_EVENT_TYPES),
+        ] {
+            let kind_names: Vec<_> = kinds.iter().map(|(name, _)| *name).collect();
+            let event_names: Vec<_> = event_types.iter().map(|(name, _)| *name).collect();
+            assert_eq!(kind_names, event_names);
+        }
+    }
+
+    #[test]
+    fn every_known_instruction_has_a_category() {
+        for (protocol, name, event_type) in instruction_mappings() {
+            let ix = RawInstructionBuilder::new(name).build();
+            let category = adapter_for(protocol).classify_instruction_category(&ix);
+            assert_ne!(category, InstructionCategory::Unknown, "{protocol} {name}");
+            assert_eq!(category.event_type(), event_type, "{protocol} {name}");
+        }
+        let ix = RawInstructionBuilder::new("CreateOrder").build();
+        assert_eq!(
+            adapter_for(Protocol::Unknown).classify_instruction_category(&ix),
+            InstructionCategory::Unknown
+        );
+    }
+
+    #[test]
+    fn wrapped_event_fields_rewraps_only_unwrapped_known_events() {
+        let variants = &dca::EVENT_VARIANTS;
+        let wrapped = RawEventBuilder::new("FilledEvent")
+            .fields(serde_json::json!({ "FilledEvent": { "dca_key": "pda" } }))
+            .build();
+        let fields = ProtocolHelpers::wrapped_event_fields(&wrapped, variants);
+        assert!(matches!(fields, Some(Cow::Borrowed(_))));
+
+        let unwrapped = RawEventBuilder::new("FilledEvent")
+            .fields(serde_json::json!({ "dca_key": "pda" }))
+            .build();
+        assert_eq!(
+            ProtocolHelpers::wrapped_event_fields(&unwrapped, variants).as_deref(),
+            Some(&serde_json::json!({ "FilledEvent": { "dca_key": "pda" } }))
+        );
+
+        let unknown = RawEventBuilder::new("MysteryEvent")
+            .fields(serde_json::json!({ "dca_key": "pda" }))
+            .build();
+        assert_eq!(
+            ProtocolHelpers::wrapped_event_fields(&unknown, variants).as_deref(),
+            Some(&serde_json::json!({ "dca_key": "pda" }))
+        );
+
+        let absent = RawEventBuilder::new("FilledEvent").build();
+        assert!(ProtocolHelpers::wrapped_event_fields(&absent, variants).is_none());
+    }
+
+    #[test]
+    fn normalize_fields_unwraps_data_name_fields() {
+        let inner = serde_json::json!({ "dca_key": "pda", "in_amount": 10, "out_amount": 4 });
+        let wrapped = RawEventBuilder::new("FilledEvent")
+            .fields(serde_json::json!({
+                "data": { "name": "FilledEvent", "fields": inner.clone() }
+            }))
+            .build();
+        let canonical = serde_json::json!({ "FilledEvent": inner });
+        assert_eq!(wrapped.normalize_fields(), canonical);
+
+        let already_canonical = RawEventBuilder::new("FilledEvent")
+            .fields(canonical.clone())
+            .build();
+        assert_eq!(already_canonical.normalize_fields(), canonical);
+        assert!(matches!(
+            already_canonical.normalized_fields(),
+            Some(Cow::Borrowed(_))
+        ));
+        assert_eq!(
+            RawEventBuilder::new("FilledEvent")
+                .build()
+                .normalize_fields(),
+            serde_json::Value::Null
+        );
+
+        let (event_type, _, payload) = adapter_for(Protocol::Dca)
+            .classify_and_resolve_event(&wrapped, &ResolveContext::default())
+            .unwrap()
+            .unwrap();
+        assert_eq!(event_type, EventType::FillCompleted);
+        assert_eq!(payload.amounts(), Some((10, 4)));
+    }
+
+    #[test]
+    fn primary_source_is_pinned_per_protocol() {
+        assert_eq!(Protocol::Dca.primary_source(), DataSource::Events);
+        assert_eq!(Protocol::LimitV1.primary_source(), DataSource::Both);
+        assert_eq!(Protocol::LimitV2.primary_source(), DataSource::Events);
+        assert_eq!(Protocol::Kamino.primary_source(), DataSource::Instructions);
+        assert_eq!(
+            Protocol::ValueAveraging.primary_source(),
+            DataSource::Events
+        );
+    }
+
+    #[test]
+    fn possible_event_types_match_mapping_tables() {
+        type Table = &'static [(&'static str, EventType)];
+        let cases: [(Protocol, Table, Table, Table); 5] = [
+            (
+                Protocol::Dca,
+                dca::INSTRUCTION_EVENT_TYPES,
+                dca::EVENT_EVENT_TYPES,
+                dca::CONTEXT_EVENT_TYPES,
+            ),
+            (
+                Protocol::LimitV1,
+                limit_v1::INSTRUCTION_EVENT_TYPES,
+                limit_v1::EVENT_EVENT_TYPES,
+                limit_v1::CONTEXT_EVENT_TYPES,
+            ),
+            (
+                Protocol::LimitV2,
+                limit_v2::INSTRUCTION_EVENT_TYPES,
+                limit_v2::EVENT_EVENT_TYPES,
+                limit_v2::CONTEXT_EVENT_TYPES,
+            ),
+            (
+                Protocol::Kamino,
+                kamino::INSTRUCTION_EVENT_TYPES,
+                kamino::EVENT_EVENT_TYPES,
+                kamino::CONTEXT_EVENT_TYPES,
+            ),
+            (
+                Protocol::ValueAveraging,
+                va::INSTRUCTION_EVENT_TYPES,
+                va::EVENT_EVENT_TYPES,
+                va::CONTEXT_EVENT_TYPES,
+            ),
+        ];
+        for (protocol, instructions, events, context_dependent) in cases {
+            let from_tables: HashSet<&str> = instructions
+                .iter()
+                .chain(events)
+                .chain(context_dependent)
+                .map(|(_, et)| et.as_ref())
+                .collect();
+            let declared: HashSet<&str> = protocol
+                .possible_event_types()
+                .iter()
+                .map(AsRef::as_ref)
+                .collect();
+            assert_eq!(declared, from_tables, "mismatch for {protocol}");
+            assert_eq!(
+                declared.len(),
+                protocol.possible_event_types().len(),
+                "duplicate entries for {protocol}"
+            );
+        }
+
+        assert!(
+            Protocol::LimitV1
+                .possible_event_types()
+                .contains(&EventType::Expired)
+        );
+        // Limit v2 has no expiry instruction; `Expired` is derived from `ResolveContext`.
+        assert!(
+            Protocol::LimitV2
+                .possible_event_types()
+                .contains(&EventType::Expired)
+        );
+    }
+
     #[test]
     fn event_type_reachability_all_variants_covered() {
         let mut all_event_types: HashSet<String> = HashSet::new();
-        let default_ctx = ResolveContext {
-            pre_fetched_order_pdas: None,
-        };
+        let default_ctx = ResolveContext::default();
 
         let dca = adapter_for(Protocol::Dca);
         let dca_ix_names = [
@@ -512,6 +1792,7 @@ mod tests {
 
         let kamino_ctx = ResolveContext {
             pre_fetched_order_pdas: Some(vec!["test_pda".to_string()]),
+            ..ResolveContext::default()
         };
         let kamino_event_payloads = [
             serde_json::json!({"OrderDisplayEvent": {"status": 1_u8}}),
@@ -523,20 +1804,10 @@ mod tests {
             }
         }
 
-        let expected: HashSet<String> = [
-            "created",
-            "fill_initiated",
-            "fill_completed",
-            "cancelled",
-            "expired",
-            "closed",
-            "fee_collected",
-            "withdrawn",
-            "deposited",
-        ]
-        .into_iter()
-        .map(String::from)
-        .collect();
+        let expected: HashSet<String> = EventType::ALL
+            .iter()
+            .map(|et| et.as_ref().to_string())
+            .collect();
 
         assert_eq!(
             all_event_types,
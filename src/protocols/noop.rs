@@ -0,0 +1,37 @@
+use crate::error::Error;
+use crate::lifecycle::adapters::{CorrelationOutcome, ProtocolAdapter, ResolvedEvent};
+use crate::protocols::{EventType, Protocol};
+use crate::types::{RawEvent, RawInstruction, ResolveContext};
+
+/// [`Protocol::Unknown`] has no event variants.
+pub static EVENT_VARIANTS: phf::Set<&'static str> = phf::phf_set! {};
+
+/// Passthrough adapter for unsupported programs (zero-sized, stored as a static).
+///
+/// Recognises nothing: classification and resolution always return `None`, and correlation is
+/// [`CorrelationOutcome::NotRequired`]. Lets callers treat every program uniformly via
+/// [`crate::adapter_for_program_id`] and skip unsupported ones without `Option` branching.
+#[derive(Debug)]
+pub struct NoOpAdapter;
+
+impl ProtocolAdapter for NoOpAdapter {
+    fn protocol(&self) -> Protocol {
+        Protocol::Unknown
+    }
+
+    fn classify_instruction(&self, _ix: &RawInstruction) -> Option<EventType> {
+        None
+    }
+
+    fn classify_and_resolve_event(
+        &self,
+        _ev: &RawEvent,
+        _ctx: &ResolveContext,
+    ) -> Option<Result<ResolvedEvent, Error>> {
+        None
+    }
+
+    fn correlate(&self, _ev: &RawEvent, _ctx: &ResolveContext) -> CorrelationOutcome {
+        CorrelationOutcome::NotRequired
+    }
+}
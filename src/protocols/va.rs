@@ -0,0 +1,551 @@
+use crate::error::{Error, ErrorCode};
+use crate::lifecycle::adapters::{
+    CorrelationOutcome, EventPayload, InstructionArgs, InstructionOutcome, ProtocolAdapter,
+    ResolvedEvent, dca_closed_terminal_status,
+};
+use crate::protocols::dca::DcaClosedEvent;
+use crate::protocols::{
+    AccountInfo, AmountCaster, EventType, InstructionCategory, Protocol, ProtocolHelpers,
+};
+use crate::types::{RawEvent, RawInstruction, ResolveContext};
+
+/// Serde-tagged envelope for Jupiter Value Averaging event variants.
+///
+/// Same vocabulary as DCA, keyed by `value_average` instead of `dca_key`.
+#[derive(serde::Deserialize, strum_macros::VariantNames)]
+pub enum VaEventEnvelope {
+    OpenedEvent(VaKeyHolder),
+    FilledEvent(VaFilledEventFields),
+    ClosedEvent(VaClosedEventFields),
+    CollectedFeeEvent(VaKeyHolder),
+    WithdrawEvent(VaKeyHolder),
+    DepositEvent(VaKeyHolder),
+}
+
+/// Serde-tagged envelope for Jupiter Value Averaging instruction variants.
+///
+/// Inner `Value` is unused at runtime — serde consumes it during deserialization.
+#[derive(serde::Deserialize, strum_macros::VariantNames)]
+pub enum VaInstructionKind {
+    Open(serde_json::Value),
+    InitiateFlashFill(serde_json::Value),
+    FulfillFlashFill(serde_json::Value),
+    Close(serde_json::Value),
+    Deposit(serde_json::Value),
+    Withdraw(serde_json::Value),
+    WithdrawFees(serde_json::Value),
+}
+
+pub const INSTRUCTION_EVENT_TYPES: &[(&str, EventType)] = &[
+    ("Open", EventType::Created),
+    ("InitiateFlashFill", EventType::FillInitiated),
+    ("FulfillFlashFill", EventType::FillCompleted),
+    ("Close", EventType::Closed),
+];
+
+/// Known instructions outside [`INSTRUCTION_EVENT_TYPES`], with their [`InstructionCategory`].
+pub const INSTRUCTION_CATEGORIES: &[(&str, InstructionCategory)] = &[
+    ("Deposit", InstructionCategory::Balance),
+    ("Withdraw", InstructionCategory::Balance),
+    ("WithdrawFees", InstructionCategory::Fee),
+];
+
+pub const EVENT_EVENT_TYPES: &[(&str, EventType)] = &[
+    ("OpenedEvent", EventType::Created),
+    ("FilledEvent", EventType::FillCompleted),
+    ("ClosedEvent", EventType::Closed),
+    ("CollectedFeeEvent", EventType::FeeCollected),
+    ("WithdrawEvent", EventType::Withdrawn),
+    ("DepositEvent", EventType::Deposited),
+];
+
+/// [`crate::EventPayload`] variant each event in [`EVENT_EVENT_TYPES`] resolves to.
+pub const EVENT_PAYLOAD_KINDS: &[(&str, &str)] = &[
+    ("OpenedEvent", "None"),
+    ("FilledEvent", "DcaFill"),
+    ("ClosedEvent", "DcaClosed"),
+    ("CollectedFeeEvent", "None"),
+    ("WithdrawEvent", "None"),
+    ("DepositEvent", "None"),
+];
+
+/// Event variants whose [`EventType`] depends on [`ResolveContext`], with the alternative type.
+///
+/// No Value Averaging event changes type with the context.
+pub const CONTEXT_EVENT_TYPES: &[(&str, EventType)] = &[];
+
+/// Every event type the adapter can produce: the union of [`INSTRUCTION_EVENT_TYPES`],
+/// [`EVENT_EVENT_TYPES`] and the context-derived [`CONTEXT_EVENT_TYPES`].
+pub const POSSIBLE_EVENT_TYPES: &[EventType] = &[
+    EventType::Created,
+    EventType::FillInitiated,
+    EventType::FillCompleted,
+    EventType::Closed,
+    EventType::FeeCollected,
+    EventType::Withdrawn,
+    EventType::Deposited,
+];
+
+pub const CLOSED_VARIANTS: &[&str] = &["Completed", "Cancelled", "Expired"];
+
+/// [`VaEventEnvelope`] variant names as a compile-time set (see [`crate::protocols::is_known_variant`]).
+pub static EVENT_VARIANTS: phf::Set<&'static str> = phf::phf_set! {
+    "OpenedEvent",
+    "FilledEvent",
+    "ClosedEvent",
+    "CollectedFeeEvent",
+    "WithdrawEvent",
+    "DepositEvent",
+};
+
+/// Jupiter Value Averaging protocol adapter (zero-sized, stored as a static).
+#[derive(Debug)]
+pub struct VaAdapter;
+
+/// Serde intermediate for `FilledEvent` payload fields.
+#[derive(serde::Deserialize)]
+pub struct VaFilledEventFields {
+    #[serde(deserialize_with = "crate::protocols::de_nonempty_pubkey")]
+    value_average: String,
+    #[serde(deserialize_with = "crate::protocols::de_u64_flexible")]
+    in_amount: u64,
+    #[serde(deserialize_with = "crate::protocols::de_u64_flexible")]
+    out_amount: u64,
+}
+
+/// Serde intermediate for `ClosedEvent` payload fields.
+///
+/// Withdrawn totals default to 0 so payloads without them still parse.
+#[derive(serde::Deserialize)]
+pub struct VaClosedEventFields {
+    #[serde(deserialize_with = "crate::protocols::de_nonempty_pubkey")]
+    value_average: String,
+    user_closed: bool,
+    #[serde(deserialize_with = "crate::protocols::de_u64_flexible")]
+    unfilled_amount: u64,
+    #[serde(default, deserialize_with = "crate::protocols::de_u64_flexible")]
+    total_in_withdrawn: u64,
+    #[serde(default, deserialize_with = "crate::protocols::de_u64_flexible")]
+    total_out_withdrawn: u64,
+}
+
+/// Serde intermediate for events that only carry a `value_average` key.
+#[derive(serde::Deserialize)]
+pub struct VaKeyHolder {
+    #[serde(deserialize_with = "crate::protocols::de_nonempty_pubkey")]
+    value_average: String,
+}
+
+/// Parsed arguments from an `Open` instruction.
+///
+/// Unlike DCA's fixed `in_amount_per_cycle`, each VA cycle buys whatever is needed to
+/// grow the position's value by `increment_usdc_value`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VaCreateArgs {
+    pub deposit_amount: i64,
+    pub increment_usdc_value: i64,
+    pub order_interval: i64,
+    pub max_out_amount: Option<i64>,
+    pub start_at: Option<i64>,
+}
+
+#[derive(serde::Deserialize)]
+struct OpenVaFields {
+    #[serde(deserialize_with = "crate::protocols::de_u64_flexible")]
+    deposit_amount: u64,
+    #[serde(deserialize_with = "crate::protocols::de_u64_flexible")]
+    increment_usdc_value: u64,
+    order_interval: i64,
+    #[serde(default, deserialize_with = "crate::protocols::de_opt_u64_flexible")]
+    max_out_amount: Option<u64>,
+    start_at: Option<i64>,
+}
+
+impl ProtocolAdapter for VaAdapter {
+    fn protocol(&self) -> Protocol {
+        Protocol::ValueAveraging
+    }
+
+    fn classify_instruction(&self, ix: &RawInstruction) -> Option<EventType> {
+        self.classify_instruction_category(ix).event_type()
+    }
+
+    fn classify_instruction_category(&self, ix: &RawInstruction) -> InstructionCategory {
+        ProtocolHelpers::lookup_instruction_category(
+            &ix.instruction_name,
+            INSTRUCTION_EVENT_TYPES,
+            INSTRUCTION_CATEGORIES,
+        )
+    }
+
+    fn resolve_instruction(
+        &self,
+        ix: &RawInstruction,
+    ) -> Option<Result<InstructionOutcome, Error>> {
+        let event_type = self.classify_instruction(ix)?;
+        Some(ProtocolHelpers::resolve_classified_instruction(
+            ix,
+            event_type,
+            Self::extract_order_pda,
+            |args| Self::parse_create_args(args).map(InstructionArgs::VaCreate),
+        ))
+    }
+
+    fn classify_and_resolve_event(
+        &self,
+        ev: &RawEvent,
+        ctx: &ResolveContext,
+    ) -> Option<Result<ResolvedEvent, Error>> {
+        let fields = ProtocolHelpers::wrapped_event_fields(ev, &EVENT_VARIANTS)?;
+        Self::resolve_fields(&fields, ctx)
+    }
+}
+
+impl VaAdapter {
+    /// Classifies and resolves a bare event `fields` payload (see [`crate::resolve_event_value`]).
+    pub fn resolve_fields(
+        fields: &serde_json::Value,
+        ctx: &ResolveContext,
+    ) -> Option<Result<ResolvedEvent, Error>> {
+        let envelope: VaEventEnvelope = match serde_json::from_value(fields.clone()) {
+            Ok(e) => e,
+            Err(err) => {
+                if !ProtocolHelpers::contains_known_variant(fields, &EVENT_VARIANTS) {
+                    return None;
+                }
+                return Some(Err(Error::Protocol {
+                    code: ErrorCode::MalformedPayload,
+                    reason: format!("failed to parse VA event payload: {err}"),
+                }));
+            }
+        };
+
+        Some(Self::resolve_event(envelope, ctx))
+    }
+
+    fn resolve_event(
+        envelope: VaEventEnvelope,
+        ctx: &ResolveContext,
+    ) -> Result<ResolvedEvent, Error> {
+        let mut amounts = AmountCaster::new(ctx);
+        match envelope {
+            VaEventEnvelope::FilledEvent(VaFilledEventFields {
+                value_average,
+                in_amount,
+                out_amount,
+            }) => Ok((
+                EventType::FillCompleted,
+                CorrelationOutcome::Correlated(vec![value_average]),
+                EventPayload::DcaFill {
+                    in_amount: amounts.cast(in_amount, "in_amount")?,
+                    out_amount: amounts.cast(out_amount, "out_amount")?,
+                    saturated: amounts.saturated(),
+                    cycle: None,
+                },
+            )),
+            VaEventEnvelope::ClosedEvent(VaClosedEventFields {
+                value_average,
+                user_closed,
+                unfilled_amount,
+                total_in_withdrawn,
+                total_out_withdrawn,
+            }) => {
+                let closed = DcaClosedEvent {
+                    order_pda: value_average,
+                    user_closed,
+                    unfilled_amount: amounts.cast(unfilled_amount, "unfilled_amount")?,
+                };
+                let status = dca_closed_terminal_status(&closed);
+                Ok((
+                    EventType::Closed,
+                    CorrelationOutcome::Correlated(vec![closed.order_pda]),
+                    EventPayload::DcaClosed {
+                        status,
+                        total_in_withdrawn: amounts
+                            .cast(total_in_withdrawn, "total_in_withdrawn")?,
+                        total_out_withdrawn: amounts
+                            .cast(total_out_withdrawn, "total_out_withdrawn")?,
+                    },
+                ))
+            }
+            VaEventEnvelope::OpenedEvent(VaKeyHolder { value_average }) => Ok((
+                EventType::Created,
+                CorrelationOutcome::Correlated(vec![value_average]),
+                EventPayload::None,
+            )),
+            VaEventEnvelope::CollectedFeeEvent(VaKeyHolder { value_average }) => Ok((
+                EventType::FeeCollected,
+                CorrelationOutcome::Correlated(vec![value_average]),
+                EventPayload::None,
+            )),
+            VaEventEnvelope::WithdrawEvent(VaKeyHolder { value_average }) => Ok((
+                EventType::Withdrawn,
+                CorrelationOutcome::Correlated(vec![value_average]),
+                EventPayload::None,
+            )),
+            VaEventEnvelope::DepositEvent(VaKeyHolder { value_average }) => Ok((
+                EventType::Deposited,
+                CorrelationOutcome::Correlated(vec![value_average]),
+                EventPayload::None,
+            )),
+        }
+    }
+
+    /// Extracts the order PDA from instruction accounts.
+    ///
+    /// Prefers the named `"value_average"` account; falls back to positional index per instruction variant.
+    pub fn extract_order_pda(
+        accounts: &[AccountInfo],
+        instruction_name: &str,
+    ) -> Result<String, Error> {
+        if let Some(acc) = ProtocolHelpers::find_account_by_name(accounts, "value_average") {
+            return Ok(acc.pubkey.clone());
+        }
+
+        let idx = Self::order_pda_index(instruction_name)?;
+
+        accounts
+            .get(idx)
+            .map(|a| a.pubkey.clone())
+            .ok_or_else(|| Error::Protocol {
+                code: ErrorCode::OutOfBoundsAccount,
+                reason: format!("VA account index {idx} out of bounds for {instruction_name}"),
+            })
+    }
+
+    /// Positional index of the `"value_average"` account in `instruction_name`, used when the account
+    /// is not named.
+    pub fn order_pda_index(instruction_name: &str) -> Result<usize, Error> {
+        let wrapper = serde_json::json!({ instruction_name: serde_json::Value::Null });
+        let kind: VaInstructionKind =
+            serde_json::from_value(wrapper).map_err(|_| Error::Protocol {
+                code: ErrorCode::UnknownInstruction,
+                reason: format!("unknown VA instruction: {instruction_name}"),
+            })?;
+
+        let idx = match kind {
+            VaInstructionKind::Open(_) => 0,
+            VaInstructionKind::InitiateFlashFill(_)
+            | VaInstructionKind::FulfillFlashFill(_)
+            | VaInstructionKind::Close(_) => 1,
+            VaInstructionKind::Deposit(_)
+            | VaInstructionKind::Withdraw(_)
+            | VaInstructionKind::WithdrawFees(_) => {
+                return Err(Error::Protocol {
+                    code: ErrorCode::UnsupportedInstruction,
+                    reason: format!("VA instruction {instruction_name} has no order PDA"),
+                });
+            }
+        };
+        Ok(idx)
+    }
+
+    /// Parses `Open` instruction args into checked [`VaCreateArgs`].
+    pub fn parse_create_args(args: &serde_json::Value) -> Result<VaCreateArgs, Error> {
+        let OpenVaFields {
+            deposit_amount,
+            increment_usdc_value,
+            order_interval,
+            max_out_amount,
+            start_at,
+        } = ProtocolHelpers::parse_create_fields(args, "VA")?;
+
+        Ok(VaCreateArgs {
+            deposit_amount: ProtocolHelpers::checked_u64_to_i64(deposit_amount, "deposit_amount")?,
+            increment_usdc_value: ProtocolHelpers::checked_u64_to_i64(
+                increment_usdc_value,
+                "increment_usdc_value",
+            )?,
+            order_interval: ProtocolHelpers::checked_timestamp(order_interval, "order_interval")?,
+            max_out_amount: max_out_amount.and_then(ProtocolHelpers::optional_u64_to_i64),
+            start_at: start_at
+                .filter(|&ts| ts > 0)
+                .map(|ts| ProtocolHelpers::checked_timestamp(ts, "start_at"))
+                .transpose()?,
+        })
+    }
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used, reason = "test assertions")]
+mod tests {
+    use super::*;
+    use crate::lifecycle::TerminalStatus;
+    use crate::test_util::{RawEventBuilder, RawInstructionBuilder};
+    use strum::VariantNames;
+
+    fn resolve(fields: serde_json::Value) -> Option<Result<ResolvedEvent, Error>> {
+        let ev = RawEventBuilder::default().fields(fields).build();
+        VaAdapter.classify_and_resolve_event(&ev, &ResolveContext::default())
+    }
+
+    #[test]
+    fn parse_create_args_accepts_flat_and_data_envelope() {
+        let fields = serde_json::json!({
+            "deposit_amount": 10_000_u64,
+            "increment_usdc_value": 250_u64,
+            "order_interval": 3_600_i64,
+            "start_at": 0_i64
+        });
+        let flat = VaAdapter::parse_create_args(&fields).unwrap();
+        let nested = VaAdapter::parse_create_args(&serde_json::json!({ "data": fields })).unwrap();
+        assert_eq!(flat, nested);
+        assert_eq!(flat.deposit_amount, 10_000);
+        assert_eq!(flat.order_interval, 3_600);
+        assert!(flat.start_at.is_none());
+    }
+
+    #[test]
+    fn parse_create_args_rejects_implausible_timestamps() {
+        let args = |order_interval: i64, start_at: i64| {
+            serde_json::json!({
+                "deposit_amount": 10_000_u64,
+                "increment_usdc_value": 250_u64,
+                "order_interval": order_interval,
+                "start_at": start_at
+            })
+        };
+        let err = VaAdapter::parse_create_args(&args(i64::MIN, 0)).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::MalformedPayload);
+        assert!(err.to_string().contains("order_interval"));
+
+        let err = VaAdapter::parse_create_args(&args(3_600, i64::MAX)).unwrap_err();
+        assert!(err.to_string().contains("start_at"));
+    }
+
+    #[test]
+    fn mirror_enums_cover_all_va_variants() {
+        let instruction_variants = [
+            "Open",
+            "InitiateFlashFill",
+            "FulfillFlashFill",
+            "Close",
+            "Deposit",
+            "Withdraw",
+            "WithdrawFees",
+        ];
+        for name in instruction_variants {
+            let json = serde_json::json!({ name: serde_json::Value::Null });
+            assert!(
+                serde_json::from_value::<VaInstructionKind>(json).is_ok(),
+                "VaInstructionKind missing variant: {name}"
+            );
+        }
+
+        let key_holder_variants = [
+            "OpenedEvent",
+            "CollectedFeeEvent",
+            "WithdrawEvent",
+            "DepositEvent",
+        ];
+        for name in key_holder_variants {
+            let json = serde_json::json!({ name: { "value_average": "test" } });
+            assert!(
+                serde_json::from_value::<VaEventEnvelope>(json).is_ok(),
+                "VaEventEnvelope missing variant: {name}"
+            );
+        }
+
+        let filled = serde_json::json!({
+            "FilledEvent": { "value_average": "t", "in_amount": 1_u64, "out_amount": 1_u64 }
+        });
+        assert!(serde_json::from_value::<VaEventEnvelope>(filled).is_ok());
+
+        let closed = serde_json::json!({
+            "ClosedEvent": { "value_average": "t", "user_closed": false, "unfilled_amount": 0_u64 }
+        });
+        assert!(serde_json::from_value::<VaEventEnvelope>(closed).is_ok());
+
+        assert_eq!(VaEventEnvelope::VARIANTS.len(), EVENT_EVENT_TYPES.len());
+    }
+
+    #[test]
+    fn resolve_filled_event_keys_on_value_average() {
+        let fields = serde_json::json!({
+            "FilledEvent": {
+                "value_average": "va_pda",
+                "in_amount": "250000",
+                "out_amount": 1_234_u64
+            }
+        });
+        let (event_type, correlation, payload) = resolve(fields).unwrap().unwrap();
+        assert_eq!(event_type, EventType::FillCompleted);
+        assert_eq!(
+            correlation,
+            CorrelationOutcome::Correlated(vec!["va_pda".to_string()])
+        );
+        assert_eq!(
+            payload,
+            EventPayload::DcaFill {
+                in_amount: 250_000,
+                out_amount: 1_234,
+                saturated: false,
+                cycle: None,
+            }
+        );
+
+        let dca_shaped = serde_json::json!({
+            "FilledEvent": { "dca_key": "va_pda", "in_amount": 1_u64, "out_amount": 1_u64 }
+        });
+        assert!(resolve(dca_shaped).unwrap().is_err());
+    }
+
+    #[test]
+    fn resolve_closed_event_derives_terminal_status() {
+        let fields = serde_json::json!({
+            "ClosedEvent": { "value_average": "va_pda", "user_closed": true, "unfilled_amount": 5_u64 }
+        });
+        let (_, _, payload) = resolve(fields).unwrap().unwrap();
+        assert_eq!(
+            payload,
+            EventPayload::DcaClosed {
+                status: TerminalStatus::Cancelled,
+                total_in_withdrawn: 0,
+                total_out_withdrawn: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn open_instruction_parses_value_target_args() {
+        let ix = RawInstructionBuilder::new("Open")
+            .accounts(serde_json::json!([
+                { "pubkey": "va_pda", "is_signer": false, "is_writable": true, "name": "value_average" },
+                { "pubkey": "user", "is_signer": true, "is_writable": true, "name": "user" }
+            ]))
+            .args(serde_json::json!({
+                "deposit_amount": 1_000_000_u64,
+                "increment_usdc_value": 50_000_u64,
+                "order_interval": 86_400,
+                "max_out_amount": null,
+                "start_at": 0
+            }))
+            .build();
+        let outcome = VaAdapter.resolve_instruction(&ix).unwrap().unwrap();
+        assert_eq!(outcome.event_type, EventType::Created);
+        assert_eq!(outcome.order_pda.as_deref(), Some("va_pda"));
+        assert_eq!(
+            outcome.args,
+            InstructionArgs::VaCreate(VaCreateArgs {
+                deposit_amount: 1_000_000,
+                increment_usdc_value: 50_000,
+                order_interval: 86_400,
+                max_out_amount: None,
+                start_at: None,
+            })
+        );
+    }
+
+    #[test]
+    fn open_order_pda_falls_back_to_index_zero() {
+        let accounts = ProtocolHelpers::parse_accounts(&serde_json::json!([
+            { "pubkey": "va_pda" },
+            { "pubkey": "user" }
+        ]))
+        .unwrap();
+        assert_eq!(
+            VaAdapter::extract_order_pda(&accounts, "Open").unwrap(),
+            "va_pda"
+        );
+    }
+}
@@ -0,0 +1,134 @@
+use std::io::BufRead;
+
+use crate::error::Error;
+use crate::lifecycle::adapters::{ResolvedEvent, adapter_for};
+use crate::protocols::Protocol;
+use crate::types::{RawEvent, ResolveContext};
+
+/// What to do with a line whose event the adapter does not recognise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UnknownEventPolicy {
+    /// Drop the line silently.
+    #[default]
+    Skip,
+    /// Yield [`Error::UnknownEvent`] for the line.
+    Error,
+}
+
+/// Resolves newline-delimited `RawEvent` JSON, skipping unrecognised events.
+///
+/// See [`resolve_events_jsonl_with`] for the per-line error semantics.
+pub fn resolve_events_jsonl<R: BufRead>(
+    protocol: Protocol,
+    reader: R,
+    ctx: &ResolveContext,
+) -> impl Iterator<Item = Result<ResolvedEvent, Error>> {
+    resolve_events_jsonl_with(protocol, reader, ctx, UnknownEventPolicy::Skip)
+}
+
+/// Resolves newline-delimited `RawEvent` JSON, yielding one result per non-blank line.
+///
+/// Read failures and malformed lines yield [`Error::Parse`] tagged with the 1-based line
+/// number; the stream keeps going so a single bad line does not abort a backfill.
+pub fn resolve_events_jsonl_with<R: BufRead>(
+    protocol: Protocol,
+    reader: R,
+    ctx: &ResolveContext,
+    unknown: UnknownEventPolicy,
+) -> impl Iterator<Item = Result<ResolvedEvent, Error>> {
+    let adapter = adapter_for(protocol);
+    reader.lines().enumerate().filter_map(move |(idx, line)| {
+        let line_no = idx + 1;
+        let line = match line {
+            Ok(line) => line,
+            Err(e) => {
+                return Some(Err(Error::Parse {
+                    reason: format!("failed to read line {line_no}: {e}"),
+                }));
+            }
+        };
+        if line.trim().is_empty() {
+            return None;
+        }
+
+        let ev: RawEvent = match serde_json::from_str(&line) {
+            Ok(ev) => ev,
+            Err(e) => {
+                return Some(Err(Error::Parse {
+                    reason: format!("line {line_no}: invalid RawEvent JSON: {e}"),
+                }));
+            }
+        };
+
+        match adapter.classify_and_resolve_event(&ev, ctx) {
+            Some(result) => Some(result),
+            None => match unknown {
+                UnknownEventPolicy::Skip => None,
+                UnknownEventPolicy::Error => Some(Err(Error::UnknownEvent {
+                    event_name: ev.event_name,
+                })),
+            },
+        }
+    })
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used, clippy::panic, reason = "test assertions")]
+mod tests {
+    use super::*;
+    use crate::lifecycle::adapters::CorrelationOutcome;
+    use crate::protocols::EventType;
+
+    const OPENED: &str = r#"{"id":1,"signature":"s1","event_index":0,"program_id":"p","inner_program_id":"p","event_name":"OpenedEvent","fields":{"OpenedEvent":{"dca_key":"order1"}},"slot":1}"#;
+    const FILLED: &str = r#"{"id":3,"signature":"s3","event_index":0,"program_id":"p","inner_program_id":"p","event_name":"FilledEvent","fields":{"FilledEvent":{"dca_key":"order1","in_amount":10,"out_amount":5}},"slot":3}"#;
+    const UNKNOWN: &str = r#"{"id":4,"signature":"s4","event_index":0,"program_id":"p","inner_program_id":"p","event_name":"MysteryEvent","fields":{"MysteryEvent":{}},"slot":4}"#;
+
+    fn ctx() -> ResolveContext {
+        ResolveContext::default()
+    }
+
+    #[test]
+    fn malformed_line_does_not_abort_stream() {
+        let input = format!("{OPENED}\n{{\"id\": not json\n{FILLED}\n");
+        let results: Vec<_> =
+            resolve_events_jsonl(Protocol::Dca, input.as_bytes(), &ctx()).collect();
+
+        assert_eq!(results.len(), 3);
+        let (event_type, correlation, _) = results[0].as_ref().unwrap();
+        assert_eq!(*event_type, EventType::Created);
+        assert_eq!(
+            *correlation,
+            CorrelationOutcome::Correlated(vec!["order1".to_string()])
+        );
+
+        let Err(Error::Parse { reason }) = &results[1] else {
+            panic!("expected parse error for line 2");
+        };
+        assert!(reason.starts_with("line 2:"), "{reason}");
+
+        let (event_type, _, _) = results[2].as_ref().unwrap();
+        assert_eq!(*event_type, EventType::FillCompleted);
+    }
+
+    #[test]
+    fn unknown_events_follow_policy() {
+        let input = format!("{OPENED}\n\n{UNKNOWN}\n");
+
+        let skipped: Vec<_> =
+            resolve_events_jsonl(Protocol::Dca, input.as_bytes(), &ctx()).collect();
+        assert_eq!(skipped.len(), 1);
+
+        let surfaced: Vec<_> = resolve_events_jsonl_with(
+            Protocol::Dca,
+            input.as_bytes(),
+            &ctx(),
+            UnknownEventPolicy::Error,
+        )
+        .collect();
+        assert_eq!(surfaced.len(), 2);
+        let Err(Error::UnknownEvent { event_name }) = &surfaced[1] else {
+            panic!("expected unknown event error");
+        };
+        assert_eq!(event_name, "MysteryEvent");
+    }
+}
@@ -0,0 +1,258 @@
+//! Builders for synthesizing [`RawEvent`] / [`RawInstruction`] rows in tests.
+//!
+//! Available to this crate's tests and, via the `test-util` feature, to downstream crates.
+
+use crate::types::{RawEvent, RawInstruction};
+
+/// Fluent builder for [`RawEvent`] with placeholder defaults (`id: 1`, `signature: "sig"`, `slot: 1`).
+#[derive(Debug, Clone)]
+pub struct RawEventBuilder {
+    id: i64,
+    signature: String,
+    event_index: i32,
+    event_path: Option<String>,
+    program_id: String,
+    inner_program_id: String,
+    event_name: String,
+    fields: Option<serde_json::Value>,
+    slot: i64,
+}
+
+impl Default for RawEventBuilder {
+    fn default() -> Self {
+        Self {
+            id: 1,
+            signature: "sig".to_string(),
+            event_index: 0,
+            event_path: None,
+            program_id: "p".to_string(),
+            inner_program_id: "p".to_string(),
+            event_name: "test".to_string(),
+            fields: None,
+            slot: 1,
+        }
+    }
+}
+
+impl RawEventBuilder {
+    /// Starts a builder for an event with the given name.
+    pub fn new(event_name: impl Into<String>) -> Self {
+        Self::default().name(event_name)
+    }
+
+    pub fn id(mut self, id: i64) -> Self {
+        self.id = id;
+        self
+    }
+
+    pub fn signature(mut self, signature: impl Into<String>) -> Self {
+        self.signature = signature.into();
+        self
+    }
+
+    pub fn index(mut self, event_index: i32) -> Self {
+        self.event_index = event_index;
+        self
+    }
+
+    pub fn path(mut self, event_path: impl Into<String>) -> Self {
+        self.event_path = Some(event_path.into());
+        self
+    }
+
+    /// Sets both `program_id` and `inner_program_id`.
+    pub fn program_id(mut self, program_id: impl Into<String>) -> Self {
+        let program_id = program_id.into();
+        self.inner_program_id.clone_from(&program_id);
+        self.program_id = program_id;
+        self
+    }
+
+    pub fn name(mut self, event_name: impl Into<String>) -> Self {
+        self.event_name = event_name.into();
+        self
+    }
+
+    pub fn fields(mut self, fields: serde_json::Value) -> Self {
+        self.fields = Some(fields);
+        self
+    }
+
+    pub fn slot(mut self, slot: i64) -> Self {
+        self.slot = slot;
+        self
+    }
+
+    pub fn build(self) -> RawEvent {
+        RawEvent {
+            id: self.id,
+            signature: self.signature,
+            event_index: self.event_index,
+            event_path: self.event_path,
+            program_id: self.program_id,
+            inner_program_id: self.inner_program_id,
+            event_name: self.event_name,
+            fields: self.fields,
+            slot: self.slot,
+        }
+    }
+}
+
+/// Fluent builder for [`RawInstruction`] with placeholder defaults (`id: 1`, `signature: "sig"`, `slot: 1`).
+#[derive(Debug, Clone)]
+pub struct RawInstructionBuilder {
+    id: i64,
+    signature: String,
+    instruction_index: i32,
+    instruction_path: Option<String>,
+    program_id: String,
+    inner_program_id: String,
+    instruction_name: String,
+    accounts: Option<serde_json::Value>,
+    args: Option<serde_json::Value>,
+    slot: i64,
+}
+
+impl Default for RawInstructionBuilder {
+    fn default() -> Self {
+        Self {
+            id: 1,
+            signature: "sig".to_string(),
+            instruction_index: 0,
+            instruction_path: None,
+            program_id: "p".to_string(),
+            inner_program_id: "p".to_string(),
+            instruction_name: "test".to_string(),
+            accounts: None,
+            args: None,
+            slot: 1,
+        }
+    }
+}
+
+impl RawInstructionBuilder {
+    /// Starts a builder for an instruction with the given name.
+    pub fn new(instruction_name: impl Into<String>) -> Self {
+        Self::default().name(instruction_name)
+    }
+
+    pub fn id(mut self, id: i64) -> Self {
+        self.id = id;
+        self
+    }
+
+    pub fn signature(mut self, signature: impl Into<String>) -> Self {
+        self.signature = signature.into();
+        self
+    }
+
+    pub fn index(mut self, instruction_index: i32) -> Self {
+        self.instruction_index = instruction_index;
+        self
+    }
+
+    pub fn path(mut self, instruction_path: impl Into<String>) -> Self {
+        self.instruction_path = Some(instruction_path.into());
+        self
+    }
+
+    /// Sets both `program_id` and `inner_program_id`.
+    pub fn program_id(mut self, program_id: impl Into<String>) -> Self {
+        let program_id = program_id.into();
+        self.inner_program_id.clone_from(&program_id);
+        self.program_id = program_id;
+        self
+    }
+
+    pub fn name(mut self, instruction_name: impl Into<String>) -> Self {
+        self.instruction_name = instruction_name.into();
+        self
+    }
+
+    pub fn accounts(mut self, accounts: serde_json::Value) -> Self {
+        self.accounts = Some(accounts);
+        self
+    }
+
+    pub fn args(mut self, args: serde_json::Value) -> Self {
+        self.args = Some(args);
+        self
+    }
+
+    pub fn slot(mut self, slot: i64) -> Self {
+        self.slot = slot;
+        self
+    }
+
+    pub fn build(self) -> RawInstruction {
+        RawInstruction {
+            id: self.id,
+            signature: self.signature,
+            instruction_index: self.instruction_index,
+            instruction_path: self.instruction_path,
+            program_id: self.program_id,
+            inner_program_id: self.inner_program_id,
+            instruction_name: self.instruction_name,
+            accounts: self.accounts,
+            args: self.args,
+            slot: self.slot,
+        }
+    }
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used, reason = "test assertions")]
+mod tests {
+    use super::*;
+    use crate::lifecycle::adapters::{CorrelationOutcome, EventPayload, adapter_for};
+    use crate::protocols::{EventType, Protocol};
+    use crate::types::ResolveContext;
+
+    #[test]
+    fn built_event_resolves_through_adapter() {
+        let ev = RawEventBuilder::new("FilledEvent")
+            .signature("fill_sig")
+            .slot(42)
+            .fields(serde_json::json!({
+                "FilledEvent": { "dca_key": "order", "in_amount": 10_u64, "out_amount": 4_u64 }
+            }))
+            .build();
+        assert_eq!(ev.signature, "fill_sig");
+        assert_eq!(ev.slot, 42);
+
+        let ctx = ResolveContext::default();
+        let (event_type, correlation, payload) = adapter_for(Protocol::Dca)
+            .classify_and_resolve_event(&ev, &ctx)
+            .unwrap()
+            .unwrap();
+        assert_eq!(event_type, EventType::FillCompleted);
+        assert_eq!(
+            correlation,
+            CorrelationOutcome::Correlated(vec!["order".to_string()])
+        );
+        assert_eq!(
+            payload,
+            EventPayload::DcaFill {
+                in_amount: 10,
+                out_amount: 4,
+                saturated: false,
+                cycle: None,
+            }
+        );
+    }
+
+    #[test]
+    fn built_instruction_uses_defaults() {
+        let ix = RawInstructionBuilder::new("OpenDcaV2")
+            .program_id("DCA265Vj8a9CEuX1eb1LWRnDT7uK6q1xMipnNyatn23M")
+            .build();
+        assert_eq!(ix.id, 1);
+        assert_eq!(ix.signature, "sig");
+        assert_eq!(ix.inner_program_id, ix.program_id);
+        assert!(ix.accounts.is_none());
+        assert_eq!(
+            adapter_for(Protocol::Dca).classify_instruction(&ix),
+            Some(EventType::Created)
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 /// A decoded Solana instruction row as produced by the upstream indexer.
 #[derive(serde::Deserialize)]
 pub struct RawInstruction {
@@ -62,6 +64,34 @@ impl RawEvent {
             .rsplit_once('.')
             .map_or(event_path, |(instruction_path, _)| instruction_path)
     }
+
+    /// `fields` reshaped into the canonical `{"EventName": {...}}` form adapters parse.
+    ///
+    /// Recognises the `{"data": {"name": "EventName", "fields": {...}}}` wrapper; any other
+    /// shape is returned unchanged, and absent `fields` become `Null`.
+    pub fn normalize_fields(&self) -> serde_json::Value {
+        self.normalized_fields()
+            .map_or(serde_json::Value::Null, Cow::into_owned)
+    }
+
+    /// [`Self::normalize_fields`] without cloning already-canonical fields.
+    pub(crate) fn normalized_fields(&self) -> Option<Cow<'_, serde_json::Value>> {
+        let fields = self.fields.as_ref()?;
+        let data = fields
+            .as_object()
+            .filter(|outer| outer.len() == 1)
+            .and_then(|outer| outer.get("data"))
+            .and_then(serde_json::Value::as_object);
+        let Some((name, inner)) = data.and_then(|data| {
+            let name = data.get("name")?.as_str()?;
+            Some((name, data.get("fields")?))
+        }) else {
+            return Some(Cow::Borrowed(fields));
+        };
+        let mut canonical = serde_json::Map::new();
+        canonical.insert(name.to_string(), inner.clone());
+        Some(Cow::Owned(serde_json::Value::Object(canonical)))
+    }
 }
 
 /// Caller-supplied context needed to resolve certain events.
@@ -70,8 +100,72 @@ impl RawEvent {
 /// The caller should use raw path metadata when available to pre-fetch the
 /// exact order PDA from the matching instruction, then pass the result here
 /// so the adapter can correlate the event.
+#[derive(Debug, Clone, Default)]
 pub struct ResolveContext {
     /// Order PDAs extracted from instruction accounts for the same transaction.
     /// Required for Kamino `OrderDisplayEvent`; `None` causes `Uncorrelated`.
     pub pre_fetched_order_pdas: Option<Vec<String>>,
+    /// Unix timestamp of the block containing the event, if known.
+    pub block_time: Option<i64>,
+    /// Cached `expired_at` (unix timestamp) of the order the event refers to, if known.
+    /// With [`Self::block_time`], lets Limit v2 report a post-expiry cancel as `Expired`.
+    pub order_expired_at: Option<i64>,
+    /// Last-known `remaining_making_amount` of the order the event refers to, if known.
+    /// When `Some(0)`, Limit v2 reports a `CancelOrderEvent` as a `Completed` close.
+    pub order_remaining_making_amount: Option<i64>,
+    /// Original `making_amount` of the order the event refers to, if known (e.g. from its
+    /// create args). A Limit `TradeEvent` remaining above it is treated as a decoder wraparound:
+    /// clamped to `0` and flagged via `LimitFill::remaining_anomaly`.
+    pub order_original_making_amount: Option<i64>,
+    /// Whether a Kamino display status `Filled` with nonzero remaining input is terminal.
+    /// `None` uses the default (`true`); see [`Self::filled_is_terminal`].
+    pub treat_filled_as_terminal: Option<bool>,
+    /// Most PDAs one Kamino `OrderDisplayEvent` may correlate to via
+    /// [`Self::pre_fetched_order_pdas`]; more yields `Uncorrelated`. `None` uses
+    /// [`DEFAULT_MAX_CORRELATED_PDAS`].
+    pub max_correlated_pdas: Option<usize>,
+    /// Owner (maker) wallet of the order the event refers to, if known (e.g. from its create
+    /// instruction). Limit adapters use it as the `LimitFill` maker when the `TradeEvent`
+    /// carries none, enabling [`EventPayload::is_self_fill`](crate::EventPayload::is_self_fill).
+    pub owner: Option<String>,
+    /// Clamp event amounts above `i64::MAX` instead of failing with
+    /// [`Error::AmountOverflow`](crate::error::Error::AmountOverflow). Amount-bearing payloads
+    /// report any clamping through their `saturated` field.
+    pub saturate_amounts: bool,
+}
+
+/// Default cap on [`ResolveContext::max_correlated_pdas`].
+pub const DEFAULT_MAX_CORRELATED_PDAS: usize = 8;
+
+impl ResolveContext {
+    /// Effective [`Self::max_correlated_pdas`].
+    pub fn correlated_pda_cap(&self) -> usize {
+        self.max_correlated_pdas
+            .unwrap_or(DEFAULT_MAX_CORRELATED_PDAS)
+    }
+
+    /// `true` when both timestamps are known and the block is at or past the order's expiry.
+    pub fn order_has_expired(&self) -> bool {
+        matches!(
+            (self.block_time, self.order_expired_at),
+            (Some(block_time), Some(expired_at)) if block_time >= expired_at
+        )
+    }
+
+    /// `true` when the order's last-known remaining making amount is zero.
+    pub fn order_fully_filled(&self) -> bool {
+        self.order_remaining_making_amount == Some(0)
+    }
+
+    /// Effective [`Self::treat_filled_as_terminal`].
+    pub fn filled_is_terminal(&self) -> bool {
+        self.treat_filled_as_terminal.unwrap_or(true)
+    }
+
+    /// `true` when a fill's raw `remaining` exceeds the order's known original making amount,
+    /// which can only come from a wrapped (negative) decoder value.
+    pub fn remaining_exceeds_original(&self, remaining: u64) -> bool {
+        self.order_original_making_amount
+            .is_some_and(|original| u64::try_from(original).is_ok_and(|orig| remaining > orig))
+    }
 }
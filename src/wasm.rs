@@ -1,8 +1,10 @@
 use wasm_bindgen::prelude::*;
 
 use crate::lifecycle::mapping;
-use crate::lifecycle::{LifecycleEngine, LifecycleTransition, TerminalStatus};
-use crate::protocols::{self, EventType, Protocol};
+use crate::lifecycle::replay::replay_events;
+use crate::lifecycle::{LifecycleEngine, LifecycleTransition, MetadataReason, TerminalStatus};
+use crate::protocols::{self, EventType, ProgramIdFilter, Protocol};
+use crate::types::{RawEvent, ResolveContext};
 
 #[wasm_bindgen]
 extern "C" {
@@ -21,6 +23,31 @@ fn parse_terminal_status(s: &str) -> Option<TerminalStatus> {
     s.parse::<TerminalStatus>().ok()
 }
 
+/// Rebuilds a [`LifecycleTransition`] from the `{type, status, reason}` shape
+/// [`wasm_event_type_to_transition`] returns. A missing `Close` status defaults to `Completed`
+/// and a missing `MetadataOnly` reason to `Diagnostic`.
+fn parse_transition(
+    transition_type: &str,
+    close_status: Option<&str>,
+    reason: Option<&str>,
+) -> Option<LifecycleTransition> {
+    Some(match transition_type {
+        "Create" => LifecycleTransition::Create,
+        "FillDelta" => LifecycleTransition::FillDelta,
+        "MetadataOnly" => LifecycleTransition::MetadataOnly {
+            reason: reason
+                .and_then(|r| r.parse::<MetadataReason>().ok())
+                .unwrap_or(MetadataReason::Diagnostic),
+        },
+        "Close" => LifecycleTransition::Close {
+            status: close_status
+                .and_then(parse_terminal_status)
+                .unwrap_or(TerminalStatus::Completed),
+        },
+        _ => return None,
+    })
+}
+
 fn parse_event_type(s: &str) -> Option<EventType> {
     match s {
         "Created" => Some(EventType::Created),
@@ -32,6 +59,7 @@ fn parse_event_type(s: &str) -> Option<EventType> {
         "FeeCollected" => Some(EventType::FeeCollected),
         "Withdrawn" => Some(EventType::Withdrawn),
         "Deposited" => Some(EventType::Deposited),
+        "Diagnostic" => Some(EventType::Diagnostic),
         _ => None,
     }
 }
@@ -47,6 +75,7 @@ fn event_type_to_pascal(et: &EventType) -> &'static str {
         EventType::FeeCollected => "FeeCollected",
         EventType::Withdrawn => "Withdrawn",
         EventType::Deposited => "Deposited",
+        EventType::Diagnostic => "Diagnostic",
     }
 }
 
@@ -56,6 +85,7 @@ fn parse_protocol(s: &str) -> Option<Protocol> {
         "limitV1" => Some(Protocol::LimitV1),
         "limitV2" => Some(Protocol::LimitV2),
         "kamino" => Some(Protocol::Kamino),
+        "valueAveraging" => Some(Protocol::ValueAveraging),
         _ => None,
     }
 }
@@ -92,18 +122,30 @@ fn variant_maps_for_protocol(
             protocols::kamino::EVENT_EVENT_TYPES,
             protocols::kamino::CLOSED_VARIANTS,
         ),
+        Protocol::ValueAveraging => (
+            protocols::va::INSTRUCTION_EVENT_TYPES,
+            protocols::va::EVENT_EVENT_TYPES,
+            protocols::va::CLOSED_VARIANTS,
+        ),
+        Protocol::Unknown => (&[], &[], &[]),
     }
 }
 
-fn lookup_transition_summary(event_type: EventType) -> (String, String) {
+/// Returns `(transition display, decision from no terminal status, target status)`.
+fn lookup_transition_summary(event_type: EventType) -> (String, String, Option<&'static str>) {
     if event_type == EventType::Closed {
-        return ("Close(...)/MetadataOnly".to_string(), "Apply".to_string());
+        return (
+            "Close(...)/MetadataOnly".to_string(),
+            "Apply".to_string(),
+            None,
+        );
     }
 
     let transition = mapping::event_type_to_transition(&event_type, None);
     (
         mapping::transition_to_display(&transition),
         format!("{:?}", LifecycleEngine::decide_transition(None, transition)),
+        mapping::transition_target(&transition),
     )
 }
 
@@ -132,8 +174,8 @@ fn lookup_notes(
     }
 
     match (protocol, variant_name) {
-        (Protocol::Dca, "ClosedEvent") => notes.push(
-            "DCA ClosedEvent derives terminal status from user_closed and unfilled_amount."
+        (Protocol::Dca | Protocol::ValueAveraging, "ClosedEvent") => notes.push(
+            "DCA/VA ClosedEvent derives terminal status from user_closed and unfilled_amount."
                 .to_string(),
         ),
         (Protocol::Kamino, "OrderDisplayEvent") => notes.push(
@@ -155,18 +197,19 @@ fn lookup_result(
     source: &str,
     variant_name: &str,
     event_type: EventType,
-) -> JsValue {
-    let (transition, decision) = lookup_transition_summary(event_type);
+) -> serde_json::Value {
+    let (transition, decision, target) = lookup_transition_summary(event_type);
     let notes = lookup_notes(protocol, source, variant_name, event_type);
 
-    to_js(&serde_json::json!({
+    serde_json::json!({
         "variantName": variant_name,
         "source": source,
         "eventType": event_type_to_pascal(&event_type),
         "transition": transition,
         "decisionFromNone": decision,
+        "target": target,
         "notes": notes,
-    }))
+    })
 }
 
 /// Returns protocol configs with instruction/event→EventType mappings.
@@ -177,6 +220,7 @@ pub fn get_all_protocols() -> JsValue {
         Protocol::LimitV1,
         Protocol::LimitV2,
         Protocol::Kamino,
+        Protocol::ValueAveraging,
     ];
     let result: Vec<serde_json::Value> = protocols
         .iter()
@@ -217,25 +261,32 @@ pub fn get_all_protocols() -> JsValue {
 }
 
 /// Performs a static variant lookup against a protocol's known variant names.
+///
+/// The result carries the variant, event type, transition, decision and target status
+/// (`null` for fills and metadata-only transitions).
 #[wasm_bindgen]
 pub fn lookup_variant(protocol: &str, json: &str) -> JsValue {
+    to_js(&lookup_variant_json(protocol, json))
+}
+
+fn lookup_variant_json(protocol: &str, json: &str) -> serde_json::Value {
     let Some(proto) = parse_protocol(protocol) else {
-        return error_result("Unknown protocol");
+        return error_json("Unknown protocol");
     };
 
     let parsed: serde_json::Value = match serde_json::from_str(json) {
         Ok(v) => v,
-        Err(_) => return error_result("Invalid JSON"),
+        Err(_) => return error_json("Invalid JSON"),
     };
 
     let obj = match parsed.as_object() {
         Some(o) if !o.is_empty() => o,
-        _ => return error_result("Expected a JSON object with a variant key"),
+        _ => return error_json("Expected a JSON object with a variant key"),
     };
 
     let variant_name = match obj.keys().next() {
         Some(k) => k.clone(),
-        None => return error_result("Empty JSON object"),
+        None => return error_json("Empty JSON object"),
     };
 
     let (ix_map, ev_map, _) = variant_maps_for_protocol(proto);
@@ -258,7 +309,7 @@ pub fn lookup_variant(protocol: &str, json: &str) -> JsValue {
 
     let all_events: Vec<&str> = ev_map.iter().map(|(n, _)| *n).collect();
     let all_ix: Vec<&str> = ix_map.iter().map(|(n, _)| *n).collect();
-    error_result(&format!(
+    error_json(&format!(
         "Unknown variant \"{variant_name}\". Known events: {}. Known instructions: {}.",
         all_events.join(", "),
         all_ix.join(", ")
@@ -271,21 +322,14 @@ pub fn decide_transition(
     current_terminal: Option<String>,
     transition_type: &str,
     close_status: Option<String>,
+    reason: Option<String>,
 ) -> String {
     let terminal = current_terminal.as_deref().and_then(parse_terminal_status);
 
-    let transition = match transition_type {
-        "Create" => LifecycleTransition::Create,
-        "FillDelta" => LifecycleTransition::FillDelta,
-        "MetadataOnly" => LifecycleTransition::MetadataOnly,
-        "Close" => {
-            let status = close_status
-                .as_deref()
-                .and_then(parse_terminal_status)
-                .unwrap_or(TerminalStatus::Completed);
-            LifecycleTransition::Close { status }
-        }
-        _ => return "IgnoreTerminalViolation".to_string(),
+    let Some(transition) =
+        parse_transition(transition_type, close_status.as_deref(), reason.as_deref())
+    else {
+        return "IgnoreTerminalViolation".to_string();
     };
 
     format!(
@@ -320,32 +364,43 @@ pub fn wasm_event_type_to_transition(event_type: &str, closed_status: Option<Str
         }),
         LifecycleTransition::Create => serde_json::json!({"type": "Create"}),
         LifecycleTransition::FillDelta => serde_json::json!({"type": "FillDelta"}),
-        LifecycleTransition::MetadataOnly => serde_json::json!({"type": "MetadataOnly"}),
+        LifecycleTransition::MetadataOnly { reason } => serde_json::json!({
+            "type": "MetadataOnly",
+            "reason": reason.as_ref(),
+        }),
     };
     to_js(&obj)
 }
 
+/// Check if a base58 program id belongs to a supported protocol.
+#[wasm_bindgen]
+pub fn is_supported_program_id(program_id: &str) -> bool {
+    ProgramIdFilter::new().contains(program_id)
+}
+
 /// Check if a status string is terminal.
 #[wasm_bindgen]
 pub fn is_terminal(status: Option<String>) -> bool {
     status.as_deref().and_then(parse_terminal_status).is_some()
 }
 
+/// Get the UI display class for a terminal status string, or null.
+#[wasm_bindgen]
+pub fn wasm_terminal_display_class(status: &str) -> Option<String> {
+    parse_terminal_status(status).map(|s| s.display_class().to_string())
+}
+
 /// Get the display string for a transition.
 #[wasm_bindgen]
-pub fn wasm_transition_to_string(transition_type: &str, close_status: Option<String>) -> String {
-    let transition = match transition_type {
-        "Create" => LifecycleTransition::Create,
-        "FillDelta" => LifecycleTransition::FillDelta,
-        "MetadataOnly" => LifecycleTransition::MetadataOnly,
-        "Close" => {
-            let status = close_status
-                .as_deref()
-                .and_then(parse_terminal_status)
-                .unwrap_or(TerminalStatus::Completed);
-            LifecycleTransition::Close { status }
-        }
-        _ => return transition_type.to_string(),
+pub fn wasm_transition_to_string(
+    transition_type: &str,
+    close_status: Option<String>,
+    reason: Option<String>,
+) -> String {
+    let Some(transition) =
+        parse_transition(transition_type, close_status.as_deref(), reason.as_deref())
+    else {
+        return transition_type.to_string();
     };
     mapping::transition_to_display(&transition)
 }
@@ -355,24 +410,155 @@ pub fn wasm_transition_to_string(transition_type: &str, close_status: Option<Str
 pub fn wasm_transition_target(
     transition_type: &str,
     close_status: Option<String>,
+    reason: Option<String>,
 ) -> Option<String> {
-    let transition = match transition_type {
-        "Create" => LifecycleTransition::Create,
-        "FillDelta" => LifecycleTransition::FillDelta,
-        "MetadataOnly" => LifecycleTransition::MetadataOnly,
-        "Close" => {
-            let status = close_status
-                .as_deref()
-                .and_then(parse_terminal_status)
-                .unwrap_or(TerminalStatus::Completed);
-            LifecycleTransition::Close { status }
-        }
-        _ => return None,
-    };
+    let transition = parse_transition(transition_type, close_status.as_deref(), reason.as_deref())?;
     mapping::transition_target(&transition).map(str::to_string)
 }
 
-fn error_result(msg: &str) -> JsValue {
-    let obj = serde_json::json!({"error": msg});
-    to_js(&obj)
+/// Replays one order's `RawEvent[]` JSON and returns its final status and fill totals.
+///
+/// The result is `{finalStatus, totalIn, totalOut, ignored, errors}`; `finalStatus` is `null`
+/// while the order is not terminal and `errors` lists resolve/apply error messages.
+#[wasm_bindgen]
+pub fn replay(
+    protocol: &str,
+    events_json: &str,
+    pre_fetched_order_pdas: Option<Vec<String>>,
+) -> JsValue {
+    to_js(&replay_json(protocol, events_json, pre_fetched_order_pdas))
+}
+
+fn replay_json(
+    protocol: &str,
+    events_json: &str,
+    pre_fetched_order_pdas: Option<Vec<String>>,
+) -> serde_json::Value {
+    let Some(proto) = parse_protocol(protocol) else {
+        return error_json("Unknown protocol");
+    };
+    let events: Vec<RawEvent> = match serde_json::from_str(events_json) {
+        Ok(events) => events,
+        Err(err) => return error_json(&format!("Invalid events JSON: {err}")),
+    };
+    let ctx = ResolveContext {
+        pre_fetched_order_pdas,
+        ..ResolveContext::default()
+    };
+
+    let summary = replay_events(proto, events, ctx);
+    let fills = summary.order.fills();
+    let errors: Vec<String> = summary.errors.iter().map(ToString::to_string).collect();
+    serde_json::json!({
+        "finalStatus": summary.order.terminal().map(|status| status.to_string()),
+        "totalIn": fills.total_in,
+        "totalOut": fills.total_out,
+        "ignored": summary.ignored,
+        "errors": errors,
+    })
+}
+
+fn error_json(msg: &str) -> serde_json::Value {
+    serde_json::json!({"error": msg})
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn terminal_display_class_parses_status_strings() {
+        assert_eq!(
+            wasm_terminal_display_class("expired").as_deref(),
+            Some("warning")
+        );
+        assert_eq!(wasm_terminal_display_class("active"), None);
+    }
+
+    #[test]
+    fn parse_transition_keeps_metadata_reason() {
+        for reason in [
+            "fee",
+            "deposit",
+            "withdraw",
+            "diagnostic",
+            "close_without_status",
+        ] {
+            let transition = parse_transition("MetadataOnly", None, Some(reason)).unwrap();
+            let LifecycleTransition::MetadataOnly { reason: parsed } = transition else {
+                panic!("expected MetadataOnly for {reason}");
+            };
+            assert_eq!(parsed.as_ref(), reason);
+        }
+        assert_eq!(
+            parse_transition("MetadataOnly", None, None),
+            Some(LifecycleTransition::MetadataOnly {
+                reason: MetadataReason::Diagnostic
+            })
+        );
+        assert_eq!(
+            parse_transition("Close", Some("expired"), None),
+            Some(LifecycleTransition::Close {
+                status: TerminalStatus::Expired
+            })
+        );
+        assert_eq!(parse_transition("Bogus", None, None), None);
+    }
+
+    #[test]
+    fn lookup_variant_reports_target_status() {
+        let created = lookup_variant_json("limitV2", r#"{"CreateOrderEvent":{}}"#);
+        assert_eq!(created["transition"], "Create");
+        assert_eq!(created["target"], "active");
+
+        let trade = lookup_variant_json("limitV2", r#"{"TradeEvent":{}}"#);
+        assert_eq!(trade["transition"], "FillDelta");
+        assert!(trade["target"].is_null());
+
+        let unknown = lookup_variant_json("limitV2", r#"{"NoSuchEvent":{}}"#);
+        assert!(unknown.get("target").is_none());
+        assert!(unknown["error"].is_string());
+    }
+
+    #[test]
+    fn replay_reports_final_status_and_fill_totals() {
+        let event = |index: i32, name: &str, fields: serde_json::Value| {
+            serde_json::json!({
+                "id": index,
+                "signature": format!("sig{index}"),
+                "event_index": index,
+                "program_id": protocols::DCA_PROGRAM_ID,
+                "inner_program_id": protocols::DCA_PROGRAM_ID,
+                "event_name": name,
+                "fields": { name: fields },
+                "slot": 100 + i64::from(index),
+            })
+        };
+        let events = serde_json::json!([
+            event(0, "OpenedEvent", serde_json::json!({ "dca_key": "pda" })),
+            event(
+                1,
+                "FilledEvent",
+                serde_json::json!({ "dca_key": "pda", "in_amount": 100, "out_amount": 40 }),
+            ),
+            event(
+                2,
+                "ClosedEvent",
+                serde_json::json!({ "dca_key": "pda", "user_closed": false, "unfilled_amount": 0 }),
+            ),
+        ]);
+
+        let result = replay_json("dca", &events.to_string(), None);
+        assert_eq!(
+            result,
+            serde_json::json!({
+                "finalStatus": "completed",
+                "totalIn": 100,
+                "totalOut": 40,
+                "ignored": 0,
+                "errors": [],
+            })
+        );
+        assert!(replay_json("dca", "not json", None)["error"].is_string());
+    }
 }
@@ -4,9 +4,11 @@
     reason = "test code uses unwrap/panic for concise assertions"
 )]
 
+use defi_tracker_lifecycle::protocols::kamino::KaminoAdapter;
 use defi_tracker_lifecycle::{
-    CorrelationOutcome, EventPayload, EventType, LifecycleEngine, LifecycleTransition, Protocol,
-    RawEvent, RawInstruction, ResolveContext, TerminalStatus, TransitionDecision, adapter_for,
+    CorrelationOutcome, EventPayload, EventType, LifecycleEngine, LifecycleTransition,
+    MetadataReason, Protocol, RawEvent, RawInstruction, ResolveContext, TerminalStatus,
+    TransitionDecision, TypedEvent, adapter_for, resolve_transaction,
 };
 
 fn load_instructions(filename: &str) -> Vec<RawInstruction> {
@@ -38,9 +40,7 @@ fn resolve_event_from_fixture(
 }
 
 fn no_context() -> ResolveContext {
-    ResolveContext {
-        pre_fetched_order_pdas: None,
-    }
+    ResolveContext::default()
 }
 
 #[test]
@@ -165,12 +165,48 @@ fn dca_resolve_fill_event_extracts_amounts() {
     let EventPayload::DcaFill {
         in_amount,
         out_amount,
+        saturated,
+        cycle,
     } = payload
     else {
         panic!("expected DcaFill, got {payload:?}");
     };
     assert!(in_amount > 0, "in_amount should be positive");
     assert!(out_amount > 0, "out_amount should be positive");
+    assert!(!saturated);
+    assert_eq!(cycle, None);
+}
+
+#[test]
+fn dca_fill_fixture_builds_typed_event() {
+    let events = load_events("dca_events.json");
+    let ev = events
+        .iter()
+        .find(|ev| ev.event_name == "FilledEvent")
+        .unwrap();
+
+    let typed = TypedEvent::from_raw(Protocol::Dca, ev, &no_context())
+        .unwrap()
+        .unwrap();
+    let TypedEvent::DcaFilled(fill) = &typed else {
+        panic!("expected DcaFilled, got {typed:?}");
+    };
+
+    let (_, correlation, payload) = resolve_event_from_fixture(Protocol::Dca, ev, &no_context());
+    assert_eq!(
+        correlation,
+        CorrelationOutcome::Correlated(vec![fill.order_pda.clone()])
+    );
+    let EventPayload::DcaFill {
+        in_amount,
+        out_amount,
+        ..
+    } = payload
+    else {
+        panic!("expected DcaFill, got {payload:?}");
+    };
+    assert_eq!((fill.in_amount, fill.out_amount), (in_amount, out_amount));
+    assert_eq!(typed.protocol(), Protocol::Dca);
 }
 
 #[test]
@@ -194,7 +230,9 @@ fn dca_resolve_closed_completed() {
     assert_eq!(
         payload,
         EventPayload::DcaClosed {
-            status: TerminalStatus::Completed
+            status: TerminalStatus::Completed,
+            total_in_withdrawn: 0,
+            total_out_withdrawn: 1_279_067_332_474,
         }
     );
 }
@@ -219,15 +257,67 @@ fn dca_resolve_closed_user_cancelled() {
     assert_eq!(
         payload,
         EventPayload::DcaClosed {
-            status: TerminalStatus::Cancelled
+            status: TerminalStatus::Cancelled,
+            total_in_withdrawn: 1_400_000_000,
+            total_out_withdrawn: 9_737_035_287_242_015,
+        }
+    );
+}
+
+#[test]
+fn dca_resolve_opened_event_extracts_schedule() {
+    let events = load_events("dca_events.json");
+    let ev = events
+        .iter()
+        .find(|ev| ev.event_name == "OpenedEvent")
+        .unwrap();
+
+    let (event_type, correlation, payload) =
+        resolve_event_from_fixture(Protocol::Dca, ev, &no_context());
+
+    assert_eq!(event_type, EventType::Created);
+    assert!(matches!(correlation, CorrelationOutcome::Correlated(_)));
+    assert_eq!(
+        payload,
+        EventPayload::DcaCreated {
+            cycle_frequency: 60,
+            in_amount_per_cycle: 403_006_662_386,
+            in_deposited: 2_015_033_311_928,
+            saturated: false,
+            dca_version: 1,
         }
     );
 }
 
+#[test]
+fn dca_opened_event_versions_are_tagged() {
+    let events = load_events("dca_opened_events.json");
+    let versions: Vec<(String, u8)> = events
+        .iter()
+        .map(|ev| {
+            let (event_type, _, payload) =
+                resolve_event_from_fixture(Protocol::Dca, ev, &no_context());
+            assert_eq!(event_type, EventType::Created);
+            let EventPayload::DcaCreated { dca_version, .. } = payload else {
+                panic!("expected DcaCreated payload for event {}", ev.id);
+            };
+            (ev.signature.clone(), dca_version)
+        })
+        .collect();
+
+    assert_eq!(
+        versions,
+        vec![
+            ("dcaOpenV1Signature".to_string(), 1),
+            ("dcaOpenV2Signature".to_string(), 2),
+        ]
+    );
+}
+
 #[test]
 fn dca_resolve_other_events_produce_none_payload() {
     let events = load_events("dca_events.json");
-    let other_event_names = ["OpenedEvent", "CollectedFeeEvent", "WithdrawEvent"];
+    let other_event_names = ["CollectedFeeEvent", "WithdrawEvent"];
 
     for name in other_event_names {
         let ev = events
@@ -277,12 +367,58 @@ fn kamino_classify_instructions_from_fixture() {
     }
 }
 
+#[test]
+fn kamino_multi_order_transaction_correlates_each_event_to_one_pda() {
+    let events = load_events("kamino_multi_order_events.json");
+    let ctx = ResolveContext {
+        pre_fetched_order_pdas: Some(vec![
+            "KaminoOrderA".to_string(),
+            "KaminoOrderB".to_string(),
+            "KaminoOrderC".to_string(),
+        ]),
+        ..ResolveContext::default()
+    };
+
+    let correlations: Vec<CorrelationOutcome> =
+        KaminoAdapter::resolve_transaction_events(&events, &ctx)
+            .into_iter()
+            .map(|resolved| resolved.unwrap().unwrap().1)
+            .collect();
+
+    // Fixture order is event_index 5, 1, 3; index 3 names its own order.
+    assert_eq!(
+        correlations,
+        vec![
+            CorrelationOutcome::Correlated(vec!["KaminoOrderC".to_string()]),
+            CorrelationOutcome::Correlated(vec!["KaminoOrderA".to_string()]),
+            CorrelationOutcome::Correlated(vec!["KaminoOrderB".to_string()]),
+        ]
+    );
+
+    // Without a one-to-one match, every order-less event keeps the full list.
+    let extra_pdas = vec![
+        "KaminoOrderA".to_string(),
+        "KaminoOrderC".to_string(),
+        "KaminoOrderD".to_string(),
+    ];
+    let ambiguous = ResolveContext {
+        pre_fetched_order_pdas: Some(extra_pdas.clone()),
+        ..ResolveContext::default()
+    };
+    let (_, correlation, _) = KaminoAdapter::resolve_transaction_events(&events, &ambiguous)
+        .swap_remove(0)
+        .unwrap()
+        .unwrap();
+    assert_eq!(correlation, CorrelationOutcome::Correlated(extra_pdas));
+}
+
 #[test]
 fn kamino_classify_events_from_fixture() {
     let events = load_events("kamino_events.json");
     let pda = "FakeOrderPda".to_string();
     let ctx = ResolveContext {
         pre_fetched_order_pdas: Some(vec![pda]),
+        ..ResolveContext::default()
     };
 
     for ev in &events {
@@ -303,6 +439,7 @@ fn kamino_resolve_display_event_with_pdas() {
     let pda = "FakeOrderPda1111111111111111111111111111111".to_string();
     let ctx = ResolveContext {
         pre_fetched_order_pdas: Some(vec![pda.clone()]),
+        ..ResolveContext::default()
     };
 
     let (_event_type, correlation, payload) =
@@ -314,6 +451,7 @@ fn kamino_resolve_display_event_with_pdas() {
         remaining_input_amount,
         filled_output_amount,
         terminal_status,
+        saturated,
     } = payload
     else {
         panic!("expected KaminoDisplay, got {payload:?}");
@@ -321,6 +459,7 @@ fn kamino_resolve_display_event_with_pdas() {
     assert!(remaining_input_amount >= 0);
     assert!(filled_output_amount >= 0);
     assert!(terminal_status.is_some(), "status 2 should be terminal");
+    assert!(!saturated);
 }
 
 #[test]
@@ -338,6 +477,37 @@ fn kamino_resolve_display_event_without_pdas() {
     assert_eq!(payload, EventPayload::None);
 }
 
+#[test]
+fn kamino_display_event_with_order_field_self_correlates() {
+    let events = load_events("kamino_events.json");
+    let ev = events
+        .iter()
+        .find(|ev| {
+            ev.fields
+                .as_ref()
+                .and_then(|f| f.get("OrderDisplayEvent"))
+                .is_some_and(|inner| inner.get("order").is_some())
+        })
+        .expect("fixture should contain an order-bearing OrderDisplayEvent");
+    let order = "HT3nVyFDjyQfD1T128nZyTK2xVLFdffPE68R29XF7cb7".to_string();
+
+    let (event_type, correlation, payload) =
+        resolve_event_from_fixture(Protocol::Kamino, ev, &no_context());
+    assert_eq!(event_type, EventType::FillCompleted);
+    assert_eq!(
+        correlation,
+        CorrelationOutcome::Correlated(vec![order.clone()])
+    );
+    assert!(matches!(payload, EventPayload::KaminoDisplay { .. }));
+
+    let ctx = ResolveContext {
+        pre_fetched_order_pdas: Some(vec!["OtherPda".to_string()]),
+        ..ResolveContext::default()
+    };
+    let (_, correlation, _) = resolve_event_from_fixture(Protocol::Kamino, ev, &ctx);
+    assert_eq!(correlation, CorrelationOutcome::Correlated(vec![order]));
+}
+
 #[test]
 fn kamino_malformed_known_event_surfaces_error() {
     let adapter = adapter_for(Protocol::Kamino);
@@ -353,11 +523,49 @@ fn kamino_malformed_known_event_surfaces_error() {
     );
     let ctx = ResolveContext {
         pre_fetched_order_pdas: Some(vec!["pda".to_string()]),
+        ..ResolveContext::default()
     };
     let result = adapter.classify_and_resolve_event(&ev, &ctx);
     assert!(matches!(result, Some(Err(_))));
 }
 
+#[test]
+fn resolve_transaction_correlates_kamino_display_event_from_sibling_instruction() {
+    let events = load_events("kamino_events.json");
+    let create_event = events
+        .iter()
+        .find(|ev| ev.signature.starts_with("4CbAE6jt"))
+        .unwrap();
+    let instructions: Vec<RawInstruction> = load_instructions("kamino_instructions.json")
+        .into_iter()
+        .filter(|ix| ix.signature == create_event.signature)
+        .collect();
+    assert_eq!(
+        KaminoAdapter::collect_order_pdas(&instructions),
+        vec!["E7ytLXK3NttMUfwgW6jeF7Qsindbue5tewAAimZ6niMu".to_string()]
+    );
+
+    let resolved = resolve_transaction(&instructions, std::slice::from_ref(create_event));
+
+    assert_eq!(resolved.len(), 1);
+    let (protocol, result) = &resolved[0];
+    assert_eq!(*protocol, Protocol::Kamino);
+    let (_, correlation, payload) = result.as_ref().unwrap();
+    assert_eq!(
+        *correlation,
+        CorrelationOutcome::Correlated(vec![
+            "E7ytLXK3NttMUfwgW6jeF7Qsindbue5tewAAimZ6niMu".to_string()
+        ])
+    );
+    assert!(matches!(payload, EventPayload::KaminoDisplay { .. }));
+
+    let without_instructions = resolve_transaction(&[], std::slice::from_ref(create_event));
+    assert!(matches!(
+        without_instructions[0].1,
+        Ok((_, CorrelationOutcome::Uncorrelated { .. }, _))
+    ));
+}
+
 // ──────────────────── Limit V1 ────────────────────
 
 #[test]
@@ -418,6 +626,7 @@ fn limit_v1_resolve_trade_event_extracts_fill() {
         out_amount,
         remaining_in_amount,
         counterparty,
+        ..
     } = &payload
     else {
         panic!("expected LimitFill, got {payload:?}");
@@ -525,6 +734,7 @@ fn limit_v2_resolve_trade_event_extracts_fill() {
         out_amount,
         remaining_in_amount,
         counterparty,
+        ..
     } = &payload
     else {
         panic!("expected LimitFill, got {payload:?}");
@@ -582,7 +792,7 @@ fn event_type_to_transition(event_type: &EventType, payload: &EventPayload) -> L
         },
         EventType::Closed => {
             let status = match payload {
-                EventPayload::DcaClosed { status } => *status,
+                EventPayload::DcaClosed { status, .. } | EventPayload::Closed { status } => *status,
                 EventPayload::KaminoDisplay {
                     terminal_status: Some(s),
                     ..
@@ -591,9 +801,12 @@ fn event_type_to_transition(event_type: &EventType, payload: &EventPayload) -> L
             };
             LifecycleTransition::Close { status }
         }
-        EventType::FeeCollected | EventType::Withdrawn | EventType::Deposited => {
-            LifecycleTransition::MetadataOnly
-        }
+        EventType::FeeCollected => metadata(MetadataReason::Fee),
+        EventType::Withdrawn => metadata(MetadataReason::Withdraw),
+        EventType::Deposited => metadata(MetadataReason::Deposit),
+        EventType::Diagnostic => metadata(MetadataReason::Diagnostic),
+        // `EventType` is `#[non_exhaustive]`: downstream treats unknown variants as metadata.
+        _ => metadata(MetadataReason::Diagnostic),
     }
 }
 
@@ -603,11 +816,15 @@ fn event_to_transition(
     payload: &EventPayload,
 ) -> LifecycleTransition {
     if matches!(correlation, CorrelationOutcome::NotRequired) {
-        return LifecycleTransition::MetadataOnly;
+        return metadata(MetadataReason::Diagnostic);
     }
     event_type_to_transition(event_type, payload)
 }
 
+fn metadata(reason: MetadataReason) -> LifecycleTransition {
+    LifecycleTransition::MetadataOnly { reason }
+}
+
 fn make_event(name: &str, fields: serde_json::Value) -> RawEvent {
     RawEvent {
         id: 1,
@@ -649,36 +866,32 @@ impl LifecycleState {
             .unwrap_or_else(|e| panic!("resolve failed: {e}"));
 
         let transition = event_to_transition(&event_type, &correlation, &payload);
-        let decision = LifecycleEngine::decide_transition(self.terminal_status(), transition);
-
-        if decision == TransitionDecision::Apply {
-            self.status = match transition {
-                LifecycleTransition::Create => Some("created".to_string()),
-                LifecycleTransition::FillDelta => self.status.take().or(Some("active".to_string())),
-                LifecycleTransition::Close { status } => Some(status.to_string()),
-                LifecycleTransition::MetadataOnly => self.status.take(),
-            };
-        }
-
-        decision
+        self.apply_transition(transition)
     }
 
     fn apply_instruction(&mut self, ix: &RawInstruction) -> Option<TransitionDecision> {
         let adapter = adapter_for(self.protocol);
         let event_type = adapter.classify_instruction(ix)?;
         let transition = event_type_to_transition(&event_type, &EventPayload::None);
-        let decision = LifecycleEngine::decide_transition(self.terminal_status(), transition);
+        Some(self.apply_transition(transition))
+    }
+
+    fn apply_transition(&mut self, transition: LifecycleTransition) -> TransitionDecision {
+        let (decision, next_terminal) =
+            LifecycleEngine::next_state(self.terminal_status(), transition);
 
         if decision == TransitionDecision::Apply {
-            self.status = match transition {
-                LifecycleTransition::Create => Some("created".to_string()),
-                LifecycleTransition::FillDelta => self.status.take().or(Some("active".to_string())),
-                LifecycleTransition::Close { status } => Some(status.to_string()),
-                LifecycleTransition::MetadataOnly => self.status.take(),
+            self.status = match (next_terminal, transition) {
+                (Some(status), _) => Some(status.to_string()),
+                (None, LifecycleTransition::Create) => Some("created".to_string()),
+                (None, LifecycleTransition::FillDelta) => {
+                    self.status.take().or(Some("active".to_string()))
+                }
+                (None, _) => self.status.take(),
             };
         }
 
-        Some(decision)
+        decision
     }
 }
 